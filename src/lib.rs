@@ -4,6 +4,7 @@
 //! a parser/writer.
 //! The symbolic module contains functionality of storing and manipulating the symbolic math
 //! expressions.
+//! The polynomial module contains a sparse representation of multivariate polynomials.
 
 #![cfg_attr(check_doc, deny(missing_docs))]
 #![feature(tool_lints)]
@@ -20,6 +21,7 @@ extern crate lazy_static;
 extern crate itertools;
 
 pub mod sym;
+pub mod poly;
 
 #[macro_use]
 extern crate cfg_if;
@@ -1,31 +1,38 @@
 //! Tools for parsing expressions.
 
-use crate::sym::Expr;
+use crate::sym::{Constant, Equation, Expr, Func, Function};
 use num::BigInt;
-use nom::{digit, float_s, IResult, Err};
+use nom::{digit, recognize_float, IResult, Err};
+use nom::types::CompleteStr;
 use nom::*;
 use std::result;
 use std::str::{self, FromStr};
 
-/// fn(&str)-> IResult<&str, Expr>
+/// fn(CompleteStr)-> IResult<CompleteStr, Expr>
 /// integer := "-" ? digit
-named!(integer<&str, Expr>,
+named!(integer<CompleteStr, Expr>,
        map!(
            map_res!(
                recognize!(pair!(
-                   opt!(tag_s!("-")),
+                   opt!(tag!("-")),
                    digit
                )),
-               BigInt::from_str
+               |s: CompleteStr| BigInt::from_str(s.0)
            ),
            Expr::integer
        ));
 
-/// fn(&str)-> IResult<&str, Expr>
-/// Parses a float.
-named!(float_e<&str, Expr>,
+/// fn(CompleteStr)-> IResult<CompleteStr, Expr>
+/// Parses a float, which has a decimal point or an exponent.
+named!(float_e<CompleteStr, Expr>,
        map!(
-           float_s,
+           map_res!(
+               verify!(
+                   recognize_float,
+                   |s: CompleteStr| s.contains(|c| c == '.' || c == 'e' || c == 'E')
+               ),
+               |s: CompleteStr| f64::from_str(s.0)
+           ),
            Expr::approximate
        ));
 
@@ -38,31 +45,79 @@ const NUM: &str = "0123456789";
 const IDENT: &str = "_ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
 /// Characters vaild for start of symbol name.
-named!(alphabet<&str, char>,
+named!(alphabet<CompleteStr, char>,
        alt!(
            one_of!(IDENT) |
            one_of!(GREEK)
            ));
 
 /// Characters vaild for rest of symbol name.
-named!(alphabet_or_num<&str, char>,
+named!(alphabet_or_num<CompleteStr, char>,
        alt!(
            alphabet |
            one_of!(NUM)
            ));
 
-/// symbol := alphabet alphabet_or_num *
-named!(symbol<&str, Expr>,
-       map!(
-           recognize!(pair!(
-               alphabet,
-               many0!(alphabet_or_num)
-           )),
-           Expr::symbol
+/// name := alphabet alphabet_or_num *
+named!(name<CompleteStr, CompleteStr>,
+       recognize!(pair!(
+           alphabet,
+           many0!(alphabet_or_num)
+       )));
+
+/// The constant with the name, or the symbol.
+fn symbol_or_constant(name: &str) -> Expr {
+    match name {
+        "pi" | "π" => Constant::Pi.into(),
+        "e" => Constant::E.into(),
+        "i" => Constant::ImaginaryUnit.into(),
+        "oo" => Constant::Infinity.into(),
+        name => Expr::symbol(name),
+    }
+}
+
+/// symbol := "∞" | name
+///
+/// The names `pi`, `π`, `e`, `i` and `oo` are constants.
+named!(symbol<CompleteStr, Expr>,
+       alt_complete!(
+           value!(Constant::Infinity.into(), tag!("∞")) |
+           map!(
+               name,
+               |s: CompleteStr| symbol_or_constant(s.0)
+           )
+       ));
+
+/// The error code of a call which has an opening parenthesis but is malformed.
+const CALL_ERROR: u32 = 1;
+
+/// call := name "(" expr ("," expr) * ")"
+///
+/// Names which aren't built-in functions are undefined functions. Once the opening parenthesis
+/// is found, an unclosed call or a wrong number of arguments for a built-in function is an error.
+named!(call<CompleteStr, Expr>,
+       do_parse!(
+           name: ws!(name) >>
+           ws!(tag!("(")) >>
+           e: return_error!(
+               ErrorKind::Custom(CALL_ERROR),
+               map_opt!(
+                   ws!(terminated!(
+                       separated_nonempty_list!(tag!(","), complete_expr),
+                       tag!(")")
+                   )),
+                   |args: Vec<Expr>| {
+                       let f = Func::from_name(name.0)
+                           .unwrap_or_else(|| Func::Undef(Function::new(name.0), vec![]));
+                       Expr::apply(f, args)
+                   }
+               )
+           ) >>
+           (e)
        ));
 
 /// float, integer or symbol.
-named!(primitive<&str, Expr>,
+named!(primitive<CompleteStr, Expr>,
        alt_complete!(
            float_e |
            integer |
@@ -70,44 +125,45 @@ named!(primitive<&str, Expr>,
        ));
 
 /// Start a new parsing within parentheses.
-named!(parens<&str, Expr>,
+named!(parens<CompleteStr, Expr>,
        ws!(delimited!(
-               tag_s!("("),
-               expr,
-               tag_s!(")")
+               tag!("("),
+               complete_expr,
+               tag!(")")
             ))
        );
 
-/// unit := primitive | parens
-named!(unit<&str, Expr>,
+/// unit := call | primitive | parens
+named!(unit<CompleteStr, Expr>,
        alt_complete!(
+           call |
            ws!(primitive) |
            parens
        ));
 
 /// pow := unit "^" negative
-named!(pow<&str, Expr>,
+named!(pow<CompleteStr, Expr>,
        ws!(alt_complete!(
            do_parse!(
                left: unit >>
-               tag_s!("^") >>
+               tag!("^") >>
                right: negative >>
                (Expr::Pow(Box::new(left), Box::new(right)))) |
            unit
        )));
 
 /// negative := pow | "-" pow
-named!(negative<&str, Expr>,
+named!(negative<CompleteStr, Expr>,
        ws!(alt_complete!(
            pow |
            do_parse!(
-               tag_s!("-") >> e: pow >>
-               (Expr::Neg(Box::new(e)))
+               tag!("-") >> e: pow >>
+               (- e)
            )
        )));
 
 /// product := negative (("*" | "/") negative) *
-named!(product<&str, Expr>, do_parse!(
+named!(product<CompleteStr, Expr>, do_parse!(
        init: negative >>
        res: fold_many0!(
            pair!(one_of!("*/"),
@@ -130,7 +186,7 @@ named!(product<&str, Expr>, do_parse!(
    ));
 
 /// sum := product (("+" | "-") product) *
-named!(sum<&str, Expr>, do_parse!(
+named!(sum<CompleteStr, Expr>, do_parse!(
        init: product >>
        res: fold_many0!(
            pair!(one_of!("+-"),
@@ -150,24 +206,77 @@ named!(sum<&str, Expr>, do_parse!(
        (res)
    ));
 
-pub fn expr(input: &str) -> IResult<&str, Expr> {
+/// An expression, where the end of the input ends it.
+fn complete_expr(input: CompleteStr) -> IResult<CompleteStr, Expr> {
     sum(input)
 }
 
+/// Parse an expression at the start of the input, and return the rest of it.
+pub fn expr(input: &str) -> IResult<&str, Expr> {
+    complete_expr(CompleteStr(input))
+        .map(|(rest, o)| (rest.0, o))
+        .map_err(str_err)
+}
+
+/// An expression which is the whole input.
+named!(whole_expr<CompleteStr, Expr>, ws!(terminated!(complete_expr, eof!())));
+
+/// equation := expr "=" expr
+///
+/// The equation must be the whole input.
+named!(equation<CompleteStr, Equation>,
+       ws!(do_parse!(
+           lhs: complete_expr >>
+           tag!("=") >>
+           rhs: complete_expr >>
+           eof!() >>
+           (Equation::new(lhs, rhs))
+       )));
+
 pub type Result<'a, T> = result::Result<T, Err<&'a str>>;
 
-/// Parse an expression.
+/// Convert an error on complete input to one on the input string.
+fn str_err(e: Err<CompleteStr<'_>>) -> Err<&str> {
+    fn context(c: Context<CompleteStr<'_>>) -> Context<&str> {
+        let Context::Code(i, kind) = c;
+        Context::Code(i.0, kind)
+    }
+    match e {
+        Err::Incomplete(n) => Err::Incomplete(n),
+        Err::Error(c) => Err::Error(context(c)),
+        Err::Failure(c) => Err::Failure(context(c)),
+    }
+}
+
+/// Parse an expression, which must be the whole input.
 ///
 /// # Examples
 /// ```
-/// use symrs::sym::Symbol;
+/// use symrs::sym::{Expr, Symbol};
 /// use symrs::par::parse_expr;
 ///
 /// let x = Symbol::new("x");
 /// assert_eq!(parse_expr("1 + (7 - x)").unwrap(), 1 + (7 - x));
+/// assert_eq!(parse_expr("sin(x)^2").unwrap(), Expr::sin(x).pow(2));
+/// assert!(parse_expr("sin(x").is_err());
 /// ```
 pub fn parse_expr(input: &str) -> Result<Expr> {
-    expr(input).map(|(_, o)| o)
+    whole_expr(CompleteStr(input)).map(|(_, o)| o).map_err(str_err)
+}
+
+/// Parse an equation `lhs = rhs`, which must be the whole input.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Equation, Symbol};
+/// use symrs::par::parse_equation;
+///
+/// let x = Symbol::new("x");
+/// assert_eq!(parse_equation("x^2 = 2 * x").unwrap(), Equation::new(x.pow(2), 2i32 * x));
+/// assert!(parse_equation("x = 1 = 2").is_err());
+/// ```
+pub fn parse_equation(input: &str) -> Result<Equation> {
+    equation(CompleteStr(input)).map(|(_, o)| o).map_err(str_err)
 }
 
 #[cfg(test)]
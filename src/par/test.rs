@@ -1,81 +1,85 @@
 use nom::IResult;
+use nom::types::CompleteStr;
 use crate::sym::Symbol;
 use super::*;
 
 // Just to assume the types are right.
-const _ASSERT_TYPE: &[fn(&str) -> IResult<&str, Expr>] = &[
+const _ASSERT_TYPE: &[fn(CompleteStr) -> IResult<CompleteStr, Expr>] = &[
     super::integer,
     super::float_e,
     super::symbol,
+    super::call,
     super::primitive,
     super::pow,
     super::negative,
     super::product,
-    super::expr,
+    super::complete_expr,
 ];
 
+const _ASSERT_EXPR_TYPE: fn(&str) -> IResult<&str, Expr> = super::expr;
+
 macro_rules! assert_floats {
     ($parser:expr) => {
-        assert_finished_and_near!($parser("123.3"), 123.3);
-        assert_finished_and_near!($parser("123.3e3"), 123.3e3);
-        assert_finished_and_near!($parser("-123.3"), -123.3);
-        assert_finished_and_near!($parser("-123.3e3"), -123.3e3);
+        assert_finished_and_near!($parser(CompleteStr("123.3")), 123.3);
+        assert_finished_and_near!($parser(CompleteStr("123.3e3")), 123.3e3);
+        assert_finished_and_near!($parser(CompleteStr("-123.3")), -123.3);
+        assert_finished_and_near!($parser(CompleteStr("-123.3e3")), -123.3e3);
     }
 }
 
 macro_rules! assert_integers {
     ($parser:expr) => {
-        // assert_finished_and_eq!($parser("123"), Expr::integer(123));
-        // assert_finished_and_eq!($parser("-123"), Expr::integer(-123));
+        assert_eq!($parser(CompleteStr("123")), Ok((CompleteStr(""), Expr::integer(123))));
+        assert_eq!($parser(CompleteStr("-123")), Ok((CompleteStr(""), Expr::integer(-123))));
     }
 }
 
 macro_rules! assert_symbols {
     ($parser:expr) => {
-        // assert_finished_and_eq!($parser("x"), Expr::symbol("x"));
-        // assert_finished_and_eq!($parser("x1"), Expr::symbol("x1"));
-        // assert_finished_and_eq!($parser("_x"), Expr::symbol("_x"));
-        // assert_finished_and_eq!($parser("x_1"), Expr::symbol("x_1"));
-        // assert_finished_and_eq!($parser("α_1"), Expr::symbol("α_1"));
+        assert_eq!($parser(CompleteStr("x")), Ok((CompleteStr(""), Expr::symbol("x"))));
+        assert_eq!($parser(CompleteStr("x1")), Ok((CompleteStr(""), Expr::symbol("x1"))));
+        assert_eq!($parser(CompleteStr("_x")), Ok((CompleteStr(""), Expr::symbol("_x"))));
+        assert_eq!($parser(CompleteStr("x_1")), Ok((CompleteStr(""), Expr::symbol("x_1"))));
+        assert_eq!($parser(CompleteStr("α_1")), Ok((CompleteStr(""), Expr::symbol("α_1"))));
     }
 }
 
 macro_rules! assert_negtive {
     ($parser:expr) => {{
         let x = Symbol::new("x");
-        // assert_finished_and_eq!($parser("-x"), - x);
-        // assert_finished_and_eq!($parser("- x"), - x);
-        // assert_finished_and_eq!($parser("-(x)"), - x);
-        // assert_finished_and_eq!($parser("-(12)"), - Expr::integer(12));
+        assert_eq!($parser(CompleteStr("-x")), Ok((CompleteStr(""), - x)));
+        assert_eq!($parser(CompleteStr("- x")), Ok((CompleteStr(""), - x)));
+        assert_eq!($parser(CompleteStr("-(x)")), Ok((CompleteStr(""), - x)));
+        assert_eq!($parser(CompleteStr("-(12)")), Ok((CompleteStr(""), - Expr::integer(12))));
     }}
 }
 
 macro_rules! assert_pow {
     ($parser:expr) => {{
         let x = Symbol::new("x");
-        // assert_finished_and_eq!($parser("x^x"), x.pow(x));
-        // assert_finished_and_eq!($parser("x ^ x"), x.pow(x));
-        // assert_finished_and_eq!($parser("x ^ x^ x"), x.pow(x.pow(x)));
-        // assert_finished_and_eq!($parser("x ^x   ^ x"), x.pow(x.pow(x)));
-        // assert_finished_and_eq!($parser("(x ^ x) ^ x"), x.pow(x).pow(x));
-        // assert_finished_and_eq!($parser("(x ^ 2) ^ x"), x.pow(2).pow(x));
-        // // println!("{:?}", $parser("(x ^ -2) ^ - x"));
-        // assert_finished_and_eq!($parser("(x ^ -2) ^ - x"), x.pow(-2).pow(- x));
+        assert_eq!($parser(CompleteStr("x^x")), Ok((CompleteStr(""), x.pow(x))));
+        assert_eq!($parser(CompleteStr("x ^ x")), Ok((CompleteStr(""), x.pow(x))));
+        assert_eq!($parser(CompleteStr("x ^ x^ x")), Ok((CompleteStr(""), x.pow(x.pow(x)))));
+        assert_eq!($parser(CompleteStr("x ^x   ^ x")), Ok((CompleteStr(""), x.pow(x.pow(x)))));
+        assert_eq!($parser(CompleteStr("(x ^ x) ^ x")), Ok((CompleteStr(""), x.pow(x).pow(x))));
+        assert_eq!($parser(CompleteStr("(x ^ 2) ^ x")), Ok((CompleteStr(""), x.pow(2).pow(x))));
+        // println!("{:?}", $parser(CompleteStr("(x ^ -2) ^ - x")));
+        assert_eq!($parser(CompleteStr("(x ^ -2) ^ - x")), Ok((CompleteStr(""), x.pow(-2).pow(- x))));
     }}
 }
 
 macro_rules! assert_product {
     ($parser:expr) => {{
         let x = Symbol::new("x");
-        // assert_finished_and_eq!($parser("x*x"), x * x);
-        // assert_finished_and_eq!($parser("x * x"), x * x);
-        // assert_finished_and_eq!($parser("x * x* x"), x * x * x);
-        // assert_finished_and_eq!($parser("x *x *x"), x * x * x);
-        // assert_finished_and_eq!($parser("x *x *-x"), x * x * - x);
-        // assert_finished_and_eq!($parser("x / x/x"), x / x / x);
-        // assert_finished_and_eq!($parser("x / x * x"), x / x * x);
-        // assert_finished_and_eq!($parser("x / (x * x)"), x / (x * x));
-        // assert_finished_and_eq!($parser("4 / (x * x)"), 4 / (x * x));
+        assert_eq!($parser(CompleteStr("x*x")), Ok((CompleteStr(""), x * x)));
+        assert_eq!($parser(CompleteStr("x * x")), Ok((CompleteStr(""), x * x)));
+        assert_eq!($parser(CompleteStr("x * x* x")), Ok((CompleteStr(""), x * x * x)));
+        assert_eq!($parser(CompleteStr("x *x *x")), Ok((CompleteStr(""), x * x * x)));
+        assert_eq!($parser(CompleteStr("x *x *-x")), Ok((CompleteStr(""), x * x * - x)));
+        assert_eq!($parser(CompleteStr("x / x/x")), Ok((CompleteStr(""), x / x / x)));
+        assert_eq!($parser(CompleteStr("x / x * x")), Ok((CompleteStr(""), x / x * x)));
+        assert_eq!($parser(CompleteStr("x / (x * x)")), Ok((CompleteStr(""), x / (x * x))));
+        assert_eq!($parser(CompleteStr("4 / (x * x)")), Ok((CompleteStr(""), 4 / (x * x))));
     }}
 }
 
@@ -94,6 +98,19 @@ fn symbol_works() {
     assert_symbols!(symbol);
 }
 
+#[test]
+fn constant_works() {
+    assert_eq!(symbol(CompleteStr("pi")), Ok((CompleteStr(""), Expr::pi())));
+    assert_eq!(symbol(CompleteStr("π")), Ok((CompleteStr(""), Expr::pi())));
+    assert_eq!(symbol(CompleteStr("e")), Ok((CompleteStr(""), Expr::e())));
+    assert_eq!(symbol(CompleteStr("i")), Ok((CompleteStr(""), Expr::i())));
+    assert_eq!(symbol(CompleteStr("oo")), Ok((CompleteStr(""), Expr::infinity())));
+    assert_eq!(symbol(CompleteStr("∞")), Ok((CompleteStr(""), Expr::infinity())));
+    assert_eq!(symbol(CompleteStr("pi2")), Ok((CompleteStr(""), Expr::symbol("pi2"))));
+    assert_eq!(complete_expr(CompleteStr("2 * pi")), Ok((CompleteStr(""), 2i32 * Expr::pi())));
+    assert_eq!(complete_expr(CompleteStr("e^x")), Ok((CompleteStr(""), Expr::e().pow(Expr::symbol("x")))));
+}
+
 #[test]
 fn primitive_works() {
     assert_integers!(primitive);
@@ -130,33 +147,76 @@ fn product_works() {
 
 #[test]
 fn parse_works() {
-    assert_integers!(expr);
-    assert_floats!(expr);
-    assert_symbols!(expr);
-    assert_pow!(expr);
-    assert_negtive!(expr);
-    assert_product!(expr);
+    assert_integers!(complete_expr);
+    assert_floats!(complete_expr);
+    assert_symbols!(complete_expr);
+    assert_pow!(complete_expr);
+    assert_negtive!(complete_expr);
+    assert_product!(complete_expr);
 
-    // assert_finished_and_eq!(expr("(123)"), Expr::integer(123));
-    // assert_finished_and_eq!(expr("( 123 )"), Expr::integer(123));
-    // assert_finished_and_eq!(expr("((123))"), Expr::integer(123));
+    assert_eq!(complete_expr(CompleteStr("(123)")), Ok((CompleteStr(""), Expr::integer(123))));
+    assert_eq!(complete_expr(CompleteStr("( 123 )")), Ok((CompleteStr(""), Expr::integer(123))));
+    assert_eq!(complete_expr(CompleteStr("((123))")), Ok((CompleteStr(""), Expr::integer(123))));
 
     let x = Symbol::new("x");
     let y = Symbol::new("y");
-    // assert_finished_and_eq!(expr("2*x"), 2i32 * x);
-    // assert_finished_and_eq!(expr("2 * x"), 2i32 * x);
-    // assert_finished_and_eq!(expr("x *x"), x * x);
-    // assert_finished_and_eq!(expr("2 * x * 5"), 2 * x * 5);
-    // assert_finished_and_eq!(expr("2 * x / y"), 2i32 * x / y);
-    // assert_finished_and_eq!(expr("2 * (x / y)"), 2i32 * (x / y));
-    // assert_finished_and_eq!(expr("2 * (x * y)"), 2i32 * (x * y));
-    // assert_finished_and_eq!(expr("(2 * x) * y"), (2i32 * x) * y);
-    // assert_finished_and_eq!(expr("2*-x"), 2i32 * - x);
-    // assert_finished_and_eq!(expr("-2 * x"), -2i32 * x);
-    // println!("{:?}", expr("2 * -(-x / - y)"));
-    // assert_finished_and_eq!(expr("2 * -(-x / - y)"), 2i32 * - (- x / - y));
-    // assert_finished_and_eq!(expr("2 * -(-x / 3 ^ - y)"), 2i32 * - (- x / Expr::pow(3.into(), - y)));
-    // assert_finished_and_eq!(expr("2 * -(-x / 3 ^ - y) - 4"), 2i32 * - (- x / Expr::pow(3.into(), - y)) - 4);
-    // assert_finished_and_eq!(expr("x / (3 + 5 * - x - (- y^4))"), x / (3 + 5 * - x - (- y.pow(4))));
+    assert_eq!(complete_expr(CompleteStr("2*x")), Ok((CompleteStr(""), 2i32 * x)));
+    assert_eq!(complete_expr(CompleteStr("2 * x")), Ok((CompleteStr(""), 2i32 * x)));
+    assert_eq!(complete_expr(CompleteStr("x *x")), Ok((CompleteStr(""), x * x)));
+    assert_eq!(complete_expr(CompleteStr("2 * x * 5")), Ok((CompleteStr(""), 2 * x * 5)));
+    assert_eq!(complete_expr(CompleteStr("2 * x / y")), Ok((CompleteStr(""), 2i32 * x / y)));
+    assert_eq!(complete_expr(CompleteStr("2 * (x / y)")), Ok((CompleteStr(""), 2i32 * (x / y))));
+    assert_eq!(complete_expr(CompleteStr("2 * (x * y)")), Ok((CompleteStr(""), 2i32 * (x * y))));
+    assert_eq!(complete_expr(CompleteStr("(2 * x) * y")), Ok((CompleteStr(""), (2i32 * x) * y)));
+    assert_eq!(complete_expr(CompleteStr("2*-x")), Ok((CompleteStr(""), 2i32 * - x)));
+    assert_eq!(complete_expr(CompleteStr("-2 * x")), Ok((CompleteStr(""), -2i32 * x)));
+    // println!("{:?}", complete_expr(CompleteStr("2 * -(-x / - y)")));
+    assert_eq!(complete_expr(CompleteStr("2 * -(-x / - y)")), Ok((CompleteStr(""), 2i32 * - (- x / - y))));
+    assert_eq!(complete_expr(CompleteStr("2 * -(-x / 3 ^ - y)")), Ok((CompleteStr(""), 2i32 * - (- x / Expr::pow(3.into(), - y)))));
+    assert_eq!(complete_expr(CompleteStr("2 * -(-x / 3 ^ - y) - 4")), Ok((CompleteStr(""), 2i32 * - (- x / Expr::pow(3.into(), - y)) - 4)));
+    assert_eq!(complete_expr(CompleteStr("x / (3 + 5 * - x - (- y^4))")), Ok((CompleteStr(""), x / (3 + 5 * - x - (- y.pow(4))))));
 }
 
+#[test]
+fn str_expr_works() {
+    let x = Symbol::new("x");
+    assert_eq!(expr("2 * x"), Ok(("", 2i32 * x)));
+    assert_eq!(expr("2 * x) + 1"), Ok((") + 1", 2i32 * x)));
+    assert!(expr("sin(x").is_err());
+}
+
+#[test]
+fn call_works() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    assert_eq!(call(CompleteStr("sin(x)")), Ok((CompleteStr(""), Expr::sin(x))));
+    assert_eq!(call(CompleteStr("sin( x )")), Ok((CompleteStr(""), Expr::sin(x))));
+    assert_eq!(call(CompleteStr("log(2, x)")), Ok((CompleteStr(""), Expr::log(2, x))));
+    assert_eq!(call(CompleteStr("exp(x + y)")), Ok((CompleteStr(""), Expr::exp(x + y))));
+    assert!(call(CompleteStr("log(x)")).is_err());
+    assert!(parse_expr("log(x)").is_err());
+    assert!(parse_expr("sin(x").is_err());
+    assert!(parse_expr("f(x, y").is_err());
+    assert!(parse_expr("2 * sin(x, y)").is_err());
+    assert_eq!(parse_expr(" 2 * sin(x) "), Ok(2i32 * Expr::sin(x)));
+    assert_eq!(call(CompleteStr("f(x, y)")), Ok((CompleteStr(""), Function::new("f").call(vec![x.into(), y.into()]))));
+
+    assert_eq!(complete_expr(CompleteStr("sin(x)^2")), Ok((CompleteStr(""), Expr::sin(x).pow(2))));
+    assert_eq!(complete_expr(CompleteStr("2 * cos(sqrt(x))")), Ok((CompleteStr(""), 2i32 * Expr::cos(Expr::sqrt(x)))));
+    assert_eq!(complete_expr(CompleteStr("-abs(x)")), Ok((CompleteStr(""), - Expr::abs(x))));
+    assert_eq!(complete_expr(CompleteStr("sinx")), Ok((CompleteStr(""), Expr::symbol("sinx"))));
+}
+
+#[test]
+fn equation_works() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    assert_eq!(equation(CompleteStr("x = 1")), Ok((CompleteStr(""), Equation::new(x, 1))));
+    assert_eq!(equation(CompleteStr("x=y")), Ok((CompleteStr(""), Equation::new(x, y))));
+    assert_eq!(equation(CompleteStr("2 * x - y = -(x + 1)")), Ok((CompleteStr(""), Equation::new(2i32 * x - y, -(x + 1)))));
+    assert_eq!(equation(CompleteStr("sin(x)^2 = 0")), Ok((CompleteStr(""), Equation::new(Expr::sin(x).pow(2), 0))));
+    assert!(equation(CompleteStr("x + 1")).is_err());
+    assert!(equation(CompleteStr("x = 1 = 2")).is_err());
+    assert!(parse_equation("x = 1 )").is_err());
+    assert_eq!(parse_equation(" x = 1 "), Ok(Equation::new(x, 1)));
+}
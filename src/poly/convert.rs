@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use num::{BigRational, Signed, ToPrimitive};
+
+use crate::sym::{Expr, Symbol};
+use super::{Coeff, Poly};
+
+/// The error type when an expression isn't a polynomial in the given variables.
+///
+/// It holds the subexpression that isn't polynomial, like `x ^ -1` or `sin(x)`, or a coefficient
+/// that can't be represented.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotPolynomial(pub Expr);
+
+impl Display for NotPolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` is not polynomial", self.0)
+    }
+}
+
+impl Error for NotPolynomial {
+    fn description(&self) -> &str {
+        "not polynomial"
+    }
+}
+
+/// Conversion from and to `Expr`.
+impl<C: Coeff> Poly<C> {
+    /// Convert an expression into a polynomial in `vars`.
+    ///
+    /// The coefficients must be numbers representable by `C`. Other symbols, negative or symbolic
+    /// powers of the variables, and functions are not polynomial.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::poly::{Poly, NotPolynomial};
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let p: Result<Poly, _> = Poly::from_expr(&(x / 2 + 1), &[x]);
+    /// assert!(p.is_ok());
+    /// let p: Result<Poly, _> = Poly::from_expr(&(x * y), &[x]);
+    /// assert_eq!(p, Err(NotPolynomial(y.into())));
+    /// ```
+    pub fn from_expr(e: &Expr, vars: &[Symbol]) -> Result<Poly<C>, NotPolynomial> {
+        let not_poly = || NotPolynomial(e.clone());
+        let constant = |r: BigRational| {
+            Ok(Poly::constant(vars, C::from_rational(&r).ok_or_else(not_poly)?))
+        };
+
+        match *e {
+            Expr::Integer(ref i) => constant(BigRational::from_integer(i.clone())),
+            Expr::Rational(ref r) => constant(r.clone()),
+            Expr::Sym(s) => match vars.iter().position(|&v| v == s) {
+                Some(i) => Ok(Poly::var(vars, i)),
+                None => Err(not_poly()),
+            },
+            Expr::Neg(ref e) => Ok(-Poly::from_expr(e, vars)?),
+            Expr::Sum(ref args) => args.iter().try_fold(Poly::zero(vars), |acc, arg| {
+                Ok(acc + Poly::from_expr(arg, vars)?)
+            }),
+            Expr::Product(ref args) => args.iter()
+                .try_fold(Poly::one(vars), |acc, arg| {
+                    Ok(acc * Poly::from_expr(arg, vars)?)
+                }),
+            // Only division by a nonzero constant is polynomial.
+            Expr::Ratio(ref n, ref d) => {
+                let n = Poly::<BigRational>::from_expr(n, vars)?;
+                let d = Poly::<BigRational>::from_expr(d, vars)?;
+                if !d.is_constant() || d.is_zero() {
+                    return Err(not_poly());
+                }
+                let d = d.coeff(&vec![0; vars.len()]);
+                (n * Poly::constant(vars, d.recip())).convert().ok_or_else(not_poly)
+            }
+            Expr::Pow(ref b, ref p) => match **p {
+                Expr::Integer(ref n) if !n.is_negative() => {
+                    let n = n.to_u32().ok_or_else(not_poly)?;
+                    Ok(Poly::from_expr(b, vars)?.pow(n))
+                }
+                // Powers of constants like `2 ^ -1` may be numbers.
+                _ => match e.simplify() {
+                    ref s if s.is_number() => Poly::from_expr(s, vars),
+                    _ => Err(not_poly()),
+                },
+            },
+            _ => Err(not_poly()),
+        }
+    }
+
+    /// Convert the polynomial to a simplified expression.
+    pub fn to_expr(&self) -> Expr {
+        let terms = self.terms.iter().map(|(m, c)| {
+            let factors = m.iter()
+                .zip(&self.vars)
+                .filter(|&(&e, _)| e != 0)
+                .map(|(&e, &v)| v.pow(e));
+            factors.fold(Expr::from(c.to_rational()), |acc, f| acc * f)
+        });
+        Expr::Sum(terms.collect()).simplify()
+    }
+
+    /// Convert the coefficients to another type, or return `None` if any isn't representable.
+    pub fn convert<D: Coeff>(&self) -> Option<Poly<D>> {
+        let terms = self.terms
+            .iter()
+            .map(|(m, c)| Some((m.clone(), D::from_rational(&c.to_rational())?)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Poly::from_terms(&self.vars, terms))
+    }
+}
@@ -0,0 +1,143 @@
+use std::slice;
+
+use crate::sym::Symbol;
+use super::{Coeff, MonomialOrder, Poly};
+
+/// Division.
+impl<C: Coeff> Poly<C> {
+    /// Divide by `d`, returning the quotient and the remainder, so that `self = q * d + r`.
+    ///
+    /// The leading terms are taken in the lexicographic order. For univariate polynomials over a
+    /// field, the degree of `r` is less than that of `d`. Otherwise no term of `r` is divisible by
+    /// the leading term of `d`.
+    ///
+    /// # Panics
+    /// Panics if `d` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// let p: Poly = Poly::from_expr(&(x.pow(3) + 2i32 * x + 1), &[x]).unwrap();
+    /// let d = Poly::from_expr(&(x + 1), &[x]).unwrap();
+    /// let (q, r) = p.div_rem(&d);
+    /// assert_eq!(q.to_expr(), (x.pow(2) - x + 3).simplify());
+    /// assert_eq!(r.to_expr(), (-2).into());
+    /// ```
+    pub fn div_rem(&self, d: &Poly<C>) -> (Poly<C>, Poly<C>) {
+        let (mut qs, r) = self.reduce(slice::from_ref(d), MonomialOrder::Lex);
+        (qs.pop().unwrap(), r)
+    }
+
+    /// Divide by several polynomials in the monomial order, so that
+    /// `self = q_1 * d_1 + ... + q_n * d_n + r`, where no term of `r` is divisible by any leading
+    /// term of the divisors.
+    ///
+    /// The divisors are tried in order. Over the integers, a term is divisible only if the
+    /// coefficient is.
+    ///
+    /// # Panics
+    /// Panics if any divisor is zero.
+    pub fn reduce(&self, divisors: &[Poly<C>], order: MonomialOrder) -> (Vec<Poly<C>>, Poly<C>) {
+        let leading = divisors
+            .iter()
+            .map(|d| {
+                let (m, c) = d.leading_term(order).expect("division by zero polynomial");
+                (m.to_vec(), c.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let mut qs = vec![Poly::zero(&self.vars); divisors.len()];
+        let mut r = Poly::zero(&self.vars);
+        let mut p = self.clone();
+        while let Some((m, c)) = p.leading_term(order).map(|(m, c)| (m.to_vec(), c.clone())) {
+            let divisible = leading.iter().position(|&(ref n, ref d)| {
+                m.iter().zip(n).all(|(a, b)| a >= b) && divides(d, &c)
+            });
+            match divisible {
+                Some(i) => {
+                    let (ref n, ref d) = leading[i];
+                    let t = m.iter().zip(n).map(|(a, b)| a - b).collect::<Vec<_>>();
+                    let tc = c / d.clone();
+                    p = p - divisors[i].mul_term(&t, &tc);
+                    qs[i].add_term(t, tc);
+                }
+                None => {
+                    p.terms.remove(&m);
+                    r.add_term(m, c);
+                }
+            }
+        }
+        (qs, r)
+    }
+
+    /// Divide by `d` exactly, or return `None` if it doesn't divide.
+    ///
+    /// # Panics
+    /// Panics if `d` is zero.
+    pub fn exact_div(&self, d: &Poly<C>) -> Option<Poly<C>> {
+        let (q, r) = self.div_rem(d);
+        if r.is_zero() { Some(q) } else { None }
+    }
+
+    /// Pseudo-divide by `d` as polynomials in `s`, returning `(q, r)` such that
+    /// `lc(d) ^ k * self = q * d + r`, where `k = max(deg(self) - deg(d) + 1, 0)`, and the degree of
+    /// `r` in `s` is less than that of `d`.
+    ///
+    /// The coefficients never leave the ring, so it works for integer and multivariate
+    /// polynomials.
+    ///
+    /// # Panics
+    /// Panics if `d` is zero, or `s` isn't a variable.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let p: Poly<BigInt> = Poly::from_expr(&(x.pow(2) + 1), &[x]).unwrap();
+    /// let d = Poly::from_expr(&(2i32 * x + 1), &[x]).unwrap();
+    /// let (q, r) = p.pseudo_div_rem(&d, x);
+    /// // 4 (x ^ 2 + 1) = (2 x - 1) (2 x + 1) + 5
+    /// assert_eq!(q.to_expr(), (2i32 * x - 1).simplify());
+    /// assert_eq!(r.to_expr(), 5.into());
+    /// # }
+    /// ```
+    pub fn pseudo_div_rem(&self, d: &Poly<C>, s: Symbol) -> (Poly<C>, Poly<C>) {
+        self.pseudo_div_rem_at(d, self.var_index(s))
+    }
+
+    pub(crate) fn pseudo_div_rem_at(&self, d: &Poly<C>, i: usize) -> (Poly<C>, Poly<C>) {
+        let m = d.degree_at(i).expect("division by zero polynomial");
+        let l = d.leading_coeff_at(i);
+        let mut q = Poly::zero(&self.vars);
+        let mut r = self.clone();
+        let mut k = match self.degree_at(i) {
+            Some(n) if n >= m => n - m + 1,
+            _ => 0,
+        };
+        while let Some(n) = r.degree_at(i).filter(|&n| n >= m) {
+            let mut x = vec![0; self.vars.len()];
+            x[i] = n - m;
+            let s = r.leading_coeff_at(i).mul_term(&x, &C::one());
+            q = &l * &q + &s;
+            r = &l * &r - &s * d;
+            k -= 1;
+        }
+        let l_k = l.pow(k);
+        (&l_k * &q, &l_k * &r)
+    }
+}
+
+/// Check if `d` divides `c` in the coefficient ring.
+fn divides<C: Coeff>(d: &C, c: &C) -> bool {
+    C::is_field() || (c.clone() % d.clone()).is_zero()
+}
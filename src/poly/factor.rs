@@ -0,0 +1,356 @@
+use num::{BigInt, Integer, Zero};
+
+use crate::sym::{Expr, Symbol};
+use super::{Coeff, MonomialOrder, NotPolynomial, Poly};
+use super::gcd::{content_at, gcd_int, normalize};
+use super::zassenhaus::{self, next_subset, Dense};
+
+/// The largest degree of the univariate image in the Kronecker substitution.
+const KRONECKER_LIMIT: u64 = 64;
+
+/// Factorization.
+impl<C: Coeff> Poly<C> {
+    /// Compute the square-free decomposition, returning the content `c` and pairs `(p_k, k)` such
+    /// that `self = c * p_1 ^ 1 * p_2 ^ 2 * ...`, where the `p_k` are square-free and pairwise
+    /// coprime.
+    ///
+    /// The `p_k` have coprime integer coefficients and a positive leading coefficient, and only
+    /// those that aren't constant are listed, in increasing order of `k`. The zero polynomial has
+    /// a content of zero.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// // 2 (x + 1) ^ 2 (x + 2) ^ 2 x ^ 3
+    /// let e = 2i32 * x.pow(3) * (x.pow(2) + 3i32 * x + 2).pow(2);
+    /// let p: Poly<BigInt> = Poly::from_expr(&e, &[x]).unwrap();
+    /// let (c, factors) = p.sqf_list();
+    /// assert_eq!(c, BigInt::from(2));
+    /// let factors = factors.iter().map(|(p, k)| (p.to_expr(), *k)).collect::<Vec<_>>();
+    /// assert_eq!(factors, vec![((x.pow(2) + 3i32 * x + 2).simplify(), 2), (x.into(), 3)]);
+    /// # }
+    /// ```
+    pub fn sqf_list(&self) -> (C, Vec<(Poly<C>, u32)>) {
+        let (c, p) = self.primitive();
+        let mut factors = match p {
+            Some(p) => sqf_int(&p),
+            None => vec![],
+        };
+
+        // Factors from different contents may have the same multiplicity.
+        factors.sort_by_key(|f| f.1);
+        let mut merged: Vec<(Poly<BigInt>, u32)> = vec![];
+        for (f, k) in factors {
+            match merged.last_mut() {
+                Some(last) if last.1 == k => last.0 = &last.0 * &f,
+                _ => merged.push((f, k)),
+            }
+        }
+        (c, merged.into_iter().map(|(f, k)| (f.convert().unwrap(), k)).collect())
+    }
+
+    /// Factor into irreducible polynomials over the integers, returning the content `c` and pairs
+    /// `(p_i, k_i)` such that `self = c * p_1 ^ k_1 * p_2 ^ k_2 * ...`.
+    ///
+    /// The `p_i` are normalized like in `sqf_list`, and sorted by total degree. For rational
+    /// coefficients, this is also the factorization over the rationals.
+    ///
+    /// Univariate polynomials are factored by the Berlekamp-Zassenhaus algorithm with Hensel
+    /// lifting. Multivariate polynomials are reduced to univariate ones by the Kronecker
+    /// substitution, which is only tried for small degrees, so large multivariate square-free
+    /// factors may be left unfactored.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let p: Poly<BigInt> = Poly::from_expr(&(x.pow(4) - 1), &[x]).unwrap();
+    /// let (c, factors) = p.factor_list();
+    /// assert_eq!(c, BigInt::from(1));
+    /// let factors = factors.iter().map(|(p, k)| (p.to_expr(), *k)).collect::<Vec<_>>();
+    /// assert_eq!(factors, vec![
+    ///     ((x - 1).simplify(), 1),
+    ///     ((x + 1).simplify(), 1),
+    ///     ((x.pow(2) + 1).simplify(), 1),
+    /// ]);
+    /// # }
+    /// ```
+    pub fn factor_list(&self) -> (C, Vec<(Poly<C>, u32)>) {
+        let (c, p) = self.primitive();
+        let p = match p {
+            Some(p) => p,
+            None => return (c, vec![]),
+        };
+
+        let mut factors = sqf_int(&p)
+            .into_iter()
+            .flat_map(|(f, k)| factor_sqf(&f).into_iter().map(move |g| (g, k)))
+            .collect::<Vec<_>>();
+        factors.sort_by_key(|&(ref f, k)| {
+            let terms = f.terms.iter().rev();
+            (f.total_degree(), terms.map(|(m, c)| (m.clone(), c.clone())).collect::<Vec<_>>(), k)
+        });
+        (c, factors.into_iter().map(|(f, k)| (f.convert().unwrap(), k)).collect())
+    }
+
+    /// Split into the content and the primitive integer part with a positive leading
+    /// coefficient, or `None` if it's a constant.
+    pub(crate) fn primitive(&self) -> (C, Option<Poly<BigInt>>) {
+        if self.is_constant() {
+            return (self.coeff(&vec![0; self.vars.len()]), None);
+        }
+        let p = self.to_integer();
+        let content = p.terms.values().fold(BigInt::zero(), |acc, c| acc.gcd(c));
+        let p = Poly::from_terms(&p.vars, p.terms.iter().map(|(m, c)| (m.clone(), c / &content)));
+        let p = normalize(p);
+        let c = self.leading_coeff(MonomialOrder::Lex).to_rational()
+            / p.leading_coeff(MonomialOrder::Lex).to_rational();
+        (C::from_rational(&c).unwrap(), Some(p))
+    }
+}
+
+/// The square-free decomposition of a primitive integer polynomial with a positive leading
+/// coefficient, which may have repeated multiplicities.
+fn sqf_int(f: &Poly<BigInt>) -> Vec<(Poly<BigInt>, u32)> {
+    let i = match (0..f.vars.len()).find(|&i| f.degree_at(i).unwrap_or(0) > 0) {
+        Some(i) => i,
+        None => return vec![],
+    };
+
+    // The content is free of `x_i`, so the derivative can't see its repeated factors.
+    let content = content_at(f, i);
+    let mut res = sqf_int(&content);
+    res.extend(yun(&f.exact_div(&content).unwrap(), i));
+    res
+}
+
+/// Yun's algorithm for a polynomial primitive in `x_i`.
+fn yun(f: &Poly<BigInt>, i: usize) -> Vec<(Poly<BigInt>, u32)> {
+    let df = derivative(f, i);
+    let g = gcd_int(f, &df);
+    let mut b = f.exact_div(&g).unwrap();
+    let mut c = df.exact_div(&g).unwrap();
+    let mut res = vec![];
+    let mut k = 1;
+    while !b.is_constant() {
+        let d = &c - &derivative(&b, i);
+        let a = gcd_int(&b, &d);
+        b = b.exact_div(&a).unwrap();
+        c = d.exact_div(&a).unwrap();
+        if !a.is_constant() {
+            res.push((a, k));
+        }
+        k += 1;
+    }
+    res
+}
+
+/// The derivative in `x_i`.
+fn derivative(f: &Poly<BigInt>, i: usize) -> Poly<BigInt> {
+    let terms = f.terms.iter().filter(|&(m, _)| m[i] > 0).map(|(m, c)| {
+        let e = m[i];
+        let mut m = m.clone();
+        m[i] -= 1;
+        (m, c * BigInt::from(e))
+    });
+    Poly::from_terms(&f.vars, terms)
+}
+
+/// Factor a square-free primitive integer polynomial with a positive leading coefficient.
+fn factor_sqf(f: &Poly<BigInt>) -> Vec<Poly<BigInt>> {
+    let present = (0..f.vars.len())
+        .filter(|&i| f.degree_at(i).unwrap_or(0) > 0)
+        .collect::<Vec<_>>();
+    match present.len() {
+        0 => vec![],
+        1 => factor_univariate(f, present[0]),
+        _ => kronecker(f, &present),
+    }
+}
+
+/// Factor a square-free primitive polynomial in `x_i` only.
+fn factor_univariate(f: &Poly<BigInt>, i: usize) -> Vec<Poly<BigInt>> {
+    let mut dense = vec![BigInt::zero(); f.degree_at(i).unwrap() as usize + 1];
+    for (m, c) in &f.terms {
+        dense[m[i] as usize] = c.clone();
+    }
+    zassenhaus::factor_squarefree(&dense)
+        .into_iter()
+        .map(|g| from_dense(&f.vars, i, &g))
+        .collect()
+}
+
+fn from_dense(vars: &[Symbol], i: usize, g: &Dense) -> Poly<BigInt> {
+    let terms = g.iter().enumerate().map(|(k, c)| {
+        let mut m = vec![0; vars.len()];
+        m[i] = k as u32;
+        (m, c.clone())
+    });
+    Poly::from_terms(vars, terms)
+}
+
+/// Factor a square-free primitive multivariate polynomial by the Kronecker substitution.
+///
+/// Substituting `x_j = t ^ (b_1 * ... * b_(j-1))`, where `b_j` is one more than the degree in
+/// `x_j`, gives a univariate polynomial with the same coefficients. Every factor maps to a product
+/// of the factors of the image, and can be mapped back.
+fn kronecker(f: &Poly<BigInt>, present: &[usize]) -> Vec<Poly<BigInt>> {
+    let bases = present
+        .iter()
+        .map(|&i| u64::from(f.degree_at(i).unwrap()) + 1)
+        .collect::<Vec<_>>();
+    let size = bases.iter().try_fold(1u64, |acc, &b| acc.checked_mul(b));
+    if size.map_or(true, |s| s > KRONECKER_LIMIT + 1) {
+        return vec![f.clone()];
+    }
+
+    let t = [f.vars[present[0]]];
+    let image = Poly::from_terms(
+        &t,
+        f.terms.iter().map(|(m, c)| {
+            let e = present
+                .iter()
+                .zip(&bases)
+                .rev()
+                .fold(0, |acc, (&i, &b)| acc * b + u64::from(m[i]));
+            (vec![e as u32], c.clone())
+        }),
+    );
+    let image = normalize(image);
+
+    // The factors of the image with multiplicity.
+    let mut parts = sqf_int(&image)
+        .into_iter()
+        .flat_map(|(g, k)| {
+            factor_univariate(&g, 0)
+                .into_iter()
+                .flat_map(move |h| ::std::iter::repeat(h).take(k as usize))
+        })
+        .collect::<Vec<_>>();
+
+    let preimage = |g: &Poly<BigInt>| {
+        let terms = g.terms.iter().map(|(m, c)| {
+            let mut e = u64::from(m[0]);
+            let mut n = vec![0; f.vars.len()];
+            for (&i, &b) in present.iter().zip(&bases) {
+                n[i] = (e % b) as u32;
+                e /= b;
+            }
+            if e == 0 { Some((n, c.clone())) } else { None }
+        });
+        Some(Poly::from_terms(&f.vars, terms.collect::<Option<Vec<_>>>()?))
+    };
+
+    let mut rest = f.clone();
+    let mut res = vec![];
+    let mut size = 1;
+    'sizes: while 2 * size <= parts.len() {
+        let mut subset = (0..size).collect::<Vec<_>>();
+        loop {
+            let g = subset
+                .iter()
+                .fold(Poly::one(&t), |acc, &i| acc * &parts[i]);
+            if let Some(g) = preimage(&g).filter(|g| !g.is_constant()) {
+                if let Some(q) = rest.exact_div(&g) {
+                    res.push(normalize(g));
+                    rest = q;
+                    for &i in subset.iter().rev() {
+                        parts.remove(i);
+                    }
+                    continue 'sizes;
+                }
+            }
+            if !next_subset(&mut subset, parts.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    res.push(normalize(rest));
+    res
+}
+
+/// Factorization of expressions.
+impl Expr {
+    /// Factor a polynomial in `vars` over the rationals, returning the content and the
+    /// irreducible factors with their multiplicities, like `Poly::factor_list`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let (c, factors) = (x.pow(2) / 2 - Expr::rational(1, 2)).factor_list(&[x]).unwrap();
+    /// assert_eq!(c, Expr::rational(1, 2));
+    /// assert_eq!(factors, vec![((x - 1).simplify(), 1), ((x + 1).simplify(), 1)]);
+    /// ```
+    pub fn factor_list(&self, vars: &[Symbol]) -> Result<(Expr, Vec<(Expr, u32)>), NotPolynomial> {
+        let p: Poly = Poly::from_expr(self, vars)?;
+        let (c, factors) = p.factor_list();
+        let factors = factors.into_iter().map(|(f, k)| (f.to_expr(), k)).collect();
+        Ok((c.into(), factors))
+    }
+
+    /// Factor a polynomial in all of its symbols over the rationals, into a product of powers of
+    /// irreducible factors.
+    ///
+    /// Anything that isn't a polynomial with rational coefficients is only simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let e = (x.pow(3) - x * y.pow(2)).factor();
+    /// assert_eq!(e, (x * (x - y) * (x + y)).simplify());
+    /// let e = (2i32 * x.pow(2) + 4i32 * x + 2).factor();
+    /// assert_eq!(e, (2i32 * (x + 1).pow(2)).simplify());
+    /// ```
+    pub fn factor(&self) -> Expr {
+        let vars = self.free_symbols().into_iter().collect::<Vec<_>>();
+        match self.factor_list(&vars) {
+            Ok((c, factors)) => factors
+                .into_iter()
+                .fold(c, |acc, (f, k)| acc * f.pow(k))
+                .simplify(),
+            Err(_) => self.simplify(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::Signed;
+    use super::*;
+
+    #[test]
+    fn yun_works() {
+        let x = Symbol::new("x");
+        let y = Symbol::new("y");
+        let vars = [x, y];
+        // (x + y) (x - y) ^ 2
+        let f: Poly<BigInt> = Poly::from_expr(&((x + y) * (x - y).pow(2)), &vars).unwrap();
+        let res = yun(&f, 0);
+        assert_eq!(res, vec![
+            (Poly::from_expr(&(x + y), &vars).unwrap(), 1),
+            (Poly::from_expr(&(x - y), &vars).unwrap(), 2),
+        ]);
+        assert!(res.iter().all(|(p, _)| p.leading_coeff(MonomialOrder::Lex).is_positive()));
+    }
+}
@@ -0,0 +1,272 @@
+use num::{BigInt, BigRational, Integer, One};
+
+use crate::sym::{Expr, Symbol};
+use super::{Coeff, MonomialOrder, NotPolynomial, Poly};
+
+/// Greatest common divisors.
+impl<C: Coeff> Poly<C> {
+    /// Compute the greatest common divisor.
+    ///
+    /// Over the integers the result has a positive leading coefficient, and over the rationals
+    /// it's monic. The GCD of two zeros is zero.
+    ///
+    /// It's computed by the subresultant polynomial remainder sequence, recursively in each
+    /// variable.
+    ///
+    /// # Panics
+    /// Panics if the polynomials have different variables.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let vars = [x, y];
+    /// let p: Poly<BigInt> = Poly::from_expr(&(2i32 * x.pow(2) - 2i32 * y.pow(2)), &vars).unwrap();
+    /// let q = Poly::from_expr(&(4i32 * x + 4i32 * y), &vars).unwrap();
+    /// assert_eq!(p.gcd(&q).to_expr(), (2i32 * x + 2i32 * y).simplify());
+    /// # }
+    /// ```
+    pub fn gcd(&self, other: &Poly<C>) -> Poly<C> {
+        assert_eq!(self.vars, other.vars, "polynomials in different variables");
+        let g = gcd_int(&self.to_integer(), &other.to_integer());
+        normalize(g.convert().unwrap())
+    }
+
+    /// Compute the least common multiple, normalized like `gcd`. It's zero if either is zero.
+    ///
+    /// # Panics
+    /// Panics if the polynomials have different variables.
+    pub fn lcm(&self, other: &Poly<C>) -> Poly<C> {
+        if self.is_zero() || other.is_zero() {
+            return Poly::zero(&self.vars);
+        }
+        let g = self.gcd(other);
+        normalize((self * other).exact_div(&g).unwrap())
+    }
+
+    /// Multiply by the least common denominator of the coefficients.
+    pub(crate) fn to_integer(&self) -> Poly<BigInt> {
+        let denom = self.terms
+            .values()
+            .fold(BigInt::one(), |acc, c| acc.lcm(c.to_rational().denom()));
+        let terms = self.terms.iter().map(|(m, c)| {
+            let c = c.to_rational() * BigRational::from_integer(denom.clone());
+            (m.clone(), c.to_integer())
+        });
+        Poly::from_terms(&self.vars, terms)
+    }
+}
+
+/// The extended Euclidean algorithm.
+impl Poly {
+    /// Compute `(s, t, g)` such that `s * self + t * other = g`, where `g` is the monic GCD of
+    /// univariate polynomials. If both are zero, all three are zero.
+    ///
+    /// # Panics
+    /// Panics if the polynomials have different variables, or aren't univariate.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let a: Poly = Poly::from_expr(&(x.pow(2) - 1), &[x]).unwrap();
+    /// let b = Poly::from_expr(&(x + 2), &[x]).unwrap();
+    /// let (s, t, g) = a.gcdex(&b);
+    /// assert_eq!(s.to_expr(), Expr::rational(1, 3));
+    /// assert_eq!(t.to_expr(), (Expr::rational(2, 3) - x / 3).simplify());
+    /// assert_eq!(g.to_expr(), 1.into());
+    /// ```
+    pub fn gcdex(&self, other: &Poly) -> (Poly, Poly, Poly) {
+        assert_eq!(self.vars, other.vars, "polynomials in different variables");
+        assert_eq!(self.vars.len(), 1, "polynomials not univariate");
+        let (zero, one) = (Poly::zero(&self.vars), Poly::one(&self.vars));
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (one.clone(), zero.clone());
+        let (mut t0, mut t1) = (zero, one);
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            let s = &s0 - &(&q * &s1);
+            let t = &t0 - &(&q * &t1);
+            r0 = ::std::mem::replace(&mut r1, r);
+            s0 = ::std::mem::replace(&mut s1, s);
+            t0 = ::std::mem::replace(&mut t1, t);
+        }
+        if r0.is_zero() {
+            return (r0.clone(), r0.clone(), r0);
+        }
+        let c = r0.leading_coeff(MonomialOrder::Lex).recip();
+        (s0.scale(&c), t0.scale(&c), r0.scale(&c))
+    }
+}
+
+/// Make the leading coefficient positive, or one over a field.
+pub(crate) fn normalize<C: Coeff>(p: Poly<C>) -> Poly<C> {
+    let lc = p.leading_coeff(MonomialOrder::Lex);
+    if p.is_zero() {
+        p
+    } else if C::is_field() {
+        p.scale(&(C::one() / lc))
+    } else if lc.is_negative() {
+        -p
+    } else {
+        p
+    }
+}
+
+/// The GCD of integer polynomials, with a positive leading coefficient.
+pub(crate) fn gcd_int(f: &Poly<BigInt>, g: &Poly<BigInt>) -> Poly<BigInt> {
+    if f.is_zero() {
+        return normalize(g.clone());
+    }
+    if g.is_zero() {
+        return normalize(f.clone());
+    }
+
+    // The main variable is the first one that appears.
+    let main = (0..f.vars.len()).find(|&i| {
+        f.degree_at(i).unwrap_or(0) > 0 || g.degree_at(i).unwrap_or(0) > 0
+    });
+    let i = match main {
+        Some(i) => i,
+        None => {
+            let c = f.coeff(&vec![0; f.vars.len()]).gcd(&g.coeff(&vec![0; g.vars.len()]));
+            return Poly::constant(&f.vars, c);
+        }
+    };
+
+    let (cf, cg) = (content_at(f, i), content_at(g, i));
+    let (mut a, mut b) = (f.exact_div(&cf).unwrap(), g.exact_div(&cg).unwrap());
+    let c = gcd_int(&cf, &cg);
+    if a.degree_at(i) < b.degree_at(i) {
+        ::std::mem::swap(&mut a, &mut b);
+    }
+    let h = if b.degree_at(i) == Some(0) {
+        // `b` is primitive and free of the main variable, so it's a unit.
+        Poly::one(&f.vars)
+    } else {
+        primitive_at(&subresultant_gcd(a, b, i), i)
+    };
+    normalize(&c * &h)
+}
+
+/// The content of `f` as a polynomial in `x_i`, the GCD of its coefficients.
+pub(crate) fn content_at(f: &Poly<BigInt>, i: usize) -> Poly<BigInt> {
+    let degree = f.degree_at(i).unwrap_or(0);
+    (0..degree + 1).fold(Poly::zero(&f.vars), |acc, k| gcd_int(&acc, &f.coeff_at(i, k)))
+}
+
+/// The primitive part of `f` as a polynomial in `x_i`.
+pub(crate) fn primitive_at(f: &Poly<BigInt>, i: usize) -> Poly<BigInt> {
+    if f.is_zero() {
+        return f.clone();
+    }
+    f.exact_div(&content_at(f, i)).unwrap()
+}
+
+/// The last nonzero polynomial in the subresultant remainder sequence of `a` and `b` in `x_i`,
+/// where `deg(a) >= deg(b) > 0`. It's an associate of the GCD up to content.
+fn subresultant_gcd(mut a: Poly<BigInt>, mut b: Poly<BigInt>, i: usize) -> Poly<BigInt> {
+    let one = Poly::one(&a.vars);
+    let (mut g, mut h) = (one.clone(), one.clone());
+    loop {
+        let delta = a.degree_at(i).unwrap() - b.degree_at(i).unwrap();
+        let (_, r) = a.pseudo_div_rem_at(&b, i);
+        if r.is_zero() {
+            return b;
+        }
+        if r.degree_at(i) == Some(0) {
+            return one;
+        }
+        a = b;
+        b = r.exact_div(&(&g * &h.pow(delta))).unwrap();
+        g = a.leading_coeff_at(i);
+        // h = h ^ (1 - delta) * g ^ delta
+        h = match delta {
+            0 => h,
+            _ => g.pow(delta).exact_div(&h.pow(delta - 1)).unwrap(),
+        };
+    }
+}
+
+/// Polynomial GCDs of expressions.
+impl Expr {
+    /// Compute the GCD of two polynomials in `vars`.
+    ///
+    /// If both have integer coefficients, the GCD is over the integers with a positive leading
+    /// coefficient, otherwise it's monic.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let a = x.pow(2) - 1;
+    /// let b = 2i32 * x.pow(2) + 4i32 * x + 2;
+    /// assert_eq!(Expr::poly_gcd(&a, &b, &[x]), Ok((x + 1).simplify()));
+    /// assert_eq!(Expr::poly_gcd(&(a / 2), &b, &[x]), Ok((x + 1).simplify()));
+    /// ```
+    pub fn poly_gcd(a: &Expr, b: &Expr, vars: &[Symbol]) -> Result<Expr, NotPolynomial> {
+        poly_op(a, b, vars, Poly::gcd, Poly::gcd)
+    }
+
+    /// Compute the LCM of two polynomials in `vars`, normalized like `poly_gcd`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let a = x.pow(2) - 1;
+    /// let b = x.pow(2) + 2i32 * x + 1;
+    /// assert_eq!(Expr::poly_lcm(&a, &b, &[x]), Ok(((x - 1) * (x + 1).pow(2)).expand()));
+    /// ```
+    pub fn poly_lcm(a: &Expr, b: &Expr, vars: &[Symbol]) -> Result<Expr, NotPolynomial> {
+        poly_op(a, b, vars, Poly::lcm, Poly::lcm)
+    }
+}
+
+/// Apply an operation over the integers if possible, otherwise over the rationals.
+fn poly_op<F, G>(a: &Expr, b: &Expr, vars: &[Symbol], int_op: F, rat_op: G)
+    -> Result<Expr, NotPolynomial>
+where
+    F: Fn(&Poly<BigInt>, &Poly<BigInt>) -> Poly<BigInt>,
+    G: Fn(&Poly, &Poly) -> Poly,
+{
+    let (p, q): (Poly, Poly) = (Poly::from_expr(a, vars)?, Poly::from_expr(b, vars)?);
+    match (p.convert::<BigInt>(), q.convert::<BigInt>()) {
+        (Some(p), Some(q)) => Ok(int_op(&p, &q).to_expr()),
+        _ => Ok(rat_op(&p, &q).to_expr()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn poly(e: Expr, vars: &[Symbol]) -> Poly<BigInt> {
+        Poly::from_expr(&e, vars).unwrap()
+    }
+
+    #[test]
+    fn content_and_primitive() {
+        let x = Symbol::new("x");
+        let y = Symbol::new("y");
+        let vars = [x, y];
+
+        // (2 y + 2) x ^ 2 + (4 y + 4)
+        let p = poly(2i32 * (y + 1) * x.pow(2) + 4i32 * (y + 1), &vars);
+        assert_eq!(content_at(&p, 0), poly(2i32 * y + 2, &vars));
+        assert_eq!(primitive_at(&p, 0), poly(x.pow(2) + 2, &vars));
+        assert_eq!(content_at(&p, 1), poly(2i32 * x.pow(2) + 4, &vars));
+    }
+}
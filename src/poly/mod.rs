@@ -0,0 +1,56 @@
+//! Sparse multivariate polynomials.
+//!
+//! A `Poly` stores each monomial as a vector of exponents, one for each of its variables, with a
+//! nonzero coefficient. The coefficients are `BigRational` by default, or `BigInt`.
+//!
+//! # Creating a `Poly`
+//!
+//! The easiest way is to convert a `Expr`. The variables are given explicitly, and everything else
+//! must be a number.
+//!
+//! ```
+//! use symrs::poly::Poly;
+//! use symrs::sym::Symbol;
+//!
+//! let x = Symbol::new("x");
+//! let y = Symbol::new("y");
+//! let p: Poly = Poly::from_expr(&((x + y).pow(2) + 1), &[x, y]).unwrap();
+//! assert_eq!(p.total_degree(), Some(2));
+//! assert_eq!(p.to_expr(), (x.pow(2) + 2i32 * x * y + y.pow(2) + 1).simplify());
+//! ```
+//!
+//! # Monomial orders
+//!
+//! The leading term depends on the order of monomials, see `MonomialOrder`.
+//!
+//! ```
+//! use symrs::poly::{Poly, MonomialOrder};
+//! use symrs::sym::Symbol;
+//!
+//! let x = Symbol::new("x");
+//! let y = Symbol::new("y");
+//! let p: Poly = Poly::from_expr(&(x + y.pow(2)), &[x, y]).unwrap();
+//! assert_eq!(p.leading_monomial(MonomialOrder::Lex), Some(&[1, 0][..]));
+//! assert_eq!(p.leading_monomial(MonomialOrder::Grlex), Some(&[0, 2][..]));
+//! ```
+
+mod polynomial;
+mod order;
+mod ops;
+mod convert;
+mod div;
+mod gcd;
+mod factor;
+mod zassenhaus;
+mod ratfunc;
+mod roots;
+mod nroots;
+mod solve;
+
+pub use self::polynomial::{Poly, Coeff, Monomial};
+pub use self::order::MonomialOrder;
+pub use self::convert::NotPolynomial;
+pub use self::roots::PolyRoots;
+pub use self::nroots::NumericRoot;
+pub use self::solve::{solve, SolutionSet, SolveError};
+pub(crate) use self::roots::coeffs;
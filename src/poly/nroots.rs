@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::f64::{self, consts};
+use num::{One, Zero};
+use num::complex::Complex64;
+
+use crate::sym::{Expr, Symbol};
+use super::NotPolynomial;
+use super::roots::coeffs;
+
+/// The maximum number of Aberth iterations.
+const MAX_ITERATIONS: usize = 1000;
+
+/// A numeric approximation of a root of a polynomial, returned by `Expr::nroots`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericRoot {
+    /// The approximate value.
+    pub value: Complex64,
+    /// A radius of a disk around `value` containing a root.
+    ///
+    /// If `k` of the disks of all the roots form a connected region apart from the others, it
+    /// contains exactly `k` roots with multiplicity. It's infinite if two approximations coincide.
+    pub error: f64,
+}
+
+/// Numeric roots of polynomials.
+impl Expr {
+    /// Find all the complex roots of a polynomial in `x` numerically, with multiplicity, sorted by
+    /// the real part and then the imaginary part.
+    ///
+    /// The coefficients are evaluated by `eval_complex`, and the roots are found by the
+    /// Aberth-Ehrlich method. The error bounds come from the Weierstrass corrections of the
+    /// approximations, including the rounding error of evaluating the polynomial. Constants,
+    /// including zero, have no roots.
+    ///
+    /// Returns `NotPolynomial` with a coefficient if it can't be evaluated.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// let roots = (x.pow(3) - 1).nroots(x).unwrap();
+    /// assert_eq!(roots.len(), 3);
+    /// assert!((roots[2].value.re - 1.0).abs() <= roots[2].error);
+    /// assert!(roots.iter().all(|r| r.error < 1e-12));
+    /// ```
+    pub fn nroots(&self, x: Symbol) -> Result<Vec<NumericRoot>, NotPolynomial> {
+        let env = HashMap::new();
+        let c = coeffs(self, x)?
+            .into_iter()
+            .map(|c| c.eval_complex(&env).map_err(|_| NotPolynomial(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(aberth(&c))
+    }
+}
+
+/// Find the roots of the polynomial with complex coefficients `c`, from the constant term, by the
+/// Aberth-Ehrlich method.
+pub(crate) fn aberth(c: &[Complex64]) -> Vec<NumericRoot> {
+    let mut c = c.to_vec();
+    while c.last().map_or(false, Zero::is_zero) {
+        c.pop();
+    }
+    if c.len() <= 1 {
+        return vec![];
+    }
+
+    // Zero roots are exact.
+    let zeros = c.iter().take_while(|c| c.is_zero()).count();
+    let zero = NumericRoot { value: Complex64::zero(), error: 0.0 };
+    let mut roots = vec![zero; zeros];
+    let c = &c[zeros..];
+    let n = c.len() - 1;
+
+    // Start on a circle with the geometric mean of the moduli of the roots, off the real axis.
+    let radius = (c[0].norm() / c[n].norm()).powf(1.0 / n as f64);
+    let mut z = (0..n)
+        .map(|k| Complex64::from_polar(&radius, &(2.0 * consts::PI * k as f64 / n as f64 + 0.4)))
+        .collect::<Vec<_>>();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut converged = true;
+        for i in 0..n {
+            let (p, dp) = horner(c, z[i]);
+            if p.is_zero() {
+                continue;
+            }
+            let ratio = p / dp;
+            let sum = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (z[i] - z[j]).inv())
+                .fold(Complex64::zero(), |acc, t| acc + t);
+            let w = ratio / (Complex64::one() - ratio * sum);
+            if !w.is_finite() {
+                continue;
+            }
+            z[i] -= w;
+            if w.norm() > 4.0 * f64::EPSILON * z[i].norm() {
+                converged = false;
+            }
+        }
+        if converged {
+            break;
+        }
+    }
+
+    roots.extend((0..n).map(|i| NumericRoot { value: z[i], error: error_bound(c, &z, i) }));
+    roots.sort_by(|a, b| {
+        let (a, b) = (a.value, b.value);
+        a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im))
+    });
+    roots
+}
+
+/// Evaluate the polynomial and its derivative.
+fn horner(c: &[Complex64], z: Complex64) -> (Complex64, Complex64) {
+    let mut p = c[c.len() - 1];
+    let mut dp = Complex64::zero();
+    for &a in c[..c.len() - 1].iter().rev() {
+        dp = dp * z + p;
+        p = p * z + a;
+    }
+    (p, dp)
+}
+
+/// `n` times the Weierstrass correction `p(z_i) / (c_n prod(z_i - z_j, j != i))`, where `p(z_i)`
+/// is bounded with its rounding error.
+fn error_bound(c: &[Complex64], z: &[Complex64], i: usize) -> f64 {
+    let n = c.len() - 1;
+    let (p, _) = horner(c, z[i]);
+    let r = z[i].norm();
+    let abs_sum = c.iter().rev().fold(0.0, |acc, a| acc * r + a.norm());
+    let rounding = 2.0 * n as f64 * f64::EPSILON * abs_sum;
+
+    let denom = (0..n)
+        .filter(|&j| j != i)
+        .fold(c[n].norm(), |acc, j| acc * (z[i] - z[j]).norm());
+    if denom == 0.0 {
+        return f64::INFINITY;
+    }
+    n as f64 * (p.norm() + rounding) / denom
+}
@@ -0,0 +1,155 @@
+use std::ops::{Add, Sub, Mul, Neg};
+
+use super::{Coeff, Poly};
+
+// The operators panic if the polynomials have different variables.
+
+impl<'a, 'b, C: Coeff> Add<&'b Poly<C>> for &'a Poly<C> {
+    type Output = Poly<C>;
+
+    fn add(self, rhs: &Poly<C>) -> Poly<C> {
+        self.clone() + rhs
+    }
+}
+
+impl<'a, C: Coeff> Add<&'a Poly<C>> for Poly<C> {
+    type Output = Poly<C>;
+
+    fn add(mut self, rhs: &Poly<C>) -> Poly<C> {
+        assert_eq!(self.vars, rhs.vars, "polynomials in different variables");
+        for (m, c) in &rhs.terms {
+            self.add_term(m.clone(), c.clone());
+        }
+        self
+    }
+}
+
+impl<C: Coeff> Add for Poly<C> {
+    type Output = Poly<C>;
+
+    fn add(self, rhs: Poly<C>) -> Poly<C> {
+        self + &rhs
+    }
+}
+
+impl<'a, 'b, C: Coeff> Sub<&'b Poly<C>> for &'a Poly<C> {
+    type Output = Poly<C>;
+
+    fn sub(self, rhs: &Poly<C>) -> Poly<C> {
+        self.clone() - rhs
+    }
+}
+
+impl<'a, C: Coeff> Sub<&'a Poly<C>> for Poly<C> {
+    type Output = Poly<C>;
+
+    fn sub(mut self, rhs: &Poly<C>) -> Poly<C> {
+        assert_eq!(self.vars, rhs.vars, "polynomials in different variables");
+        for (m, c) in &rhs.terms {
+            self.add_term(m.clone(), -c.clone());
+        }
+        self
+    }
+}
+
+impl<C: Coeff> Sub for Poly<C> {
+    type Output = Poly<C>;
+
+    fn sub(self, rhs: Poly<C>) -> Poly<C> {
+        self - &rhs
+    }
+}
+
+impl<'a, 'b, C: Coeff> Mul<&'b Poly<C>> for &'a Poly<C> {
+    type Output = Poly<C>;
+
+    fn mul(self, rhs: &Poly<C>) -> Poly<C> {
+        assert_eq!(self.vars, rhs.vars, "polynomials in different variables");
+        let mut res = Poly::zero(&self.vars);
+        for (m1, c1) in &self.terms {
+            for (m2, c2) in &rhs.terms {
+                let m = m1.iter().zip(m2).map(|(a, b)| a + b).collect();
+                res.add_term(m, c1.clone() * c2.clone());
+            }
+        }
+        res
+    }
+}
+
+impl<'a, C: Coeff> Mul<&'a Poly<C>> for Poly<C> {
+    type Output = Poly<C>;
+
+    fn mul(self, rhs: &Poly<C>) -> Poly<C> {
+        &self * rhs
+    }
+}
+
+impl<C: Coeff> Mul for Poly<C> {
+    type Output = Poly<C>;
+
+    fn mul(self, rhs: Poly<C>) -> Poly<C> {
+        &self * &rhs
+    }
+}
+
+impl<'a, C: Coeff> Neg for &'a Poly<C> {
+    type Output = Poly<C>;
+
+    fn neg(self) -> Poly<C> {
+        -self.clone()
+    }
+}
+
+impl<C: Coeff> Neg for Poly<C> {
+    type Output = Poly<C>;
+
+    fn neg(mut self) -> Poly<C> {
+        for c in self.terms.values_mut() {
+            *c = -c.clone();
+        }
+        self
+    }
+}
+
+/// Arithmetic.
+impl<C: Coeff> Poly<C> {
+    /// Raise the polynomial to the `n`th power, by repeated squaring.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// let p: Poly = Poly::from_expr(&(x + 1), &[x]).unwrap();
+    /// assert_eq!(p.pow(2).to_expr(), (x.pow(2) + 2i32 * x + 1).simplify());
+    /// ```
+    pub fn pow(&self, mut n: u32) -> Poly<C> {
+        let mut res = Poly::one(&self.vars);
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                res = &res * &base;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = &base * &base;
+            }
+        }
+        res
+    }
+
+    /// Multiply by the term `c * m`.
+    pub(crate) fn mul_term(&self, m: &[u32], c: &C) -> Poly<C> {
+        let terms = self.terms.iter().map(|(n, d)| {
+            (n.iter().zip(m).map(|(a, b)| a + b).collect(), d.clone() * c.clone())
+        });
+        Poly::from_terms(&self.vars, terms)
+    }
+
+    /// Multiply every coefficient by `c`.
+    pub fn scale(&self, c: &C) -> Poly<C> {
+        let terms = self.terms.iter().map(|(m, d)| (m.clone(), d.clone() * c.clone()));
+        Poly::from_terms(&self.vars, terms)
+    }
+}
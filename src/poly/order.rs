@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+/// The orders of monomials, which decide the leading term of a polynomial.
+///
+/// Variables are ordered as they're given to the polynomial, so the first one is the greatest.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use symrs::poly::MonomialOrder;
+///
+/// // x y ^ 2 and x ^ 2
+/// let (a, b) = ([1, 2], [2, 0]);
+/// assert_eq!(MonomialOrder::Lex.cmp(&a, &b), Ordering::Less);
+/// assert_eq!(MonomialOrder::Grlex.cmp(&a, &b), Ordering::Greater);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MonomialOrder {
+    /// The lexicographic order, comparing the exponents of the first variable, and then the next.
+    Lex,
+    /// The graded lexicographic order, comparing the total degree first and then by `Lex`.
+    Grlex,
+    /// The graded reverse lexicographic order, comparing the total degree first, and then the
+    /// smaller exponent of the last differing variable is greater.
+    Grevlex,
+}
+
+impl MonomialOrder {
+    /// Compare two monomials of the same number of variables.
+    pub fn cmp(self, a: &[u32], b: &[u32]) -> Ordering {
+        debug_assert_eq!(a.len(), b.len());
+        let degree = |m: &[u32]| m.iter().map(|&e| u64::from(e)).sum::<u64>();
+        match self {
+            MonomialOrder::Lex => a.cmp(b),
+            MonomialOrder::Grlex => degree(a).cmp(&degree(b)).then_with(|| a.cmp(b)),
+            MonomialOrder::Grevlex => degree(a)
+                .cmp(&degree(b))
+                .then_with(|| b.iter().rev().cmp(a.iter().rev())),
+        }
+    }
+}
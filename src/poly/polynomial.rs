@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map;
+use std::fmt::{self, Debug, Display};
+use num::{BigInt, BigRational, Num, Signed};
+
+use crate::sym::Symbol;
+use super::MonomialOrder;
+
+/// The exponents of the variables in a monomial.
+pub type Monomial = Vec<u32>;
+
+/// The coefficient types of polynomials.
+pub trait Coeff: Clone + Debug + Display + Num + Signed {
+    /// Convert a rational number, or return `None` if it isn't representable.
+    fn from_rational(r: &BigRational) -> Option<Self>;
+
+    /// Convert to a rational number.
+    fn to_rational(&self) -> BigRational;
+
+    /// Check if every nonzero coefficient is invertible, as in a field.
+    fn is_field() -> bool;
+}
+
+impl Coeff for BigInt {
+    fn from_rational(r: &BigRational) -> Option<BigInt> {
+        if r.is_integer() { Some(r.to_integer()) } else { None }
+    }
+
+    fn to_rational(&self) -> BigRational {
+        BigRational::from_integer(self.clone())
+    }
+
+    fn is_field() -> bool {
+        false
+    }
+}
+
+impl Coeff for BigRational {
+    fn from_rational(r: &BigRational) -> Option<BigRational> {
+        Some(r.clone())
+    }
+
+    fn to_rational(&self) -> BigRational {
+        self.clone()
+    }
+
+    fn is_field() -> bool {
+        true
+    }
+}
+
+/// A sparse multivariate polynomial.
+///
+/// The terms are kept in lexicographic order of their monomials, and none of the coefficients are
+/// zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly<C = BigRational> {
+    pub(crate) vars: Vec<Symbol>,
+    pub(crate) terms: BTreeMap<Monomial, C>,
+}
+
+/// Constructors.
+impl<C: Coeff> Poly<C> {
+    /// Construct the zero polynomial in `vars`.
+    pub fn zero(vars: &[Symbol]) -> Poly<C> {
+        Poly {
+            vars: vars.to_vec(),
+            terms: BTreeMap::new(),
+        }
+    }
+
+    /// Construct the constant polynomial of one in `vars`.
+    pub fn one(vars: &[Symbol]) -> Poly<C> {
+        Poly::constant(vars, C::one())
+    }
+
+    /// Construct a constant polynomial in `vars`.
+    pub fn constant(vars: &[Symbol], c: C) -> Poly<C> {
+        Poly::from_terms(vars, vec![(vec![0; vars.len()], c)])
+    }
+
+    /// Construct the polynomial of the variable `vars[i]`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of range.
+    pub fn var(vars: &[Symbol], i: usize) -> Poly<C> {
+        let mut m = vec![0; vars.len()];
+        m[i] = 1;
+        Poly::from_terms(vars, vec![(m, C::one())])
+    }
+
+    /// Construct a polynomial from terms. Like terms are added up.
+    ///
+    /// # Panics
+    /// Panics if a monomial has the wrong number of variables.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// // 2 x ^ 2 + 1
+    /// let p = Poly::from_terms(&[x], vec![(vec![2], BigInt::from(2)), (vec![0], BigInt::from(1))]);
+    /// assert_eq!(p.to_expr(), (2i32 * x.pow(2) + 1).simplify());
+    /// # }
+    /// ```
+    pub fn from_terms<I>(vars: &[Symbol], terms: I) -> Poly<C>
+    where
+        I: IntoIterator<Item = (Monomial, C)>,
+    {
+        let mut p = Poly::zero(vars);
+        for (m, c) in terms {
+            assert_eq!(m.len(), vars.len(), "wrong number of variables");
+            p.add_term(m, c);
+        }
+        p
+    }
+
+    /// Add `c * m` to the polynomial.
+    pub(crate) fn add_term(&mut self, m: Monomial, c: C) {
+        if c.is_zero() {
+            return;
+        }
+        match self.terms.entry(m) {
+            btree_map::Entry::Vacant(e) => {
+                e.insert(c);
+            }
+            btree_map::Entry::Occupied(mut e) => {
+                let sum = e.get().clone() + c;
+                if sum.is_zero() {
+                    e.remove();
+                } else {
+                    *e.get_mut() = sum;
+                }
+            }
+        }
+    }
+}
+
+/// Queries.
+impl<C: Coeff> Poly<C> {
+    /// Get the variables.
+    pub fn vars(&self) -> &[Symbol] {
+        &self.vars
+    }
+
+    /// Iterate over the terms in lexicographic order, from the smallest.
+    pub fn terms(&self) -> impl Iterator<Item = (&[u32], &C)> {
+        self.terms.iter().map(|(m, c)| (&m[..], c))
+    }
+
+    /// Get the number of terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Check if the polynomial is zero.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check if the polynomial is a constant, including zero.
+    pub fn is_constant(&self) -> bool {
+        self.terms.keys().all(|m| m.iter().all(|&e| e == 0))
+    }
+
+    /// Get the coefficient of the monomial `m`.
+    pub fn coeff(&self, m: &[u32]) -> C {
+        self.terms.get(m).cloned().unwrap_or_else(C::zero)
+    }
+
+    /// Get the degree in the variable `s`, or `None` if the polynomial is zero.
+    ///
+    /// # Panics
+    /// Panics if `s` isn't a variable of the polynomial.
+    pub fn degree(&self, s: Symbol) -> Option<u32> {
+        self.degree_at(self.var_index(s))
+    }
+
+    /// Get the total degree, or `None` if the polynomial is zero.
+    pub fn total_degree(&self) -> Option<u32> {
+        self.terms.keys().map(|m| m.iter().sum()).max()
+    }
+
+    /// Get the leading term under the order, or `None` if the polynomial is zero.
+    pub fn leading_term(&self, order: MonomialOrder) -> Option<(&[u32], &C)> {
+        match order {
+            // The terms are stored in lexicographic order.
+            MonomialOrder::Lex => self.terms.iter().next_back(),
+            order => self.terms.iter().max_by(|a, b| order.cmp(a.0, b.0)),
+        }.map(|(m, c)| (&m[..], c))
+    }
+
+    /// Get the leading monomial under the order, or `None` if the polynomial is zero.
+    pub fn leading_monomial(&self, order: MonomialOrder) -> Option<&[u32]> {
+        self.leading_term(order).map(|t| t.0)
+    }
+
+    /// Get the leading coefficient under the order, or zero if the polynomial is zero.
+    pub fn leading_coeff(&self, order: MonomialOrder) -> C {
+        self.leading_term(order).map_or_else(C::zero, |t| t.1.clone())
+    }
+
+    /// Get the degree in the `i`th variable.
+    pub(crate) fn degree_at(&self, i: usize) -> Option<u32> {
+        self.terms.keys().map(|m| m[i]).max()
+    }
+
+    /// Get the coefficient of `x_i ^ k`, which is a polynomial free of `x_i`.
+    pub(crate) fn coeff_at(&self, i: usize, k: u32) -> Poly<C> {
+        let terms = self.terms.iter().filter(|&(m, _)| m[i] == k).map(|(m, c)| {
+            let mut m = m.clone();
+            m[i] = 0;
+            (m, c.clone())
+        });
+        Poly::from_terms(&self.vars, terms)
+    }
+
+    /// Get the leading coefficient as a polynomial in `x_i`, or zero if the polynomial is zero.
+    pub(crate) fn leading_coeff_at(&self, i: usize) -> Poly<C> {
+        match self.degree_at(i) {
+            Some(k) => self.coeff_at(i, k),
+            None => Poly::zero(&self.vars),
+        }
+    }
+
+    pub(crate) fn var_index(&self, s: Symbol) -> usize {
+        self.vars
+            .iter()
+            .position(|&v| v == s)
+            .unwrap_or_else(|| panic!("`{}` is not a variable of the polynomial", s))
+    }
+}
+
+/// Evaluation.
+impl<C: Coeff> Poly<C> {
+    /// Evaluate the polynomial at `values`, one for each variable.
+    ///
+    /// # Panics
+    /// Panics if the number of values is wrong.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use num::BigInt;
+    /// use symrs::poly::Poly;
+    /// use symrs::sym::Symbol;
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let p: Poly<BigInt> = Poly::from_expr(&(x.pow(2) * y - 1), &[x, y]).unwrap();
+    /// assert_eq!(p.eval(&[3.into(), 2.into()]), BigInt::from(17));
+    /// # }
+    /// ```
+    pub fn eval(&self, values: &[C]) -> C {
+        assert_eq!(values.len(), self.vars.len(), "wrong number of values");
+        self.terms.iter().fold(C::zero(), |acc, (m, c)| {
+            let term = m.iter()
+                .zip(values)
+                .fold(c.clone(), |t, (&e, v)| t * num::pow(v.clone(), e as usize));
+            acc + term
+        })
+    }
+
+    /// Evaluate the polynomial at `values` numerically.
+    ///
+    /// # Panics
+    /// Panics if the number of values is wrong.
+    pub fn eval_f64(&self, values: &[f64]) -> f64 {
+        assert_eq!(values.len(), self.vars.len(), "wrong number of values");
+        self.terms.iter().fold(0.0, |acc, (m, c)| {
+            let term = m.iter()
+                .zip(values)
+                .fold(to_f64(&c.to_rational()), |t, (&e, v)| t * v.powi(e as i32));
+            acc + term
+        })
+    }
+}
+
+/// Convert a rational number to the nearest `f64`.
+pub(crate) fn to_f64(r: &BigRational) -> f64 {
+    use num::ToPrimitive;
+
+    let n = r.numer().to_f64().unwrap_or(::std::f64::NAN);
+    let d = r.denom().to_f64().unwrap_or(::std::f64::NAN);
+    n / d
+}
+
+impl<C: Coeff> Display for Poly<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_expr())
+    }
+}
@@ -0,0 +1,96 @@
+use num::BigInt;
+
+use crate::sym::{Expr, Symbol};
+use super::Poly;
+
+/// Rational functions.
+impl Expr {
+    /// Cancel the common factors of the numerator and the denominator, after combining them with
+    /// `together`. The result is simplified.
+    ///
+    /// The numerator and the denominator are expanded polynomials with coprime integer
+    /// coefficients, and the denominator has a positive leading coefficient. Expressions that
+    /// aren't rational functions of their symbols are only combined.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!(((x.pow(2) - 1) / (x - 1)).cancel(), (x + 1).simplify());
+    /// assert_eq!(
+    ///     ((x.pow(2) - y.pow(2)) / (2i32 * x + 2i32 * y)).cancel(),
+    ///     ((x - y) / 2).simplify()
+    /// );
+    /// ```
+    pub fn cancel(&self) -> Expr {
+        let (n, d) = self.numer_denom();
+        let vars = n.free_symbols().union(&d.free_symbols()).cloned().collect::<Vec<_>>();
+        let (n, d): (Poly, Poly) = match (Poly::from_expr(&n, &vars), Poly::from_expr(&d, &vars)) {
+            (Ok(n), Ok(d)) => (n, d),
+            _ => return self.together(),
+        };
+
+        let g = n.gcd(&d);
+        let (cn, n) = n.exact_div(&g).unwrap().primitive();
+        let (cd, d) = d.exact_div(&g).unwrap().primitive();
+        let part = |p: Option<Poly<BigInt>>| p.map_or_else(|| Expr::integer(1), |p| p.to_expr());
+        (Expr::from(cn / cd) * part(n) / part(d)).simplify()
+    }
+
+    /// Decompose a rational function of `x` into partial fractions. The result is simplified.
+    ///
+    /// It's a sum of a polynomial and terms `a / f ^ k`, where the `f` are the irreducible
+    /// factors of the denominator over the rationals, and each `a` has a lower degree than `f`.
+    /// Expressions that aren't rational functions of `x` with rational coefficients are only
+    /// simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(
+    ///     (x.pow(-2) - 1).pow(-1).apart(x),
+    ///     (Expr::integer(-1) - 1i32 / (2i32 * (x - 1)) + 1i32 / (2i32 * (x + 1))).simplify()
+    /// );
+    /// assert_eq!(
+    ///     ((x + 2) / (x + 1).pow(2)).apart(x),
+    ///     (1i32 / (x + 1) + 1i32 / (x + 1).pow(2)).simplify()
+    /// );
+    /// ```
+    pub fn apart(&self, x: Symbol) -> Expr {
+        let (n, d) = self.numer_denom();
+        let (n, d): (Poly, Poly) = match (Poly::from_expr(&n, &[x]), Poly::from_expr(&d, &[x])) {
+            (Ok(n), Ok(d)) => (n, d),
+            _ => return self.simplify(),
+        };
+
+        let g = n.gcd(&d);
+        let (n, d) = (n.exact_div(&g).unwrap(), d.exact_div(&g).unwrap());
+        let (q, r) = n.div_rem(&d);
+        let (c, factors) = d.factor_list();
+        let r = r.scale(&c.recip());
+
+        // The numerator over each `f ^ k` is `r` times the inverse of the other factors modulo
+        // `f ^ k`, which is then expanded in powers of `f`.
+        let mut terms = vec![q.to_expr()];
+        for (i, &(ref f, k)) in factors.iter().enumerate() {
+            let fk = f.pow(k);
+            let rest = factors
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Poly::one(&[x]), |acc, (_, &(ref f, k))| acc * f.pow(k));
+            let (s, _, _) = rest.gcdex(&fk);
+            let mut a = (&r * &s).div_rem(&fk).1;
+            for j in (1..k + 1).rev() {
+                let (q, c) = a.div_rem(f);
+                terms.push(c.to_expr() * f.to_expr().pow(-i64::from(j)));
+                a = q;
+            }
+        }
+        Expr::Sum(terms).simplify()
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+use num::complex::Complex64;
+
+use crate::sym::{Expr, Symbol};
+use super::{NotPolynomial, Poly};
+
+/// The bound of the square factors taken out of square roots of rational numbers.
+const SQUARE_FACTOR_LIMIT: u32 = 1000;
+
+/// The roots of a polynomial, returned by `Expr::solve_poly`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolyRoots {
+    /// The roots that are found, with their multiplicities.
+    pub roots: Vec<(Expr, u32)>,
+    /// The factors whose roots can't be expressed in radicals, with their multiplicities.
+    pub unsolved: Vec<(Expr, u32)>,
+}
+
+/// Roots of polynomials.
+impl Expr {
+    /// Find the roots of a polynomial in `x` exactly.
+    ///
+    /// With rational coefficients, the polynomial is factored over the rationals first, so all
+    /// rational roots are found, and the roots of the irreducible factors of degree up to four
+    /// are expressed in radicals. Irreducible cubics with three real roots are expressed with
+    /// `cos` and `acos` instead of complex radicals. The other factors are left unsolved.
+    ///
+    /// Other coefficients, like symbols, are plugged into the general formulas up to degree four,
+    /// which hold for generic values. Constants, including zero, have no roots listed.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let res = (x.pow(3) - 2i32 * x.pow(2) + x).solve_poly(x).unwrap();
+    /// assert_eq!(res.roots, vec![(0.into(), 1), (1.into(), 2)]);
+    ///
+    /// let res = (x.pow(2) + 1).solve_poly(x).unwrap();
+    /// assert_eq!(res.roots, vec![((-Expr::i()).simplify(), 1), (Expr::i(), 1)]);
+    ///
+    /// let a = Symbol::new("a");
+    /// let res = (x.pow(2) - a).solve_poly(x).unwrap();
+    /// assert_eq!(res.roots, vec![
+    ///     ((-1i32 * a.pow(Expr::rational(1, 2))).simplify(), 1),
+    ///     (a.pow(Expr::rational(1, 2)), 1),
+    /// ]);
+    /// ```
+    pub fn solve_poly(&self, x: Symbol) -> Result<PolyRoots, NotPolynomial> {
+        Ok(exact_roots(&coeffs(self, x)?, x))
+    }
+}
+
+/// The coefficients of a polynomial in `x`, from the constant term up to the nonzero leading
+/// coefficient. The zero polynomial has no coefficients.
+pub(crate) fn coeffs(e: &Expr, x: Symbol) -> Result<Vec<Expr>, NotPolynomial> {
+    let terms = match e.expand() {
+        Expr::Sum(args) => args,
+        e => vec![e],
+    };
+
+    let mut coeffs: Vec<Vec<Expr>> = vec![];
+    for term in terms {
+        let factors = match term {
+            Expr::Product(args) => args,
+            t => vec![t],
+        };
+        let mut k = 0;
+        let mut rest = vec![];
+        for f in factors {
+            match f {
+                Expr::Sym(s) if s == x => k += 1,
+                Expr::Pow(ref b, ref n) if **b == Expr::Sym(x) => match **n {
+                    Expr::Integer(ref n) if n.is_positive() => {
+                        k += n.to_usize().ok_or_else(|| NotPolynomial(f.clone()))?;
+                    }
+                    _ => return Err(NotPolynomial(f.clone())),
+                },
+                f => {
+                    if f.has_symbol(x) {
+                        return Err(NotPolynomial(f));
+                    }
+                    rest.push(f);
+                }
+            }
+        }
+        if coeffs.len() <= k {
+            coeffs.resize(k + 1, vec![]);
+        }
+        coeffs[k].push(Expr::Product(rest));
+    }
+
+    let mut coeffs = coeffs.into_iter().map(|c| Expr::Sum(c).simplify()).collect::<Vec<_>>();
+    while coeffs.last().map_or(false, is_zero) {
+        coeffs.pop();
+    }
+    Ok(coeffs)
+}
+
+/// Find the roots of the polynomial with coefficients `c` in `x`.
+pub(crate) fn exact_roots(c: &[Expr], x: Symbol) -> PolyRoots {
+    let mut res = PolyRoots { roots: vec![], unsolved: vec![] };
+    if c.len() <= 1 {
+        return res;
+    }
+
+    match c.iter().map(rational).collect::<Option<Vec<_>>>() {
+        Some(c) => {
+            let terms = c.into_iter().enumerate().map(|(k, c)| (vec![k as u32], c));
+            let p = Poly::from_terms(&[x], terms);
+            for (f, k) in p.factor_list().1 {
+                let degree = f.degree_at(0).unwrap();
+                let c = (0..degree + 1).map(|i| Expr::from(f.coeff(&[i]))).collect::<Vec<_>>();
+                match radicals(&c) {
+                    Some(roots) => res.roots.extend(roots.into_iter().map(|r| (r, k))),
+                    None => res.unsolved.push((f.to_expr(), k)),
+                }
+            }
+        }
+        None => {
+            let zeros = c.iter().take_while(|c| is_zero(c)).count();
+            if zeros > 0 {
+                res.roots.push((Expr::integer(0), zeros as u32));
+            }
+            let c = &c[zeros..];
+            match radicals(c) {
+                Some(roots) => res.roots.extend(roots.into_iter().map(|r| (r, 1))),
+                None if c.len() > 1 => {
+                    let terms = c.iter().enumerate().map(|(k, c)| c.clone() * x.pow(k));
+                    res.unsolved.push((Expr::Sum(terms.collect()).simplify(), 1));
+                }
+                None => {}
+            }
+        }
+    }
+    res
+}
+
+/// The value of an integer or rational number.
+pub(crate) fn rational(e: &Expr) -> Option<BigRational> {
+    match *e {
+        Expr::Integer(ref i) => Some(BigRational::from_integer(i.clone())),
+        Expr::Rational(ref r) => Some(r.clone()),
+        _ => None,
+    }
+}
+
+fn is_zero(e: &Expr) -> bool {
+    *e == Expr::integer(0)
+}
+
+/// The numeric value of an expression without symbols.
+fn value(e: &Expr) -> Option<Complex64> {
+    e.eval_complex(&HashMap::new()).ok()
+}
+
+/// Check if the value of `e` is known to be a negative real number.
+fn is_negative(e: &Expr) -> bool {
+    value(e).map_or(false, |v| v.im == 0.0 && v.re < 0.0)
+}
+
+/// The square root, which is `i` times the root of the negation for negative numbers.
+///
+/// Small square factors are taken out of rational numbers, like `sqrt(8) = 2 sqrt(2)`.
+fn square_root(e: Expr) -> Expr {
+    let half = Expr::rational(1, 2);
+    let e = e.simplify();
+    if let Some(r) = rational(&e) {
+        let (outer, inner) = square_part(&r.abs());
+        let root = Expr::from(outer) * Expr::from(inner).pow(half);
+        return if r.is_negative() { Expr::i() * root } else { root }.simplify();
+    }
+    if is_negative(&e) {
+        (Expr::i() * root((-e).simplify(), half)).simplify()
+    } else {
+        root(e, half)
+    }
+}
+
+/// Write a nonnegative rational `r` as `s ^ 2 * m`, where `m` is an integer without square
+/// factors up to `SQUARE_FACTOR_LIMIT`.
+fn square_part(r: &BigRational) -> (BigRational, BigInt) {
+    let mut m = r.numer() * r.denom();
+    let mut s = BigRational::new(BigInt::one(), r.denom().clone());
+    for k in 2..SQUARE_FACTOR_LIMIT {
+        let k = BigInt::from(k);
+        let square = &k * &k;
+        if square > m {
+            break;
+        }
+        while (&m % &square).is_zero() {
+            m = &m / &square;
+            s *= BigRational::from_integer(k.clone());
+        }
+    }
+    (s, m)
+}
+
+/// The cube root, which is real for negative numbers.
+fn cube_root(e: Expr) -> Expr {
+    let third = Expr::rational(1, 3);
+    let e = e.simplify();
+    if is_negative(&e) {
+        (-root((-e).simplify(), third)).simplify()
+    } else {
+        root(e, third)
+    }
+}
+
+/// The power `e ^ exp` of a simplified `e`, where a positive rational factor `c` of a product is
+/// taken out if `c ^ exp` is a number, like `sqrt(4 a) = 2 sqrt(a)`.
+fn root(e: Expr, exp: Expr) -> Expr {
+    if let Expr::Product(ref args) = e {
+        if rational(&args[0]).map_or(false, |c| c.is_positive()) {
+            let c = args[0].clone().pow(exp.clone()).simplify();
+            if c.is_number() {
+                return (c * Expr::Product(args[1..].to_vec()).simplify().pow(exp)).simplify();
+            }
+        }
+    }
+    e.pow(exp).simplify()
+}
+
+/// The roots of a polynomial of degree up to four with coefficients `c`, from the constant
+/// term, in radicals.
+fn radicals(c: &[Expr]) -> Option<Vec<Expr>> {
+    let roots = match c.len() {
+        2 => vec![(-c[0].clone() / c[1].clone())],
+        3 => quadratic(&c[2], &c[1], &c[0]),
+        4 => cubic(&c[3], &c[2], &c[1], &c[0]),
+        5 => quartic(&c[4], &c[3], &c[2], &c[1], &c[0]),
+        _ => return None,
+    };
+    Some(roots.into_iter().map(|r| r.simplify()).collect())
+}
+
+/// The roots of `a x ^ 2 + b x + c`.
+fn quadratic(a: &Expr, b: &Expr, c: &Expr) -> Vec<Expr> {
+    let d = square_root(b.clone().pow(2) - 4i32 * a.clone() * c.clone());
+    vec![
+        (-b.clone() - d.clone()) / (2i32 * a.clone()),
+        (-b.clone() + d) / (2i32 * a.clone()),
+    ]
+}
+
+/// The roots of `a x ^ 3 + b x ^ 2 + c x + d` by Cardano's formula.
+fn cubic(a: &Expr, b: &Expr, c: &Expr, d: &Expr) -> Vec<Expr> {
+    let (a, b, c, d) = (a.clone(), b.clone(), c.clone(), d.clone());
+    let shift = (-b.clone() / (3i32 * a.clone())).simplify();
+
+    // The depressed cubic `t ^ 3 + p t + q` where `x = t + shift`.
+    let p = ((3i32 * a.clone() * c.clone() - b.clone().pow(2)) / (3i32 * a.clone().pow(2))).simplify();
+    let q = ((2i32 * b.clone().pow(3) - 9i32 * a.clone() * b * c + 27i32 * a.clone().pow(2) * d)
+        / (27i32 * a.pow(3)))
+        .simplify();
+
+    // Three distinct real roots, where the radicals would be complex.
+    if let (Some(p), Some(q)) = (rational(&p), rational(&q)) {
+        let four = BigRational::from_integer(4.into());
+        let disc = -(four * &p * &p * &p + BigRational::from_integer(27.into()) * &q * &q);
+        if disc.is_positive() {
+            let (p, q) = (Expr::from(p), Expr::from(q));
+            let r = 2i32 * square_root(-p.clone() / 3);
+            let angle = Expr::acos(3i32 * q / (2i32 * p.clone()) * square_root(-3i32 / p)) / 3;
+            return (0..3)
+                .map(|k| {
+                    let t = r.clone() * Expr::cos(angle.clone() - 2i32 * k * Expr::pi() / 3);
+                    t + shift.clone()
+                })
+                .collect();
+        }
+    }
+
+    // The roots are `u + v`, `w u + w^2 v` and `w^2 u + w v`, where `u v = -p / 3` and `w` is a
+    // primitive cube root of unity.
+    let (u, v) = if is_zero(&p) {
+        (cube_root(-q), Expr::integer(0))
+    } else {
+        let u = cube_root(-q.clone() / 2 + square_root(q.pow(2) / 4 + p.clone().pow(3) / 27));
+        let v = -p / (3i32 * u.clone());
+        (u, v)
+    };
+    let real = u.clone() + v.clone();
+    let imag = Expr::i() * square_root(Expr::integer(3)) / 2 * (u - v);
+    vec![
+        real.clone() + shift.clone(),
+        -real.clone() / 2 - imag.clone() + shift.clone(),
+        -real / 2 + imag + shift,
+    ]
+}
+
+/// The roots of `a x ^ 4 + b x ^ 3 + c x ^ 2 + d x + e` by Ferrari's method.
+fn quartic(a: &Expr, b: &Expr, c: &Expr, d: &Expr, e: &Expr) -> Vec<Expr> {
+    let (b, c, d, e) = (b.clone() / a, c.clone() / a, d.clone() / a, e.clone() / a);
+    let shift = (-b.clone() / 4).simplify();
+
+    // The depressed quartic `y ^ 4 + p y ^ 2 + q y + r` where `x = y + shift`.
+    let p = (c.clone() - 3i32 * b.clone().pow(2) / 8).simplify();
+    let q = (b.clone().pow(3) / 8 - b.clone() * c.clone() / 2 + d.clone()).simplify();
+    let r = (-3i32 * b.clone().pow(4) / 256 + b.clone().pow(2) * c / 16 - b * d / 4 + e).simplify();
+
+    let ys = if is_zero(&q) {
+        // A quadratic in `y ^ 2`.
+        quadratic(&Expr::integer(1), &p, &r)
+            .into_iter()
+            .flat_map(|z| {
+                let y = square_root(z);
+                vec![-y.clone(), y]
+            })
+            .collect::<Vec<_>>()
+    } else {
+        // `(y ^ 2 + p / 2 + m) ^ 2 = 2 m y ^ 2 - q y + m ^ 2 + m p + p ^ 2 / 4 - r`, where the right
+        // side is a square if `m` is a root of the resolvent cubic, which has a positive root.
+        let resolvent = [
+            (-q.clone().pow(2)).simplify(),
+            (2i32 * p.clone().pow(2) - 8i32 * r).simplify(),
+            (8i32 * p.clone()).simplify(),
+            Expr::integer(8),
+        ];
+        let m = resolvent_root(&resolvent);
+        let s = square_root(2i32 * m.clone());
+        [1i32, -1]
+            .iter()
+            .flat_map(|&sign| {
+                let w = square_root(-(2i32 * p.clone() + 2i32 * m.clone() + sign * 2i32 * q.clone() / s.clone()));
+                vec![(sign * s.clone() - w.clone()) / 2, (sign * s.clone() + w) / 2]
+            })
+            .collect()
+    };
+    ys.into_iter().map(|y| y + shift.clone()).collect()
+}
+
+/// A root of the resolvent cubic of a quartic, which is the greatest real root if the values are
+/// known.
+fn resolvent_root(c: &[Expr]) -> Expr {
+    let x = Symbol::new("x");
+    let roots = exact_roots(c, x).roots.into_iter().map(|r| r.0).collect::<Vec<_>>();
+    let is_real = |v: &Complex64| v.im.abs() <= 1e-9 * (1.0 + v.re.abs());
+    roots
+        .iter()
+        .filter_map(|r| value(r).filter(is_real).map(|v| (v.re, r)))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or_else(|| roots[0].clone(), |(_, r)| r.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn coeffs_works() {
+        let x = Symbol::new("x");
+        let y = Symbol::new("y");
+        assert_eq!(
+            coeffs(&((x + y).pow(2) - x.pow(2)), x),
+            Ok(vec![y.pow(2), (2i32 * y).simplify()])
+        );
+        assert_eq!(coeffs(&(x - x), x), Ok(vec![]));
+        assert_eq!(coeffs(&(x.pow(-1) + 1), x), Err(NotPolynomial(x.pow(-1))));
+        assert_eq!(coeffs(&(Expr::sin(x) * 2), x), Err(NotPolynomial(Expr::sin(x))));
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use num::{BigRational, Zero};
+
+use crate::sym::{Equation, Expr, Symbol};
+use super::NotPolynomial;
+use super::roots::{coeffs, exact_roots, rational};
+
+/// The solutions of a system of equations, returned by `solve`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolutionSet {
+    /// Finitely many solutions, each of which gives the values of all the variables.
+    Finite(Vec<HashMap<Symbol, Expr>>),
+    /// Infinitely many solutions, where the variables in `params` are free, and the values of the
+    /// others are given in terms of them.
+    Parametric {
+        /// The free variables.
+        params: Vec<Symbol>,
+        /// The values of the other variables.
+        solution: HashMap<Symbol, Expr>,
+    },
+    /// No solutions.
+    Empty,
+    /// Every value of the variables is a solution.
+    All,
+}
+
+/// The error type of `solve`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    /// An equation isn't polynomial in the variables. It holds the subexpression that isn't.
+    NotPolynomial(Expr),
+    /// A system isn't linear in the variables with rational coefficients. It holds the equation
+    /// as `lhs - rhs`.
+    NonLinear(Expr),
+    /// A factor of a polynomial has no roots in radicals.
+    Unsolved(Expr),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SolveError::NotPolynomial(ref e) => write!(f, "`{}` is not polynomial", e),
+            SolveError::NonLinear(ref e) => write!(f, "`{} = 0` is not linear", e),
+            SolveError::Unsolved(ref e) => write!(f, "the roots of `{}` are unknown", e),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn description(&self) -> &str {
+        "unsolvable equations"
+    }
+}
+
+impl From<NotPolynomial> for SolveError {
+    fn from(e: NotPolynomial) -> SolveError {
+        SolveError::NotPolynomial(e.0)
+    }
+}
+
+/// Solve a system of equations for `vars`. Other symbols are treated as parameters, and the
+/// solutions hold for their generic values.
+///
+/// A single equation in a single variable may be polynomial, and its roots are found by
+/// `Expr::solve_poly`. Otherwise the equations must be linear in `vars` with rational
+/// coefficients, and they are solved by Gaussian elimination.
+///
+/// # Examples
+/// ```
+/// use symrs::poly::{solve, SolutionSet};
+/// use symrs::sym::{Expr, Symbol};
+///
+/// let x = Symbol::new("x");
+/// let y = Symbol::new("y");
+/// let eqs = [(x + y).eq_to(3), (x - y).eq_to(1)];
+/// let solution = vec![(x, Expr::integer(2)), (y, Expr::integer(1))];
+/// assert_eq!(
+///     solve(&eqs, &[x, y]),
+///     Ok(SolutionSet::Finite(vec![solution.into_iter().collect()]))
+/// );
+///
+/// match solve(&[x.pow(2).eq_to(4)], &[x]) {
+///     Ok(SolutionSet::Finite(solutions)) => {
+///         let mut roots = solutions.iter().map(|s| s[&x].clone()).collect::<Vec<_>>();
+///         roots.sort_by_key(|r| r.to_string());
+///         assert_eq!(roots, vec![Expr::integer(-2), Expr::integer(2)]);
+///     }
+///     _ => panic!("x ^ 2 = 4 has two roots"),
+/// }
+/// ```
+pub fn solve(eqs: &[Equation], vars: &[Symbol]) -> Result<SolutionSet, SolveError> {
+    if eqs.len() == 1 && vars.len() == 1 {
+        polynomial(&eqs[0].to_expr(), vars[0])
+    } else {
+        linear(&eqs.iter().map(Equation::to_expr).collect::<Vec<_>>(), vars)
+    }
+}
+
+/// Solve the polynomial equation `e = 0` for `x`.
+fn polynomial(e: &Expr, x: Symbol) -> Result<SolutionSet, SolveError> {
+    let c = coeffs(e, x)?;
+    if c.is_empty() {
+        return Ok(SolutionSet::All);
+    }
+    let res = exact_roots(&c, x);
+    if let Some(&(ref f, _)) = res.unsolved.first() {
+        return Err(SolveError::Unsolved(f.clone()));
+    }
+    if res.roots.is_empty() {
+        return Ok(SolutionSet::Empty);
+    }
+    let solutions = res.roots.into_iter().map(|(r, _)| Some((x, r)).into_iter().collect());
+    Ok(SolutionSet::Finite(solutions.collect()))
+}
+
+/// A linear equation `a . vars = b`.
+struct Row {
+    a: Vec<BigRational>,
+    b: Expr,
+}
+
+impl Row {
+    /// Convert `e = 0` into a row.
+    fn new(e: &Expr, vars: &[Symbol]) -> Result<Row, SolveError> {
+        let e = e.expand();
+        let mut a = vec![];
+        let mut constant = e.clone();
+        for &v in vars {
+            let c = coeffs(&e, v)?;
+            if c.len() > 2 {
+                return Err(SolveError::NonLinear(e));
+            }
+            let coeff = match c.get(1) {
+                Some(c) => rational(c).ok_or_else(|| SolveError::NonLinear(e.clone()))?,
+                None => BigRational::zero(),
+            };
+            a.push(coeff);
+            constant = constant.subs(v, 0);
+        }
+        Ok(Row { a, b: (-constant).expand() })
+    }
+}
+
+/// Solve the linear equations `e = 0` for `vars`. The values are expanded.
+fn linear(eqs: &[Expr], vars: &[Symbol]) -> Result<SolutionSet, SolveError> {
+    let mut rows = eqs.iter().map(|e| Row::new(e, vars)).collect::<Result<Vec<_>, _>>()?;
+
+    // Reduce to the row echelon form where each pivot is one and the only nonzero entry in its
+    // column.
+    let mut pivots = vec![];
+    for j in 0..vars.len() {
+        let r = pivots.len();
+        let i = match (r..rows.len()).find(|&i| !rows[i].a[j].is_zero()) {
+            Some(i) => i,
+            None => continue,
+        };
+        rows.swap(r, i);
+
+        let p = rows[r].a[j].clone();
+        for a in &mut rows[r].a {
+            *a = &*a / &p;
+        }
+        rows[r].b = (rows[r].b.clone() / Expr::from(p)).expand();
+
+        for i in 0..rows.len() {
+            if i == r || rows[i].a[j].is_zero() {
+                continue;
+            }
+            let f = rows[i].a[j].clone();
+            let pivot = rows[r].a.clone();
+            for (a, p) in rows[i].a.iter_mut().zip(pivot) {
+                *a -= &f * p;
+            }
+            rows[i].b = (rows[i].b.clone() - Expr::from(f) * rows[r].b.clone()).expand();
+        }
+        pivots.push(j);
+    }
+
+    if rows[pivots.len()..].iter().any(|row| row.b != Expr::integer(0)) {
+        return Ok(SolutionSet::Empty);
+    }
+    if pivots.is_empty() {
+        return Ok(SolutionSet::All);
+    }
+
+    let params = (0..vars.len()).filter(|j| !pivots.contains(j)).collect::<Vec<_>>();
+    let solution = pivots
+        .iter()
+        .zip(&rows)
+        .map(|(&j, row)| {
+            let terms = params.iter().map(|&k| Expr::from(-row.a[k].clone()) * vars[k]);
+            let value = Expr::Sum(Some(row.b.clone()).into_iter().chain(terms).collect());
+            (vars[j], value.expand())
+        })
+        .collect();
+    if params.is_empty() {
+        Ok(SolutionSet::Finite(vec![solution]))
+    } else {
+        let params = params.into_iter().map(|j| vars[j]).collect();
+        Ok(SolutionSet::Parametric { params, solution })
+    }
+}
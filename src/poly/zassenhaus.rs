@@ -0,0 +1,564 @@
+//! Factorization of univariate integer polynomials by the Berlekamp-Zassenhaus algorithm.
+//!
+//! The polynomials here are dense coefficient vectors, from the constant term up, without
+//! trailing zeros.
+
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
+
+/// A dense polynomial over the integers.
+pub(crate) type Dense = Vec<BigInt>;
+
+/// A dense polynomial over a prime field `Z_p`.
+type Modular = Vec<u64>;
+
+/// Factor a primitive, square-free polynomial of positive degree, whose leading coefficient is
+/// positive, into irreducible factors.
+pub(crate) fn factor_squarefree(f: &[BigInt]) -> Vec<Dense> {
+    let n = f.len() - 1;
+    if n <= 1 {
+        return vec![f.to_vec()];
+    }
+
+    let (p, factors) = choose_prime(f);
+    if factors.len() == 1 {
+        return vec![f.to_vec()];
+    }
+
+    // Lift until the modulus exceeds twice the bound on the coefficients of `lc * g` for any
+    // factor `g`.
+    let lc = f[n].clone();
+    let bound = BigInt::from(2) * &lc * mignotte_bound(f);
+    let mut modulus = BigInt::from(p);
+    while modulus <= bound {
+        modulus *= p;
+    }
+    let lifted = hensel_lift(f, p, &factors, &modulus);
+    recombine(f, lifted, &modulus)
+}
+
+/// Choose a prime for which `f` stays square-free with the same degree, and factor `f` modulo
+/// it. A few primes are tried to find fewer modular factors.
+fn choose_prime(f: &[BigInt]) -> (u64, Vec<Modular>) {
+    const TRIES: usize = 3;
+
+    let lc = f.last().unwrap();
+    let mut best: Option<(u64, Vec<Modular>)> = None;
+    let mut tries = 0;
+    for p in primes() {
+        if (lc % BigInt::from(p)).is_zero() {
+            continue;
+        }
+        let fp = reduce(f, p);
+        if degree(&gcd(&fp, &derivative(&fp, p), p)) != 0 {
+            continue;
+        }
+        let factors = berlekamp(&monic(&fp, p), p);
+        if best.as_ref().map_or(true, |b| factors.len() < b.1.len()) {
+            best = Some((p, factors));
+        }
+        tries += 1;
+        if tries == TRIES || best.as_ref().unwrap().1.len() == 1 {
+            break;
+        }
+    }
+    best.expect("no suitable prime")
+}
+
+/// Odd primes, which are small enough to multiply in `u64`.
+fn primes() -> impl Iterator<Item = u64> {
+    (3u64..).step_by(2).filter(|&n| (3..).step_by(2).take_while(|d| d * d <= n).all(|d| n % d != 0))
+}
+
+/// A bound on the coefficients of the factors of `f`, `2 ^ n * |f|_2`.
+fn mignotte_bound(f: &[BigInt]) -> BigInt {
+    let norm2 = f.iter().fold(BigInt::zero(), |acc, c| acc + c * c);
+    let norm = norm2.nth_root(2) + 1;
+    norm << (f.len() - 1)
+}
+
+// Modular arithmetic.
+
+fn trim(mut a: Modular) -> Modular {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+/// The degree, where the zero polynomial has degree 0 too.
+fn degree(a: &[u64]) -> usize {
+    a.len().saturating_sub(1)
+}
+
+fn reduce(f: &[BigInt], p: u64) -> Modular {
+    let p = BigInt::from(p);
+    trim(f.iter().map(|c| c.mod_floor(&p).to_u64().unwrap()).collect())
+}
+
+fn pow_mod(mut b: u64, mut e: u64, p: u64) -> u64 {
+    let mut res = 1;
+    b %= p;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = res * b % p;
+        }
+        b = b * b % p;
+        e >>= 1;
+    }
+    res
+}
+
+fn inverse(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+fn add(a: &[u64], b: &[u64], p: u64) -> Modular {
+    let n = a.len().max(b.len());
+    let get = |v: &[u64], i: usize| v.get(i).cloned().unwrap_or(0);
+    trim((0..n).map(|i| (get(a, i) + get(b, i)) % p).collect())
+}
+
+fn sub(a: &[u64], b: &[u64], p: u64) -> Modular {
+    let n = a.len().max(b.len());
+    let get = |v: &[u64], i: usize| v.get(i).cloned().unwrap_or(0);
+    trim((0..n).map(|i| (get(a, i) + p - get(b, i)) % p).collect())
+}
+
+fn mul(a: &[u64], b: &[u64], p: u64) -> Modular {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut res = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] = (res[i + j] + x * y) % p;
+        }
+    }
+    trim(res)
+}
+
+fn scale(a: &[u64], c: u64, p: u64) -> Modular {
+    trim(a.iter().map(|&x| x * c % p).collect())
+}
+
+fn monic(a: &[u64], p: u64) -> Modular {
+    match a.last() {
+        Some(&lc) => scale(a, inverse(lc, p), p),
+        None => vec![],
+    }
+}
+
+/// Divide `a` by the nonzero `b`.
+fn div_rem(a: &[u64], b: &[u64], p: u64) -> (Modular, Modular) {
+    let mut r = a.to_vec();
+    if r.len() < b.len() {
+        return (vec![], r);
+    }
+    let inv = inverse(*b.last().unwrap(), p);
+    let mut q = vec![0; r.len() - b.len() + 1];
+    for k in (0..q.len()).rev() {
+        let c = r[k + b.len() - 1] * inv % p;
+        q[k] = c;
+        for (j, &y) in b.iter().enumerate() {
+            r[k + j] = (r[k + j] + p - c * y % p) % p;
+        }
+    }
+    r.truncate(b.len() - 1);
+    (trim(q), trim(r))
+}
+
+fn rem(a: &[u64], b: &[u64], p: u64) -> Modular {
+    div_rem(a, b, p).1
+}
+
+/// The monic GCD.
+fn gcd(a: &[u64], b: &[u64], p: u64) -> Modular {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    while !b.is_empty() {
+        let r = rem(&a, &b, p);
+        a = b;
+        b = r;
+    }
+    monic(&a, p)
+}
+
+/// Find `s` and `t` such that `s * a + t * b = 1`, where `a` and `b` are coprime.
+fn bezout(a: &[u64], b: &[u64], p: u64) -> (Modular, Modular) {
+    let (mut r0, mut r1) = (a.to_vec(), b.to_vec());
+    let (mut s0, mut s1) = (vec![1], vec![]);
+    let (mut t0, mut t1) = (vec![], vec![1]);
+    while !r1.is_empty() {
+        let (q, r) = div_rem(&r0, &r1, p);
+        let s = sub(&s0, &mul(&q, &s1, p), p);
+        let t = sub(&t0, &mul(&q, &t1, p), p);
+        r0 = r1;
+        r1 = r;
+        s0 = s1;
+        s1 = s;
+        t0 = t1;
+        t1 = t;
+    }
+    debug_assert_eq!(r0.len(), 1, "not coprime");
+    let inv = inverse(r0[0], p);
+    (scale(&s0, inv, p), scale(&t0, inv, p))
+}
+
+fn derivative(a: &[u64], p: u64) -> Modular {
+    trim(a.iter().enumerate().skip(1).map(|(i, &c)| (i as u64 % p) * c % p).collect())
+}
+
+/// Factor a monic square-free polynomial over `Z_p` into monic irreducible factors by
+/// Berlekamp's algorithm.
+fn berlekamp(f: &[u64], p: u64) -> Vec<Modular> {
+    let n = degree(f);
+    if n <= 1 {
+        return vec![f.to_vec()];
+    }
+
+    // The rows of `Q` are `x ^ (p * i) mod f`.
+    let x_p = pow_poly(&[0, 1], p, f, p);
+    let mut rows = vec![vec![1]];
+    for i in 1..n {
+        let row = rem(&mul(&rows[i - 1], &x_p, p), f, p);
+        rows.push(row);
+    }
+
+    // The polynomials `v` with `v ^ p = v (mod f)` are the null space of `Q - I`, as row vectors.
+    let matrix = (0..n)
+        .map(|j| {
+            (0..n)
+                .map(|i| {
+                    let q = rows[i].get(j).cloned().unwrap_or(0);
+                    if i == j { (q + p - 1) % p } else { q }
+                })
+                .collect()
+        })
+        .collect();
+    let basis = null_space(matrix, p);
+
+    // Each basis vector splits the factors by `gcd(u, v - s)`.
+    let r = basis.len();
+    let mut factors = vec![f.to_vec()];
+    for v in basis.into_iter().map(trim).filter(|v| degree(v) > 0) {
+        if factors.len() == r {
+            break;
+        }
+        factors = factors
+            .into_iter()
+            .flat_map(|u| {
+                if degree(&u) <= 1 {
+                    return vec![u];
+                }
+                (0..p)
+                    .map(|s| gcd(&u, &sub(&v, &[s], p), p))
+                    .filter(|g| degree(g) > 0)
+                    .collect()
+            })
+            .collect();
+    }
+    factors
+}
+
+/// `b ^ e mod m`.
+fn pow_poly(b: &[u64], mut e: u64, m: &[u64], p: u64) -> Modular {
+    let mut res = vec![1];
+    let mut b = rem(b, m, p);
+    while e > 0 {
+        if e & 1 == 1 {
+            res = rem(&mul(&res, &b, p), m, p);
+        }
+        b = rem(&mul(&b, &b, p), m, p);
+        e >>= 1;
+    }
+    res
+}
+
+/// A basis of the null space of a square matrix over `Z_p`.
+fn null_space(mut a: Vec<Vec<u64>>, p: u64) -> Vec<Vec<u64>> {
+    let n = a.len();
+    // Reduce to the reduced row echelon form, remembering the pivot columns.
+    let mut pivots = vec![];
+    let mut row = 0;
+    for col in 0..n {
+        let pivot = match (row..n).find(|&r| a[r][col] != 0) {
+            Some(r) => r,
+            None => continue,
+        };
+        a.swap(row, pivot);
+        let inv = inverse(a[row][col], p);
+        for x in &mut a[row] {
+            *x = *x * inv % p;
+        }
+        let pivot_row = a[row].clone();
+        for (r, other) in a.iter_mut().enumerate() {
+            let c = other[col];
+            if r != row && c != 0 {
+                for (x, &y) in other.iter_mut().zip(&pivot_row) {
+                    *x = (*x + p - c * y % p) % p;
+                }
+            }
+        }
+        pivots.push(col);
+        row += 1;
+    }
+
+    // Each free column gives a basis vector.
+    (0..n)
+        .filter(|c| !pivots.contains(c))
+        .map(|free| {
+            let mut v = vec![0; n];
+            v[free] = 1;
+            for (r, &c) in pivots.iter().enumerate() {
+                v[c] = (p - a[r][free]) % p;
+            }
+            v
+        })
+        .collect()
+}
+
+// Hensel lifting.
+
+fn to_integer(a: &[u64]) -> Dense {
+    a.iter().map(|&c| BigInt::from(c)).collect()
+}
+
+fn trim_int(mut a: Dense) -> Dense {
+    while a.last().map_or(false, Zero::is_zero) {
+        a.pop();
+    }
+    a
+}
+
+fn mul_int(a: &[BigInt], b: &[BigInt]) -> Dense {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut res = vec![BigInt::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    trim_int(res)
+}
+
+fn sub_int(a: &[BigInt], b: &[BigInt]) -> Dense {
+    let n = a.len().max(b.len());
+    let zero = BigInt::zero();
+    let get = |v: &'_ [BigInt], i: usize| v.get(i).cloned().unwrap_or_else(|| zero.clone());
+    trim_int((0..n).map(|i| get(a, i) - get(b, i)).collect())
+}
+
+/// Add `m * b` to `a`.
+fn add_scaled(a: &[BigInt], m: &BigInt, b: &[u64]) -> Dense {
+    let n = a.len().max(b.len());
+    trim_int(
+        (0..n)
+            .map(|i| {
+                let x = a.get(i).cloned().unwrap_or_else(BigInt::zero);
+                x + m * BigInt::from(b.get(i).cloned().unwrap_or(0))
+            })
+            .collect(),
+    )
+}
+
+/// Reduce the coefficients into the symmetric range `(-m / 2, m / 2]`.
+fn symmetric(a: &[BigInt], m: &BigInt) -> Dense {
+    let half = m / 2;
+    trim_int(
+        a.iter()
+            .map(|c| {
+                let c = c.mod_floor(m);
+                if c > half { c - m } else { c }
+            })
+            .collect(),
+    )
+}
+
+/// Lift `f = lc * g_1 * ... * g_r (mod p)` to monic factors modulo `modulus`, which is a power of
+/// `p`.
+fn hensel_lift(f: &[BigInt], p: u64, factors: &[Modular], modulus: &BigInt) -> Vec<Dense> {
+    let lc = reduce(&f[f.len() - 1..], p)[0];
+    let mut target = f.to_vec();
+    let mut lifted = vec![];
+    for (i, g) in factors.iter().enumerate().take(factors.len() - 1) {
+        let h = factors[i + 1..]
+            .iter()
+            .fold(vec![lc], |acc, g| mul(&acc, g, p));
+        let (g, h) = lift_pair(&target, g, &h, p, modulus);
+        lifted.push(g);
+        target = h;
+    }
+
+    // The last factor is the rest divided by the leading coefficient.
+    let inv = target.last().unwrap().mod_inverse(modulus);
+    let last = target.iter().map(|c| c * &inv).collect::<Vec<_>>();
+    lifted.push(symmetric(&last, modulus));
+    lifted
+}
+
+/// Lift `f = g * h (mod p)`, where `g` is monic and coprime to `h`, modulo `modulus`.
+fn lift_pair(f: &[BigInt], g: &[u64], h: &[u64], p: u64, modulus: &BigInt) -> (Dense, Dense) {
+    let (s, t) = bezout(g, h, p);
+    let (mut g, mut h) = (to_integer(g), to_integer(h));
+    let mut m = BigInt::from(p);
+    while m < *modulus {
+        // f - g h = m e, and σ g + τ h = e (mod p).
+        let e = sub_int(f, &mul_int(&g, &h))
+            .into_iter()
+            .map(|c| c / &m)
+            .collect::<Vec<_>>();
+        let e = reduce(&e, p);
+        let (q, tau) = div_rem(&mul(&t, &e, p), &reduce(&g, p), p);
+        let sigma = add(&mul(&s, &e, p), &mul(&q, &reduce(&h, p), p), p);
+        g = add_scaled(&g, &m, &tau);
+        h = add_scaled(&h, &m, &sigma);
+        m *= p;
+        g = symmetric(&g, &m);
+        h = symmetric(&h, &m);
+    }
+    (symmetric(&g, modulus), symmetric(&h, modulus))
+}
+
+/// The modular inverse.
+trait ModInverse {
+    fn mod_inverse(&self, m: &BigInt) -> BigInt;
+}
+
+impl ModInverse for BigInt {
+    fn mod_inverse(&self, m: &BigInt) -> BigInt {
+        let (mut r0, mut r1) = (self.mod_floor(m), m.clone());
+        let (mut s0, mut s1) = (BigInt::one(), BigInt::zero());
+        while !r1.is_zero() {
+            let q = &r0 / &r1;
+            let r = &r0 - &q * &r1;
+            let s = &s0 - &q * &s1;
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+        }
+        debug_assert!(r0.is_one(), "not invertible");
+        s0.mod_floor(m)
+    }
+}
+
+// Recombination.
+
+/// Divide `f` by `g` over the integers, or return `None` if it doesn't divide.
+pub(crate) fn exact_div_int(f: &[BigInt], g: &[BigInt]) -> Option<Dense> {
+    if f.len() < g.len() {
+        return if f.is_empty() { Some(vec![]) } else { None };
+    }
+    let mut r = f.to_vec();
+    let lc = g.last().unwrap();
+    let mut q = vec![BigInt::zero(); f.len() - g.len() + 1];
+    for k in (0..q.len()).rev() {
+        let (c, rem) = r[k + g.len() - 1].div_rem(lc);
+        if !rem.is_zero() {
+            return None;
+        }
+        for (j, y) in g.iter().enumerate() {
+            r[k + j] -= &c * y;
+        }
+        q[k] = c;
+    }
+    if r.iter().all(Zero::is_zero) { Some(trim_int(q)) } else { None }
+}
+
+/// Divide by the GCD of the coefficients, and make the leading coefficient positive.
+pub(crate) fn primitive(f: &[BigInt]) -> Dense {
+    let content = f.iter().fold(BigInt::zero(), |acc, c| acc.gcd(c));
+    let content = if f.last().map_or(false, Signed::is_negative) { -content } else { content };
+    f.iter().map(|c| c / &content).collect()
+}
+
+/// Find the true factors by trying products of subsets of the lifted factors, smallest first.
+fn recombine(f: &[BigInt], mut lifted: Vec<Dense>, modulus: &BigInt) -> Vec<Dense> {
+    let mut f = f.to_vec();
+    let mut res = vec![];
+    let mut size = 1;
+    'sizes: while 2 * size <= lifted.len() {
+        let mut subset = (0..size).collect::<Vec<_>>();
+        loop {
+            // The leading coefficient of `f` is multiplied in, so the product has integer
+            // coefficients if it's a factor.
+            let lc = vec![f.last().unwrap().clone()];
+            let g = subset
+                .iter()
+                .fold(lc, |acc, &i| symmetric(&mul_int(&acc, &lifted[i]), modulus));
+            let g = primitive(&g);
+            if let Some(q) = exact_div_int(&f, &g) {
+                res.push(g);
+                f = q;
+                for &i in subset.iter().rev() {
+                    lifted.remove(i);
+                }
+                continue 'sizes;
+            }
+            if !next_subset(&mut subset, lifted.len()) {
+                break;
+            }
+        }
+        size += 1;
+    }
+    res.push(f);
+    res
+}
+
+/// Advance to the next subset of `0..n` of the same size in lexicographic order.
+pub(crate) fn next_subset(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    match (0..k).rev().find(|&i| subset[i] < n - k + i) {
+        Some(i) => {
+            subset[i] += 1;
+            for j in i + 1..k {
+                subset[j] = subset[j - 1] + 1;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dense(coeffs: &[i64]) -> Dense {
+        coeffs.iter().map(|&c| BigInt::from(c)).collect()
+    }
+
+    #[test]
+    fn berlekamp_works() {
+        // x ^ 4 + 1 = (x ^ 2 + x + 2) (x ^ 2 + 2 x + 2) (mod 3)? It splits into quadratics
+        // modulo every prime.
+        let f = [1, 0, 0, 0, 1];
+        for &p in &[3, 5, 7] {
+            let factors = berlekamp(&f, p);
+            assert_eq!(factors.iter().fold(vec![1], |acc, g| mul(&acc, g, p)), f.to_vec());
+            assert!(factors.len() >= 2);
+        }
+        assert_eq!(berlekamp(&[1, 1, 1], 2).len(), 1);
+    }
+
+    #[test]
+    fn hensel_works() {
+        // x ^ 2 - 2 = (x - 3) (x + 3) (mod 7)
+        let f = dense(&[-2, 0, 1]);
+        let modulus = BigInt::from(7 * 7 * 7);
+        let lifted = hensel_lift(&f, 7, &[vec![4, 1], vec![3, 1]], &modulus);
+        let product = symmetric(&mul_int(&lifted[0], &lifted[1]), &modulus);
+        assert_eq!(product, f);
+    }
+
+    #[test]
+    fn factor_works() {
+        // x ^ 4 + 1 is irreducible over the integers.
+        assert_eq!(factor_squarefree(&dense(&[1, 0, 0, 0, 1])).len(), 1);
+        // 6 x ^ 2 + 5 x + 1 = (2 x + 1) (3 x + 1)
+        let mut factors = factor_squarefree(&dense(&[1, 5, 6]));
+        factors.sort();
+        assert_eq!(factors, vec![dense(&[1, 2]), dense(&[1, 3])]);
+    }
+}
@@ -0,0 +1,112 @@
+use std::f64::consts;
+use std::fmt::{self, Display};
+
+use super::Expr;
+
+/// The exact mathematical constants.
+///
+/// They are displayed in ASCII by default, and in Unicode with the alternate flag.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::Constant;
+///
+/// assert_eq!(format!("{}", Constant::Pi), "pi");
+/// assert_eq!(format!("{:#}", Constant::Pi), "π");
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Constant {
+    /// The ratio of a circle's circumference to its diameter, `π`.
+    Pi,
+    /// The base of the natural logarithm, `e`.
+    E,
+    /// The imaginary unit `i`, where `i ^ 2 = -1`.
+    ImaginaryUnit,
+    /// The positive real infinity.
+    Infinity,
+    /// The negative real infinity.
+    NegInfinity,
+    /// The infinity in the complex plane, which has no direction, like `1 / 0` in the complex
+    /// numbers.
+    ComplexInfinity,
+}
+
+use self::Constant::*;
+
+impl Constant {
+    /// Get the ASCII name of the constant.
+    pub fn ascii(self) -> &'static str {
+        match self {
+            Pi => "pi",
+            E => "e",
+            ImaginaryUnit => "i",
+            Infinity => "oo",
+            NegInfinity => "-oo",
+            ComplexInfinity => "zoo",
+        }
+    }
+
+    /// Get the Unicode name of the constant.
+    pub fn unicode(self) -> &'static str {
+        match self {
+            Pi => "π",
+            E => "e",
+            ImaginaryUnit => "i",
+            Infinity => "∞",
+            NegInfinity => "-∞",
+            ComplexInfinity => "∞\u{303}",
+        }
+    }
+
+    /// Check if the constant is one of the infinities.
+    pub fn is_infinite(self) -> bool {
+        match self {
+            Infinity | NegInfinity | ComplexInfinity => true,
+            _ => false,
+        }
+    }
+
+    /// Get the real value of the constant, or `None` if it isn't real.
+    pub fn to_f64(self) -> Option<f64> {
+        match self {
+            Pi => Some(consts::PI),
+            E => Some(consts::E),
+            Infinity => Some(::std::f64::INFINITY),
+            NegInfinity => Some(::std::f64::NEG_INFINITY),
+            ImaginaryUnit | ComplexInfinity => None,
+        }
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.unicode())
+        } else {
+            write!(f, "{}", self.ascii())
+        }
+    }
+}
+
+/// Constructors of constants.
+impl Expr {
+    /// Construct `π`.
+    pub fn pi() -> Expr {
+        Expr::Const(Pi)
+    }
+
+    /// Construct `e`.
+    pub fn e() -> Expr {
+        Expr::Const(E)
+    }
+
+    /// Construct the imaginary unit `i`.
+    pub fn i() -> Expr {
+        Expr::Const(ImaginaryUnit)
+    }
+
+    /// Construct the positive infinity.
+    pub fn infinity() -> Expr {
+        Expr::Const(Infinity)
+    }
+}
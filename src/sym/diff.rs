@@ -0,0 +1,157 @@
+use super::{Expr, Func, Symbol};
+use super::simplify::{sum, product, power, apply};
+use self::Expr::*;
+
+/// Differentiation.
+impl Expr {
+    /// Differentiate the expression with respect to `s`. The result is simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!(x.pow(3).diff(x), (3i32 * x.pow(2)).simplify());
+    /// assert_eq!((x * y).diff(y), Expr::Sym(x));
+    /// assert_eq!(Expr::integer(5).diff(x), Expr::integer(0));
+    /// ```
+    pub fn diff(&self, s: Symbol) -> Expr {
+        derivative(&self.simplify(), s)
+    }
+
+    /// Differentiate the expression `n` times with respect to `s`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(x.pow(3).diff_n(x, 2), (6i32 * x).simplify());
+    /// assert_eq!(x.pow(3).diff_n(x, 0), x.pow(3));
+    /// ```
+    pub fn diff_n(&self, s: Symbol, n: usize) -> Expr {
+        let mut res = self.simplify();
+        for _ in 0..n {
+            res = derivative(&res, s);
+        }
+        res
+    }
+
+    /// Take the mixed partial derivative, differentiating with respect to each symbol the given
+    /// number of times, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let e = x.pow(2) * y.pow(3);
+    /// assert_eq!(e.diff_many(&[(x, 1), (y, 2)]), (12i32 * x * y).simplify());
+    /// ```
+    pub fn diff_many(&self, vars: &[(Symbol, usize)]) -> Expr {
+        vars.iter()
+            .fold(self.simplify(), |res, &(s, n)| res.diff_n(s, n))
+    }
+}
+
+/// Differentiate a canonical expression.
+fn derivative(e: &Expr, s: Symbol) -> Expr {
+    if !e.has_symbol(s) {
+        return if *e == Undefined { Undefined } else { Expr::integer(0) };
+    }
+
+    match *e {
+        Integer(_) | Rational(_) | Approx(_) | Const(_) | Undefined => unreachable!(),
+        // It must be `s` itself.
+        Sym(_) => Expr::integer(1),
+        Neg(ref e) => product(vec![Expr::integer(-1), derivative(e, s)]),
+        Sum(ref args) => sum(args.iter().map(|arg| derivative(arg, s)).collect()),
+        // (a b c)' = a' b c + a b' c + a b c'
+        Product(ref args) => sum(
+            (0..args.len())
+                .map(|i| {
+                    let mut factors = args.clone();
+                    factors[i] = derivative(&args[i], s);
+                    product(factors)
+                })
+                .collect(),
+        ),
+        // (n / d)' = (n' d - n d') / d ^ 2
+        Ratio(ref n, ref d) => {
+            let (n, d) = (n.simplify(), d.simplify());
+            let numer = sum(vec![
+                product(vec![derivative(&n, s), d.clone()]),
+                product(vec![Expr::integer(-1), n.clone(), derivative(&d, s)]),
+            ]);
+            product(vec![numer, power(d, Expr::integer(-2))])
+        }
+        Pow(ref b, ref p) => {
+            let (b, p) = ((**b).clone(), (**p).clone());
+            if !p.has_symbol(s) {
+                // (b ^ p)' = p b ^ (p - 1) b'
+                let p_1 = sum(vec![p.clone(), Expr::integer(-1)]);
+                return product(vec![p.clone(), power(b.clone(), p_1), derivative(&b, s)]);
+            }
+            // (b ^ p)' = b ^ p (p' ln(b) + p b' / b)
+            let ln_b = apply(Func::Ln, vec![b.clone()]);
+            let inner = sum(vec![
+                product(vec![derivative(&p, s), ln_b]),
+                product(vec![p.clone(), derivative(&b, s), power(b.clone(), Expr::integer(-1))]),
+            ]);
+            product(vec![e.clone(), inner])
+        }
+        // f(u, v)' = f^(1, 0)(u, v) u' + f^(0, 1)(u, v) v'
+        Apply(Func::Undef(f, ref orders), ref args) => sum(
+            (0..args.len())
+                .map(|i| {
+                    let mut orders = if orders.is_empty() {
+                        vec![0; args.len()]
+                    } else {
+                        orders.clone()
+                    };
+                    orders[i] += 1;
+                    let f_i = apply(Func::Undef(f, orders), args.clone());
+                    product(vec![f_i, derivative(&args[i], s)])
+                })
+                .collect(),
+        ),
+        Apply(ref f, ref args) => {
+            if f.arity() != Some(1) {
+                // Functions of more arguments are rewritten by simplification.
+                return derivative(&e.simplify(), s);
+            }
+            let u = &args[0];
+            product(vec![func_derivative(f, u), derivative(u, s)])
+        }
+    }
+}
+
+/// The derivative of `f(u)` with respect to `u`.
+fn func_derivative(f: &Func, u: &Expr) -> Expr {
+    let f_u = |f| apply(f, vec![u.clone()]);
+    let neg = |e| product(vec![Expr::integer(-1), e]);
+    let u2 = power(u.clone(), Expr::integer(2));
+    match *f {
+        Func::Sin => f_u(Func::Cos),
+        Func::Cos => neg(f_u(Func::Sin)),
+        // tan' = 1 + tan ^ 2
+        Func::Tan => sum(vec![Expr::integer(1), power(f_u(Func::Tan), Expr::integer(2))]),
+        // asin' = (1 - u ^ 2) ^ (-1/2)
+        Func::Asin => power(sum(vec![Expr::integer(1), neg(u2)]), Expr::rational(-1, 2)),
+        Func::Acos => neg(power(sum(vec![Expr::integer(1), neg(u2)]), Expr::rational(-1, 2))),
+        // atan' = (1 + u ^ 2) ^ -1
+        Func::Atan => power(sum(vec![Expr::integer(1), u2]), Expr::integer(-1)),
+        Func::Sinh => f_u(Func::Cosh),
+        Func::Cosh => f_u(Func::Sinh),
+        // tanh' = 1 - tanh ^ 2
+        Func::Tanh => sum(vec![Expr::integer(1), neg(power(f_u(Func::Tanh), Expr::integer(2)))]),
+        Func::Exp => f_u(Func::Exp),
+        Func::Ln => power(u.clone(), Expr::integer(-1)),
+        Func::Sqrt => product(vec![Expr::rational(1, 2), power(u.clone(), Expr::rational(-1, 2))]),
+        // abs' = u / abs(u)
+        Func::Abs => product(vec![u.clone(), power(f_u(Func::Abs), Expr::integer(-1))]),
+        Func::Log | Func::Undef(..) => unreachable!("not a built-in unary function"),
+    }
+}
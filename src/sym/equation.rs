@@ -0,0 +1,67 @@
+use std::fmt::{self, Display};
+
+use super::{Expr, Symbol};
+
+/// An equation `lhs = rhs` between two expressions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equation {
+    /// The left-hand side.
+    pub lhs: Expr,
+    /// The right-hand side.
+    pub rhs: Expr,
+}
+
+impl Equation {
+    /// Construct a new equation.
+    pub fn new<L: Into<Expr>, R: Into<Expr>>(lhs: L, rhs: R) -> Equation {
+        Equation { lhs: lhs.into(), rhs: rhs.into() }
+    }
+
+    /// The expression `lhs - rhs`, which is zero exactly when the equation holds.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::Symbol;
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(x.pow(2).eq_to(1).to_expr(), x.pow(2) - 1);
+    /// ```
+    pub fn to_expr(&self) -> Expr {
+        self.lhs.clone() - self.rhs.clone()
+    }
+
+    /// Simplify both sides.
+    pub fn simplify(&self) -> Equation {
+        Equation { lhs: self.lhs.simplify(), rhs: self.rhs.simplify() }
+    }
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.lhs, self.rhs)
+    }
+}
+
+impl Expr {
+    /// Construct the equation `self = rhs`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Equation, Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let eq = (2i32 * x).eq_to(Expr::integer(4));
+    /// assert_eq!(eq, Equation::new(2i32 * x, 4));
+    /// assert_eq!(format!("{}", eq), "2 * x = 4");
+    /// ```
+    pub fn eq_to<E: Into<Expr>>(self, rhs: E) -> Equation {
+        Equation::new(self, rhs)
+    }
+}
+
+impl Symbol {
+    /// Construct the equation `self = rhs`.
+    pub fn eq_to<E: Into<Expr>>(self, rhs: E) -> Equation {
+        Equation::new(self, rhs)
+    }
+}
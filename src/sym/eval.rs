@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use num::{BigRational, Zero, One, Signed, ToPrimitive};
+use num::complex::Complex64;
+
+use super::{Constant, Expr, Func, Function, Symbol};
+use super::simplify::{to_f64, to_rational};
+use self::Expr::*;
+
+/// The error type of evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// A symbol has no value in the environment.
+    UnboundSymbol(Symbol),
+    /// The expression divides by zero.
+    DivisionByZero,
+    /// The expression contains `Undefined`.
+    Undefined,
+    /// The value can't be represented exactly.
+    Inexact,
+    /// The value isn't a real number, like `i`.
+    NonReal,
+    /// The expression applies an undefined function.
+    UndefinedFunction(Function),
+    /// The expression applies a function to the wrong number of arguments.
+    WrongArity(Func),
+    /// A numeric method didn't reach the requested accuracy.
+    NoConvergence,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EvalError::UnboundSymbol(s) => write!(f, "symbol `{}` is unbound", s),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::Undefined => write!(f, "undefined value"),
+            EvalError::Inexact => write!(f, "inexact value"),
+            EvalError::NonReal => write!(f, "non-real value"),
+            EvalError::UndefinedFunction(g) => write!(f, "function `{}` is undefined", g),
+            EvalError::WrongArity(ref g) => {
+                write!(f, "function `{}` is applied to the wrong number of arguments", g)
+            }
+            EvalError::NoConvergence => write!(f, "no convergence"),
+        }
+    }
+}
+
+impl Error for EvalError {
+    fn description(&self) -> &str {
+        match *self {
+            EvalError::UnboundSymbol(_) => "unbound symbol",
+            EvalError::DivisionByZero => "division by zero",
+            EvalError::Undefined => "undefined value",
+            EvalError::Inexact => "inexact value",
+            EvalError::NonReal => "non-real value",
+            EvalError::UndefinedFunction(_) => "undefined function",
+            EvalError::WrongArity(_) => "wrong number of arguments",
+            EvalError::NoConvergence => "no convergence",
+        }
+    }
+}
+
+/// Evaluation.
+impl Expr {
+    /// Evaluate the expression numerically, looking up symbols in `env`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use symrs::sym::{Expr, Symbol, EvalError};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let mut env = HashMap::new();
+    /// env.insert(x, 2.0);
+    /// assert_eq!((x.pow(3) + 1).eval(&env), Ok(9.0));
+    /// assert_eq!((1i32 / (x - 2)).eval(&env), Err(EvalError::DivisionByZero));
+    /// assert_eq!((x + y).eval(&env), Err(EvalError::UnboundSymbol(y)));
+    /// ```
+    pub fn eval(&self, env: &HashMap<Symbol, f64>) -> Result<f64, EvalError> {
+        match *self {
+            Integer(_) | Rational(_) | Approx(_) => Ok(to_f64(self)),
+            Const(c) => c.to_f64().ok_or(EvalError::NonReal),
+            Sym(s) => env.get(&s).cloned().ok_or(EvalError::UnboundSymbol(s)),
+            Undefined => Err(EvalError::Undefined),
+            Neg(ref e) => Ok(-e.eval(env)?),
+            Sum(ref args) => args.iter().map(|arg| arg.eval(env)).sum(),
+            Product(ref args) => args.iter().map(|arg| arg.eval(env)).product(),
+            Ratio(ref n, ref d) => {
+                let (n, d) = (n.eval(env)?, d.eval(env)?);
+                if d == 0.0 {
+                    return Err(EvalError::DivisionByZero);
+                }
+                Ok(n / d)
+            }
+            Pow(ref b, ref e) => {
+                let (b, e) = (b.eval(env)?, e.eval(env)?);
+                if b == 0.0 && e < 0.0 {
+                    return Err(EvalError::DivisionByZero);
+                }
+                real(&[b, e], b.powf(e))
+            }
+            Apply(ref f, ref args) if !f.accepts(args.len()) => {
+                Err(EvalError::WrongArity(f.clone()))
+            }
+            Apply(Func::Undef(g, _), _) => Err(EvalError::UndefinedFunction(g)),
+            Apply(ref f, ref args) => {
+                let args = args.iter()
+                    .map(|arg| arg.eval(env))
+                    .collect::<Result<Vec<_>, _>>()?;
+                real(&args, f.eval_f64(&args))
+            }
+        }
+    }
+
+    /// Evaluate the expression numerically in the complex plane, looking up symbols in `env`.
+    ///
+    /// Powers and functions take their principal values, so `(-1) ^ (1/2)` is `i`. The complex
+    /// infinity is `Undefined`.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use std::collections::HashMap;
+    /// use num::complex::Complex64;
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let mut env = HashMap::new();
+    /// env.insert(x, Complex64::new(0.0, 2.0));
+    /// assert_eq!((x.pow(2) + Expr::i()).eval_complex(&env), Ok(Complex64::new(-4.0, 1.0)));
+    /// # }
+    /// ```
+    pub fn eval_complex(&self, env: &HashMap<Symbol, Complex64>) -> Result<Complex64, EvalError> {
+        match *self {
+            Integer(_) | Rational(_) | Approx(_) => Ok(to_f64(self).into()),
+            Const(Constant::ImaginaryUnit) => Ok(Complex64::i()),
+            Const(c) => c.to_f64().map(Complex64::from).ok_or(EvalError::Undefined),
+            Sym(s) => env.get(&s).cloned().ok_or(EvalError::UnboundSymbol(s)),
+            Undefined => Err(EvalError::Undefined),
+            Neg(ref e) => Ok(-e.eval_complex(env)?),
+            Sum(ref args) => args.iter().map(|arg| arg.eval_complex(env)).sum(),
+            Product(ref args) => args.iter().map(|arg| arg.eval_complex(env)).product(),
+            Ratio(ref n, ref d) => {
+                let (n, d) = (n.eval_complex(env)?, d.eval_complex(env)?);
+                if d.is_zero() {
+                    return Err(EvalError::DivisionByZero);
+                }
+                Ok(n / d)
+            }
+            Pow(ref b, ref e) => {
+                let (b, e) = (b.eval_complex(env)?, e.eval_complex(env)?);
+                if b.is_zero() {
+                    return if e.re < 0.0 {
+                        Err(EvalError::DivisionByZero)
+                    } else if e.re == 0.0 {
+                        Ok(Complex64::one())
+                    } else {
+                        Ok(b)
+                    };
+                }
+                // Integer powers are exact for real bases.
+                if e.im == 0.0 && e.re.fract() == 0.0 && e.re.abs() <= f64::from(u16::max_value()) {
+                    let res = num::pow(b, e.re.abs() as usize);
+                    return Ok(if e.re < 0.0 { res.inv() } else { res });
+                }
+                Ok(b.powc(e))
+            }
+            Apply(ref f, ref args) if !f.accepts(args.len()) => {
+                Err(EvalError::WrongArity(f.clone()))
+            }
+            Apply(Func::Undef(g, _), _) => Err(EvalError::UndefinedFunction(g)),
+            Apply(ref f, ref args) => {
+                let args = args.iter()
+                    .map(|arg| arg.eval_complex(env))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(f.eval_complex(&args))
+            }
+        }
+    }
+
+    /// Evaluate the expression exactly, looking up symbols in `env`.
+    ///
+    /// Approximate values, constants like `pi`, irrational powers like `2 ^ (1/2)` and functions
+    /// at values like `sin(1)` can't be evaluated exactly, and return `EvalError::Inexact`.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate num;
+    /// extern crate symrs;
+    ///
+    /// use std::collections::HashMap;
+    /// use num::BigRational;
+    /// use symrs::sym::{Expr, Symbol, EvalError};
+    ///
+    /// # fn main() {
+    /// let x = Symbol::new("x");
+    /// let mut env = HashMap::new();
+    /// env.insert(x, BigRational::from_integer(3.into()));
+    /// let third = BigRational::new(1.into(), 3.into());
+    /// assert_eq!((1i32 / x).eval_exact(&env), Ok(third));
+    /// assert_eq!(x.pow(0.5).eval_exact(&env), Err(EvalError::Inexact));
+    /// # }
+    /// ```
+    pub fn eval_exact(&self, env: &HashMap<Symbol, BigRational>) -> Result<BigRational, EvalError> {
+        match *self {
+            Integer(ref i) => Ok(BigRational::from_integer(i.clone())),
+            Rational(ref r) => Ok(r.clone()),
+            Approx(_) => Err(EvalError::Inexact),
+            Const(Constant::ImaginaryUnit) | Const(Constant::ComplexInfinity) => {
+                Err(EvalError::NonReal)
+            }
+            Const(_) => Err(EvalError::Inexact),
+            Sym(s) => env.get(&s).cloned().ok_or(EvalError::UnboundSymbol(s)),
+            Undefined => Err(EvalError::Undefined),
+            Neg(ref e) => Ok(-e.eval_exact(env)?),
+            Sum(ref args) => args.iter()
+                .try_fold(BigRational::zero(), |acc, arg| Ok(acc + arg.eval_exact(env)?)),
+            Product(ref args) => args.iter()
+                .try_fold(BigRational::one(), |acc, arg| Ok(acc * arg.eval_exact(env)?)),
+            Ratio(ref n, ref d) => {
+                let (n, d) = (n.eval_exact(env)?, d.eval_exact(env)?);
+                if d.is_zero() {
+                    return Err(EvalError::DivisionByZero);
+                }
+                Ok(n / d)
+            }
+            Pow(ref b, ref e) => {
+                let (b, e) = (b.eval_exact(env)?, e.eval_exact(env)?);
+                if b.is_zero() && e.is_negative() {
+                    return Err(EvalError::DivisionByZero);
+                }
+                if !e.is_integer() {
+                    // Roots like `4 ^ (1/2)` are exact if the simplified power is rational.
+                    if b.is_negative() {
+                        return Err(EvalError::NonReal);
+                    }
+                    let power = Expr::from_big_rational(b).pow(Expr::from_big_rational(e));
+                    return to_rational(&power.simplify()).ok_or(EvalError::Inexact);
+                }
+                let e = e.to_integer();
+                let n = e.abs().to_usize().ok_or(EvalError::Inexact)?;
+                let res = num::pow(b, n);
+                Ok(if e.is_negative() { res.recip() } else { res })
+            }
+            Apply(ref f, ref args) if !f.accepts(args.len()) => {
+                Err(EvalError::WrongArity(f.clone()))
+            }
+            Apply(Func::Undef(g, _), _) => Err(EvalError::UndefinedFunction(g)),
+            Apply(ref f, ref args) => {
+                // Only exact values like `sin(0)` or `sqrt(4)` can be evaluated.
+                let args = args.iter()
+                    .map(|arg| arg.eval_exact(env).map(Expr::from_big_rational))
+                    .collect::<Result<Vec<_>, _>>()?;
+                to_rational(&Apply(f.clone(), args).simplify()).ok_or(EvalError::Inexact)
+            }
+        }
+    }
+}
+
+/// Check that a value computed from finite real inputs is real, like `sqrt(-1)` isn't.
+fn real(inputs: &[f64], value: f64) -> Result<f64, EvalError> {
+    if value.is_nan() && inputs.iter().all(|x| x.is_finite()) {
+        Err(EvalError::NonReal)
+    } else {
+        Ok(value)
+    }
+}
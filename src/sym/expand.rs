@@ -0,0 +1,198 @@
+use num::{BigInt, One, Signed, ToPrimitive};
+
+use super::Expr;
+use super::simplify::{sum, product, power, apply, is_negative_term};
+use self::Expr::*;
+
+/// Expansion.
+impl Expr {
+    /// Expand the expression. The result is simplified.
+    ///
+    /// Products are distributed over sums, and integer powers of sums are expanded by the
+    /// multinomial theorem. Denominators like `(x + 1) ^ -2` are expanded inside, and arguments of
+    /// functions are expanded too.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!((x * (x + y)).expand(), (x.pow(2) + x * y).simplify());
+    /// assert_eq!((x + 1).pow(2).expand(), (x.pow(2) + 2i32 * x + 1).simplify());
+    /// ```
+    pub fn expand(&self) -> Expr {
+        expand(&self.simplify())
+    }
+
+    /// Expand only the numerator of each term, leaving the denominators as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(
+    ///     ((x + 1).pow(2) / (x - 1).pow(2)).expand_numer(),
+    ///     ((x.pow(2) + 2i32 * x + 1) / (x - 1).pow(2)).simplify()
+    /// );
+    /// ```
+    pub fn expand_numer(&self) -> Expr {
+        match self.simplify() {
+            Sum(args) => sum(args.iter().map(expand_numer).collect()),
+            e => expand_numer(&e),
+        }
+    }
+
+    /// Distribute powers over products, so `(x * y) ^ a` becomes `x ^ a * y ^ a`. The result is
+    /// simplified.
+    ///
+    /// Integer powers are always distributed by simplification. For other powers it's valid only
+    /// if the factors are positive, which is assumed here.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let a = Symbol::new("a");
+    /// assert_eq!((x * y).pow(a).expand_power_base(), (x.pow(a) * y.pow(a)).simplify());
+    /// ```
+    pub fn expand_power_base(&self) -> Expr {
+        expand_power_base(&self.simplify())
+    }
+}
+
+/// Expand a canonical expression.
+fn expand(e: &Expr) -> Expr {
+    match *e {
+        Sum(ref args) => sum(args.iter().map(expand).collect()),
+        Product(ref args) => args.iter()
+            .map(expand)
+            .fold(Expr::integer(1), |acc, arg| distribute(&acc, &arg)),
+        Pow(ref b, ref p) => expand_power(power(expand(b), expand(p))),
+        Apply(ref f, ref args) => apply(f.clone(), args.iter().map(expand).collect()),
+        _ => e.clone(),
+    }
+}
+
+/// Expand a canonical power whose base and exponent are expanded.
+fn expand_power(e: Expr) -> Expr {
+    match e {
+        Pow(b, p) => {
+            let n = match (&*b, &*p) {
+                (&Sum(_), &Integer(ref n)) => n.clone(),
+                _ => return Pow(b, p),
+            };
+            let terms = match *b {
+                Sum(terms) => terms,
+                _ => unreachable!(),
+            };
+            let res = multinomial(&terms, &n.abs());
+            if n.is_negative() { power(res, Expr::integer(-1)) } else { res }
+        }
+        // Integer powers of products are distributed by simplification.
+        Product(args) => args.into_iter()
+            .map(expand_power)
+            .fold(Expr::integer(1), |acc, arg| distribute(&acc, &arg)),
+        e => e,
+    }
+}
+
+/// Multiply two expanded expressions, distributing over sums.
+fn distribute(a: &Expr, b: &Expr) -> Expr {
+    match (a, b) {
+        (&Sum(ref args), b) => sum(args.iter().map(|arg| distribute(arg, b)).collect()),
+        (a, &Sum(ref args)) => sum(args.iter().map(|arg| distribute(a, arg)).collect()),
+        (a, b) => product(vec![a.clone(), b.clone()]),
+    }
+}
+
+/// Expand `(a_1 + ... + a_k) ^ n` by the multinomial theorem:
+///
+/// ```text
+/// sum(n! / (e_1! ... e_k!) * a_1 ^ e_1 ... a_k ^ e_k, e_1 + ... + e_k = n)
+/// ```
+fn multinomial(terms: &[Expr], n: &BigInt) -> Expr {
+    let n = match n.to_usize() {
+        Some(n) => n,
+        // Too large to expand.
+        None => return power(Sum(terms.to_vec()), Integer(n.clone())),
+    };
+
+    let mut res = vec![];
+    let mut exps = vec![0; terms.len()];
+    compositions(n, &mut exps, 0, &mut |exps| {
+        let mut factors = vec![Expr::integer(multinomial_coeff(n, exps))];
+        for (term, &e) in terms.iter().zip(exps) {
+            factors.push(power(term.clone(), Expr::integer(e)));
+        }
+        // Powers of terms may be sums again, like `(x + 1) ^ (1/2)` squared.
+        res.push(expand(&product(factors)));
+    });
+    sum(res)
+}
+
+/// Call `f` with each way to write `n` as the sum of `exps[i..]`.
+fn compositions<F: FnMut(&[usize])>(n: usize, exps: &mut [usize], i: usize, f: &mut F) {
+    if i + 1 == exps.len() {
+        exps[i] = n;
+        f(exps);
+        return;
+    }
+    for e in 0..n + 1 {
+        exps[i] = e;
+        compositions(n - e, exps, i + 1, f);
+    }
+}
+
+/// `n! / (e_1! ... e_k!)`, where `e_1 + ... + e_k = n`.
+fn multinomial_coeff(n: usize, exps: &[usize]) -> BigInt {
+    // The product of binomial coefficients `C(e_1, e_1) C(e_1 + e_2, e_2) ...`.
+    let mut res = BigInt::one();
+    let mut total = 0;
+    for &e in exps {
+        for j in 1..e + 1 {
+            res = res * BigInt::from(total + j) / BigInt::from(j);
+        }
+        total += e;
+    }
+    debug_assert_eq!(total, n);
+    res
+}
+
+/// Expand the numerator of a canonical term.
+fn expand_numer(e: &Expr) -> Expr {
+    let factors = match *e {
+        Product(ref args) => args.clone(),
+        ref e => vec![e.clone()],
+    };
+    let (denom, numer): (Vec<_>, Vec<_>) = factors.into_iter().partition(is_denominator);
+    product(vec![expand(&product(numer)), product(denom)])
+}
+
+/// Check if a canonical factor is in the denominator, like `x ^ -2` or `x ^ (-1 * y)`.
+fn is_denominator(e: &Expr) -> bool {
+    match *e {
+        Pow(_, ref p) => is_negative_term(p),
+        _ => false,
+    }
+}
+
+/// Distribute powers over products in a canonical expression.
+fn expand_power_base(e: &Expr) -> Expr {
+    match *e {
+        Sum(ref args) => sum(args.iter().map(expand_power_base).collect()),
+        Product(ref args) => product(args.iter().map(expand_power_base).collect()),
+        Pow(ref b, ref p) => {
+            let p = expand_power_base(p);
+            match expand_power_base(b) {
+                Product(args) => product(args.into_iter().map(|arg| power(arg, p.clone())).collect()),
+                b => power(b, p),
+            }
+        }
+        Apply(ref f, ref args) => apply(f.clone(), args.iter().map(expand_power_base).collect()),
+        _ => e.clone(),
+    }
+}
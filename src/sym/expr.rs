@@ -1,19 +1,40 @@
+use std::cmp::Ordering;
+use std::f64;
 use std::fmt::{self, Display};
-use num::BigInt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use num::{BigInt, BigRational, Zero};
 use itertools::Itertools;
 
-use super::Symbol;
+use super::{Constant, Func, Symbol};
 use self::Expr::*;
 
 /// An owned expression representation.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// # Comparison
+///
+/// Expressions are compared structurally, so `x + 1` and `1 + x` differ until they're
+/// simplified. `Approx` values are equal when they're numerically equal, so `0.0` equals `-0.0`,
+/// and all `NaN`s are equal to each other.
+///
+/// The total order of `Ord` compares the variants in the order they're declared, from `Integer`
+/// to `Undefined`, and then their contents: numbers by value with `NaN` the greatest, symbols
+/// by name, constants in the order of `Constant`, functions by `Func` and then the arguments,
+/// and argument lists lexicographically. It's suitable for sorting and search trees, and it's
+/// different from the order of the arguments in canonical sums and products, which `simplify`
+/// chooses so that `1 + x + x ^ 2` is in order.
+#[derive(Debug, Clone)]
 pub enum Expr {
     /// Represent an integer.
     Integer(BigInt),
+    /// Represent an exact fraction in lowest terms, like `1/3`.
+    Rational(BigRational),
     /// Represent a symbol like `x`, `y`, or `delta`.
     Sym(Symbol),
     /// Represent a float approximate value.
     Approx(f64),
+    /// Represent an exact constant, like `pi` or `oo`.
+    Const(Constant),
 
     /// Represent negative of a expression.
     Neg(Box<Expr>),
@@ -27,10 +48,105 @@ pub enum Expr {
     /// Represent a power.
     Pow(Box<Expr>, Box<Expr>),
 
+    /// Represent a function application, like `sin(x)`.
+    Apply(Func, Vec<Expr>),
+
     /// Represent an undefined value.
     Undefined,
 }
 
+impl PartialEq for Expr {
+    fn eq(&self, other: &Expr) -> bool {
+        match (self, other) {
+            (Integer(a), Integer(b)) => a == b,
+            (Rational(a), Rational(b)) => a == b,
+            (Sym(a), Sym(b)) => a == b,
+            (Approx(a), Approx(b)) => cmp_approx(*a, *b) == Ordering::Equal,
+            (Const(a), Const(b)) => a == b,
+            (Neg(a), Neg(b)) => a == b,
+            (Sum(a), Sum(b)) | (Product(a), Product(b)) => a == b,
+            (Ratio(a1, b1), Ratio(a2, b2)) | (Pow(a1, b1), Pow(a2, b2)) => a1 == a2 && b1 == b2,
+            (Apply(f, a), Apply(g, b)) => f == g && a == b,
+            (Undefined, Undefined) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Expr {}
+
+impl PartialOrd for Expr {
+    fn partial_cmp(&self, other: &Expr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Expr {
+    fn cmp(&self, other: &Expr) -> Ordering {
+        match (self, other) {
+            (Integer(a), Integer(b)) => a.cmp(b),
+            (Rational(a), Rational(b)) => a.cmp(b),
+            (Sym(a), Sym(b)) => a.cmp(b),
+            (Approx(a), Approx(b)) => cmp_approx(*a, *b),
+            (Const(a), Const(b)) => a.cmp(b),
+            (Neg(a), Neg(b)) => a.cmp(b),
+            (Sum(a), Sum(b)) | (Product(a), Product(b)) => a.cmp(b),
+            (Ratio(a1, b1), Ratio(a2, b2)) | (Pow(a1, b1), Pow(a2, b2)) => {
+                a1.cmp(a2).then_with(|| b1.cmp(b2))
+            }
+            (Apply(f, a), Apply(g, b)) => f.cmp(g).then_with(|| a.cmp(b)),
+            (Undefined, Undefined) => Ordering::Equal,
+            _ => self.variant_index().cmp(&other.variant_index()),
+        }
+    }
+}
+
+impl Hash for Expr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match *self {
+            Integer(ref i) => i.hash(state),
+            Rational(ref r) => r.hash(state),
+            Sym(s) => s.hash(state),
+            Approx(f) => approx_bits(f).hash(state),
+            Const(c) => c.hash(state),
+            Neg(ref e) => e.hash(state),
+            Sum(ref args) | Product(ref args) => args.hash(state),
+            Ratio(ref a, ref b) | Pow(ref a, ref b) => {
+                a.hash(state);
+                b.hash(state);
+            }
+            Apply(ref f, ref args) => {
+                f.hash(state);
+                args.hash(state);
+            }
+            Undefined => {}
+        }
+    }
+}
+
+/// Compare approximate values, where `-0.0` equals `0.0`, and `NaN` equals itself and is greater
+/// than everything else.
+fn cmp_approx(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+/// The bits of an approximate value, which are the same when the values compare equal.
+pub(crate) fn approx_bits(f: f64) -> u64 {
+    if f.is_nan() {
+        f64::NAN.to_bits()
+    } else if f == 0.0 {
+        0
+    } else {
+        f.to_bits()
+    }
+}
+
 /// Constructors.
 impl Expr {
     /// Construct a integer value.
@@ -38,6 +154,35 @@ impl Expr {
         Integer(i.into())
     }
 
+    /// Construct a rational value `n / d`, reduced to lowest terms.
+    ///
+    /// It's an integer if `d` divides `n`, and `Undefined` if `d` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::Expr;
+    ///
+    /// assert_eq!(Expr::rational(2, 6), Expr::rational(1, 3));
+    /// assert_eq!(Expr::rational(6, 2), Expr::integer(3));
+    /// assert_eq!(Expr::rational(1, 0), Expr::Undefined);
+    /// ```
+    pub fn rational<N: Into<BigInt>, D: Into<BigInt>>(n: N, d: D) -> Expr {
+        let d = d.into();
+        if d.is_zero() {
+            return Undefined;
+        }
+        Expr::from_big_rational(BigRational::new(n.into(), d))
+    }
+
+    /// Construct a number from a rational value, which is an integer if possible.
+    pub(crate) fn from_big_rational(r: BigRational) -> Expr {
+        if r.is_integer() {
+            Integer(r.to_integer())
+        } else {
+            Rational(r)
+        }
+    }
+
     /// Construct a symbol.
     pub fn symbol(s: &str) -> Expr {
         Sym(Symbol::new(s))
@@ -73,21 +218,56 @@ impl Expr {
     /// ```
     pub fn is_primitive(&self) -> bool {
         match *self {
-            Integer(_) | Sym(_) | Approx(_) => true,
+            Integer(_) | Rational(_) | Sym(_) | Approx(_) | Const(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if the expression is a numeric constant.
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// assert_eq!(Expr::integer(1).is_number(), true);
+    /// assert_eq!(Expr::rational(1, 2).is_number(), true);
+    /// assert_eq!(Expr::approximate(1).is_number(), true);
+    /// assert_eq!(Expr::symbol("x").is_number(), false);
+    /// ```
+    pub fn is_number(&self) -> bool {
+        match *self {
+            Integer(_) | Rational(_) | Approx(_) => true,
             _ => false,
         }
     }
 
+    /// The position of the variant in the declaration, which orders the variants in `Ord`.
+    fn variant_index(&self) -> u8 {
+        match *self {
+            Integer(_) => 0,
+            Rational(_) => 1,
+            Sym(_) => 2,
+            Approx(_) => 3,
+            Const(_) => 4,
+            Neg(_) => 5,
+            Sum(_) => 6,
+            Product(_) => 7,
+            Ratio(..) => 8,
+            Pow(..) => 9,
+            Apply(..) => 10,
+            Undefined => 11,
+        }
+    }
+
     /// Get the priority rank when they are displayed. A lower number implys a higher priority. The
     /// value is intended to use in comparation context and may be changed later, so don't rely on
     /// a specific number.
     ///
     /// The order of operators are as follows: (From higher to lower)
     ///
-    /// - Scalars: `Integer`, `Sym`, `Approx`, `Undefined`.
+    /// - Scalars: `Integer`, `Sym`, `Approx`, `Const`, `Undefined`, `Apply`.
     /// - `Pow`.
-    /// - `Neg`.
-    /// - `Product`, `Ratio`.
+    /// - `Neg`, and the negative infinity.
+    /// - `Product`, `Ratio`, `Rational`.
     /// - `Sum`.
     ///
     /// # Examples
@@ -99,32 +279,51 @@ impl Expr {
     /// ```
     pub fn priority_rank(&self) -> i32 {
         match *self {
-            Integer(..) | Sym(..) | Approx(..) | Undefined => 0,
+            Const(Constant::NegInfinity) => 3,
+            Integer(..) | Sym(..) | Approx(..) | Const(..) | Undefined | Apply(..) => 0,
             Pow(..) => 2,
             Neg(..) => 3,
-            Product(..) | Ratio(..) => 4,
+            Product(..) | Ratio(..) | Rational(..) => 4,
             Sum(..) => 5,
         }
     }
 }
 
+/// Constants are displayed in Unicode with the alternate flag, like `format!("{:#}", e)`.
 impl Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_styled(f, f.alternate())
+    }
+}
+
+/// An expression displayed in ASCII or Unicode.
+struct Styled<'a>(&'a Expr, bool);
+
+impl<'a> Display for Styled<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_styled(f, self.1)
+    }
+}
+
+impl Expr {
+    fn fmt_styled(&self, f: &mut fmt::Formatter, unicode: bool) -> fmt::Result {
         // The formatter function, used in `itertools`'s `format_with` function, and recursive
         // `format_args`.
         let fmt_f = |elt: &Expr, f: &mut FnMut(&Display) -> fmt::Result| if elt.priority_rank() <
             self.priority_rank()
         {
-            f(elt)
+            f(&Styled(elt, unicode))
         } else {
-            f(&format_args!("({})", elt))
+            f(&format_args!("({})", Styled(elt, unicode)))
         };
 
         match *self {
             Undefined => write!(f, "Undefined"),
             Integer(ref i) => write!(f, "{}", i),
+            Rational(ref r) => write!(f, "{}", r),
             Sym(s) => write!(f, "{}", s),
             Approx(n) => write!(f, "{}", n),
+            Const(c) => write!(f, "{}", if unicode { c.unicode() } else { c.ascii() }),
             Neg(ref e) => fmt_f(e, &mut |e| write!(f, "- {}", e)),
             Sum(ref args) => write!(f, "{}", args.into_iter().format_with(" + ", fmt_f)),
             Product(ref args) => write!(f, "{}", args.into_iter().format_with(" * ", fmt_f)),
@@ -140,6 +339,10 @@ impl Display for Expr {
                     &mut |e_| fmt_f(p, &mut |p_| write!(f, "{} ^ {}", e_, p_)),
                 )
             }
+            Apply(ref func, ref args) => {
+                let args = args.iter().map(|arg| Styled(arg, unicode));
+                write!(f, "{}({})", func, args.format(", "))
+            }
         }
     }
 }
@@ -162,6 +365,7 @@ mod test {
 
         let mul = &neg * 4;
         let div = &s / &i;
+        let rational = Expr::rational(1, 3);
 
         let add = &i + &div;
         let sub = &approx - &i;
@@ -186,6 +390,7 @@ mod test {
 
         // x * y / z, x / y * z
         assert_eq!(mul.priority_rank(), div.priority_rank());
+        assert_eq!(div.priority_rank(), rational.priority_rank());
 
         // x + y * z == x + (y * z)
         assert!(mul.priority_rank() < add.priority_rank());
@@ -0,0 +1,314 @@
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use num::complex::Complex64;
+
+use super::{Expr, Symbol};
+
+/// An undefined function symbol, like `f` in `f(x, y)`.
+///
+/// The name is interned in the same pool as `Symbol`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Function(Symbol);
+
+impl Function {
+    /// Construct a new function symbol.
+    pub fn new(name: &str) -> Function {
+        Function(Symbol::new(name))
+    }
+
+    /// Apply the function to the arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Function, Symbol};
+    ///
+    /// let f = Function::new("f");
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!(format!("{}", f.call(vec![x.into(), y.into()])), "f(x, y)");
+    /// ```
+    pub fn call(self, args: Vec<Expr>) -> Expr {
+        Expr::Apply(Func::Undef(self, vec![]), args)
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The functions, which are built-in or undefined.
+///
+/// They're ordered as declared, and undefined functions by name and then by derivatives.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Func {
+    /// The sine function.
+    Sin,
+    /// The cosine function.
+    Cos,
+    /// The tangent function.
+    Tan,
+    /// The inverse sine function.
+    Asin,
+    /// The inverse cosine function.
+    Acos,
+    /// The inverse tangent function.
+    Atan,
+    /// The hyperbolic sine function.
+    Sinh,
+    /// The hyperbolic cosine function.
+    Cosh,
+    /// The hyperbolic tangent function.
+    Tanh,
+    /// The exponential function.
+    Exp,
+    /// The natural logarithm.
+    Ln,
+    /// The logarithm `log(base, x)`.
+    Log,
+    /// The square root.
+    Sqrt,
+    /// The absolute value.
+    Abs,
+
+    /// An undefined function, and how many times it's differentiated with respect to each
+    /// argument. The function itself has no derivatives, which is represented by an empty list.
+    Undef(Function, Vec<usize>),
+}
+
+use self::Func::*;
+
+/// All the built-in functions.
+const FUNCS: &[Func] = &[
+    Sin, Cos, Tan, Asin, Acos, Atan, Sinh, Cosh, Tanh, Exp, Ln, Log, Sqrt, Abs,
+];
+
+impl Func {
+    /// Get the name of the function.
+    pub fn name(&self) -> String {
+        match *self {
+            Undef(f, _) => f.to_string(),
+            ref f => f.builtin_name().to_string(),
+        }
+    }
+
+    fn builtin_name(&self) -> &'static str {
+        match *self {
+            Sin => "sin",
+            Cos => "cos",
+            Tan => "tan",
+            Asin => "asin",
+            Acos => "acos",
+            Atan => "atan",
+            Sinh => "sinh",
+            Cosh => "cosh",
+            Tanh => "tanh",
+            Exp => "exp",
+            Ln => "ln",
+            Log => "log",
+            Sqrt => "sqrt",
+            Abs => "abs",
+            Undef(..) => unreachable!("undefined functions have no static name"),
+        }
+    }
+
+    /// Find the built-in function by its name.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::Func;
+    ///
+    /// assert_eq!(Func::from_name("sin"), Some(Func::Sin));
+    /// assert_eq!(Func::from_name("f"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Func> {
+        FUNCS.iter().find(|f| f.builtin_name() == name).cloned()
+    }
+
+    /// Get the number of arguments the function takes, or `None` if it takes any number of
+    /// arguments.
+    pub fn arity(&self) -> Option<usize> {
+        match *self {
+            Log => Some(2),
+            Undef(_, ref derivs) if derivs.is_empty() => None,
+            Undef(_, ref derivs) => Some(derivs.len()),
+            _ => Some(1),
+        }
+    }
+
+    /// Check if the function can be applied to `n` arguments.
+    pub(crate) fn accepts(&self, n: usize) -> bool {
+        self.arity().map_or(true, |arity| arity == n)
+    }
+
+    /// Check if the function is built-in.
+    pub fn is_builtin(&self) -> bool {
+        if let Undef(..) = *self { false } else { true }
+    }
+}
+
+/// Evaluation of the functions.
+impl Func {
+    /// Evaluate the built-in function numerically.
+    pub(crate) fn eval_f64(&self, args: &[f64]) -> f64 {
+        let x = args[0];
+        match *self {
+            Sin => x.sin(),
+            Cos => x.cos(),
+            Tan => x.tan(),
+            Asin => x.asin(),
+            Acos => x.acos(),
+            Atan => x.atan(),
+            Sinh => x.sinh(),
+            Cosh => x.cosh(),
+            Tanh => x.tanh(),
+            Exp => x.exp(),
+            Ln => x.ln(),
+            Log => args[1].log(x),
+            Sqrt => x.sqrt(),
+            Abs => x.abs(),
+            Undef(..) => unreachable!("undefined functions can't be evaluated"),
+        }
+    }
+
+    /// Evaluate the built-in function numerically in the complex plane, on the principal
+    /// branches.
+    pub(crate) fn eval_complex(&self, args: &[Complex64]) -> Complex64 {
+        let z = args[0];
+        match *self {
+            Sin => z.sin(),
+            Cos => z.cos(),
+            Tan => z.tan(),
+            Asin => z.asin(),
+            Acos => z.acos(),
+            Atan => z.atan(),
+            Sinh => z.sinh(),
+            Cosh => z.cosh(),
+            Tanh => z.tanh(),
+            Exp => z.exp(),
+            Ln => z.ln(),
+            Log => args[1].ln() / z.ln(),
+            Sqrt => z.sqrt(),
+            Abs => Complex64::new(z.norm(), 0.0),
+            Undef(..) => unreachable!("undefined functions can't be evaluated"),
+        }
+    }
+
+    /// Check if `f(-x) = -f(x)`.
+    pub(crate) fn is_odd(&self) -> bool {
+        match *self {
+            Sin | Tan | Asin | Atan | Sinh | Tanh => true,
+            _ => false,
+        }
+    }
+
+    /// Check if `f(-x) = f(x)`.
+    pub(crate) fn is_even(&self) -> bool {
+        match *self {
+            Cos | Cosh | Abs => true,
+            _ => false,
+        }
+    }
+}
+
+/// Derivatives are displayed with primes like `f'` for a function of one argument, and with
+/// orders like `f^(1, 0)` for more arguments.
+impl Display for Func {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Undef(func, ref derivs) if derivs.len() == 1 => {
+                write!(f, "{}{}", func, "'".repeat(derivs[0]))
+            }
+            Undef(func, ref derivs) if !derivs.is_empty() => {
+                write!(f, "{}^({})", func, derivs.iter().format(", "))
+            }
+            ref func => write!(f, "{}", func.name()),
+        }
+    }
+}
+
+/// Expand to constructors of unary functions.
+macro_rules! unary_constructors {
+    ( $( $(#[$attr:meta])* fn $name:ident => $func:ident; )* ) => {
+        /// Constructors of function applications.
+        impl Expr {
+            $(
+                $(#[$attr])*
+                pub fn $name<E: Into<Expr>>(e: E) -> Expr {
+                    Expr::Apply($func, vec![e.into()])
+                }
+            )*
+        }
+    }
+}
+
+unary_constructors! {
+    /// Construct the sine of an expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Func, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(Expr::sin(x), Expr::Apply(Func::Sin, vec![x.into()]));
+    /// ```
+    fn sin => Sin;
+    /// Construct the cosine of an expression.
+    fn cos => Cos;
+    /// Construct the tangent of an expression.
+    fn tan => Tan;
+    /// Construct the inverse sine of an expression.
+    fn asin => Asin;
+    /// Construct the inverse cosine of an expression.
+    fn acos => Acos;
+    /// Construct the inverse tangent of an expression.
+    fn atan => Atan;
+    /// Construct the hyperbolic sine of an expression.
+    fn sinh => Sinh;
+    /// Construct the hyperbolic cosine of an expression.
+    fn cosh => Cosh;
+    /// Construct the hyperbolic tangent of an expression.
+    fn tanh => Tanh;
+    /// Construct the exponential of an expression.
+    fn exp => Exp;
+    /// Construct the natural logarithm of an expression.
+    fn ln => Ln;
+    /// Construct the square root of an expression.
+    fn sqrt => Sqrt;
+    /// Construct the absolute value of an expression.
+    fn abs => Abs;
+}
+
+impl Expr {
+    /// Construct the logarithm of `e` to `base`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(format!("{}", Expr::log(2, x)), "log(2, x)");
+    /// ```
+    pub fn log<B: Into<Expr>, E: Into<Expr>>(base: B, e: E) -> Expr {
+        Expr::Apply(Log, vec![base.into(), e.into()])
+    }
+
+    /// Apply a function to the arguments, or return `None` if the number of arguments is wrong.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Func, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(Expr::apply(Func::Cos, vec![x.into()]), Some(Expr::cos(x)));
+    /// assert_eq!(Expr::apply(Func::Cos, vec![]), None);
+    /// ```
+    pub fn apply(f: Func, args: Vec<Expr>) -> Option<Expr> {
+        if f.accepts(args.len()) {
+            Some(Expr::Apply(f, args))
+        } else {
+            None
+        }
+    }
+}
@@ -1,10 +1,20 @@
 use super::Expr;
 use super::Symbol;
-use num::{BigInt, BigUint};
+use super::Constant;
+use super::simplify::to_rational;
+use num::{BigInt, BigUint, BigRational, Zero};
 use std::ops::{Add, Sub, Mul, Div, Neg};
 
 // impls for operators
 
+/// Apply `f` directly if both operands are exact numbers.
+fn fold_exact<F>(lhs: &Expr, rhs: &Expr, f: F) -> Option<Expr>
+where
+    F: FnOnce(BigRational, BigRational) -> Expr,
+{
+    Some(f(to_rational(lhs)?, to_rational(rhs)?))
+}
+
 // Clone it if it's a ref.
 macro_rules! fwd_clone {
     (@Take $name:ident) => {$name};
@@ -16,8 +26,11 @@ macro_rules! op_func_impl {
     // Add: self + rhs
     (Add, $type_:ty, @$lm:ident, @$rm:ident) => {
         fn add(self, rhs: $type_) -> Self::Output {
-            let lhs = fwd_clone!(@$lm self).into();
-            let rhs = fwd_clone!(@$rm rhs).into();
+            let lhs: Expr = fwd_clone!(@$lm self).into();
+            let rhs: Expr = fwd_clone!(@$rm rhs).into();
+            if let Some(e) = fold_exact(&lhs, &rhs, |a, b| Expr::from_big_rational(a + b)) {
+                return e;
+            }
             match lhs {
                 $crate::sym::Expr::Sum(mut args) => {
                     args.push(rhs);
@@ -30,8 +43,11 @@ macro_rules! op_func_impl {
     // Mul: self * rhs
     (Mul, $type_:ty, @$lm:ident, @$rm:ident) => {
         fn mul(self, rhs: $type_) -> Self::Output {
-            let lhs = fwd_clone!(@$lm self).into();
-            let rhs = fwd_clone!(@$rm rhs).into();
+            let lhs: Expr = fwd_clone!(@$lm self).into();
+            let rhs: Expr = fwd_clone!(@$rm rhs).into();
+            if let Some(e) = fold_exact(&lhs, &rhs, |a, b| Expr::from_big_rational(a * b)) {
+                return e;
+            }
             match lhs {
                 $crate::sym::Expr::Product(mut args) => {
                     args.push(rhs);
@@ -44,10 +60,17 @@ macro_rules! op_func_impl {
     // Div: self / rhs
     (Div, $type_:ty, @$lm:ident, @$rm:ident) => {
         fn div(self, rhs: $type_) -> Self::Output {
-            $crate::sym::Expr::Ratio(
-                Box::new(fwd_clone!(@$lm self).into()),
-                Box::new(fwd_clone!(@$rm rhs).into())
-            )
+            let lhs: Expr = fwd_clone!(@$lm self).into();
+            let rhs: Expr = fwd_clone!(@$rm rhs).into();
+            let quotient = |a: BigRational, b: BigRational| if b.is_zero() {
+                $crate::sym::Expr::Undefined
+            } else {
+                Expr::from_big_rational(a / b)
+            };
+            if let Some(e) = fold_exact(&lhs, &rhs, quotient) {
+                return e;
+            }
+            $crate::sym::Expr::Ratio(Box::new(lhs), Box::new(rhs))
         }
     };
     // There isn't a minus constructor, should add a negative.
@@ -55,14 +78,21 @@ macro_rules! op_func_impl {
     (Sub, $type_:ty, @$lm:ident, @$rm:ident) => {
         // Defined by operator Add
         fn sub(self, rhs: $type_) -> Self::Output {
-            $crate::sym::Expr::from(fwd_clone!(@$lm self)) + 
-                $crate::sym::Expr::negative(fwd_clone!(@$rm rhs))
-
+            let lhs: Expr = fwd_clone!(@$lm self).into();
+            let rhs: Expr = fwd_clone!(@$rm rhs).into();
+            if let Some(e) = fold_exact(&lhs, &rhs, |a, b| Expr::from_big_rational(a - b)) {
+                return e;
+            }
+            lhs + (- rhs)
         }
     };
     (Neg, $type_:ty, @$m:ident) => {
         fn neg(self) -> Self::Output {
-            $crate::sym::Expr::negative(fwd_clone!(@$m self))
+            let e: Expr = fwd_clone!(@$m self).into();
+            match to_rational(&e) {
+                Some(r) => Expr::from_big_rational(-r),
+                None => $crate::sym::Expr::negative(e),
+            }
         }
     };
 }
@@ -288,6 +318,7 @@ convs! {
     impl From<u32> for Expr using integer;
     impl From<BigUint> for Expr using integer;
     impl From<BigInt> for Expr using integer;
+    impl From<BigRational> for Expr using from_big_rational;
 
     impl From<f32> for Expr using approximate;
     impl From<f64> for Expr using approximate;
@@ -304,3 +335,9 @@ impl From<Symbol> for Expr {
         Expr::Sym(s)
     }
 }
+
+impl From<Constant> for Expr {
+    fn from(c: Constant) -> Expr {
+        Expr::Const(c)
+    }
+}
@@ -0,0 +1,654 @@
+use std::collections::HashMap;
+use num::{BigRational, Signed, Zero};
+
+use crate::poly::Poly;
+use super::{Direction, EvalError, Expr, Func, Symbol};
+use super::quad::gauss_kronrod;
+use super::simplify::{sum, product};
+use self::Expr::*;
+
+/// The maximum depth of nested integration by parts and substitution.
+const MAX_DEPTH: usize = 8;
+
+/// The value of a definite integral, returned by `Expr::integrate_between`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefiniteIntegral {
+    /// The exact value, from an antiderivative.
+    Exact(Expr),
+    /// A numeric approximation by quadrature.
+    Numeric {
+        /// The approximate value.
+        value: f64,
+        /// An estimate of the absolute error.
+        error: f64,
+    },
+}
+
+/// Integration.
+impl Expr {
+    /// Find an antiderivative with respect to `x`, without a constant of integration, or `None`
+    /// if none is found. The result is simplified.
+    ///
+    /// Polynomials and other sums are integrated term by term, after taking out constant factors.
+    /// Rational functions with rational coefficients are decomposed by `apart`, which gives
+    /// logarithms and inverse tangents. Besides a table of elementary functions of linear
+    /// arguments, substitution of subexpressions whose derivative divides the integrand and
+    /// integration by parts are tried.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(x.pow(2).integrate(x), Some((x.pow(3) / 3).simplify()));
+    /// assert_eq!(
+    ///     (2i32 * x * Expr::cos(x.pow(2))).integrate(x),
+    ///     Some(Expr::sin(x.pow(2)).simplify())
+    /// );
+    /// assert_eq!(
+    ///     (x * Expr::exp(x)).integrate(x),
+    ///     Some(((x - 1) * Expr::exp(x)).expand())
+    /// );
+    /// assert_eq!(Expr::exp(x.pow(2)).integrate(x), None);
+    /// ```
+    pub fn integrate(&self, x: Symbol) -> Option<Expr> {
+        antiderivative(&self.simplify(), x, 0).map(|e| e.simplify())
+    }
+
+    /// Integrate with respect to `x` from `a` to `b`.
+    ///
+    /// If an antiderivative is found, it's evaluated at the bounds, or its limits are taken there
+    /// by `Expr::limit`, and the result is exact. With numeric bounds, the antiderivative must be
+    /// known to be continuous between them, so that singularities like in `1 / x` from `-1` to
+    /// `1` aren't missed. Otherwise the integral is approximated by
+    /// adaptive Gauss-Kronrod quadrature, where the bounds may be infinite, and the integrand
+    /// may be singular at the bounds.
+    ///
+    /// Returns an error if the integrand can't be evaluated numerically when needed, like when
+    /// it contains other symbols, or `NoConvergence` if the approximation doesn't reach the
+    /// requested accuracy, like when the integral diverges.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{DefiniteIntegral, Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(
+    ///     x.pow(2).integrate_between(x, 0, 3),
+    ///     Ok(DefiniteIntegral::Exact(Expr::integer(9)))
+    /// );
+    /// assert_eq!(
+    ///     Expr::exp(-x).integrate_between(x, 0, Expr::infinity()),
+    ///     Ok(DefiniteIntegral::Exact(Expr::integer(1)))
+    /// );
+    /// match Expr::exp(-x.pow(2)).integrate_between(x, 0, 1) {
+    ///     Ok(DefiniteIntegral::Numeric { value, error }) => {
+    ///         assert!((value - 0.746_824_132_812_427_1).abs() < 1e-12);
+    ///         assert!(error < 1e-12);
+    ///     }
+    ///     res => panic!("unexpected {:?}", res),
+    /// }
+    /// ```
+    pub fn integrate_between<A, B>(
+        &self,
+        x: Symbol,
+        a: A,
+        b: B,
+    ) -> Result<DefiniteIntegral, EvalError>
+    where
+        A: Into<Expr>,
+        B: Into<Expr>,
+    {
+        let (a, b) = (a.into().simplify(), b.into().simplify());
+        if let Some(value) = self.integrate(x).and_then(|f| definite(&f, x, &a, &b)) {
+            return Ok(DefiniteIntegral::Exact(value));
+        }
+
+        let env = HashMap::new();
+        let (a, b) = (a.eval(&env)?, b.eval(&env)?);
+        let f = self.simplify();
+        let (value, error) = gauss_kronrod(
+            |t| {
+                let mut env = HashMap::new();
+                env.insert(x, t);
+                f.eval(&env)
+            },
+            a,
+            b,
+        )?;
+        Ok(DefiniteIntegral::Numeric { value, error })
+    }
+}
+
+/// Evaluate the antiderivative `f` from `a` to `b`, or `None` if the value isn't known.
+fn definite(f: &Expr, x: Symbol, a: &Expr, b: &Expr) -> Option<Expr> {
+    if !is_continuous(f, x, a, b) {
+        return None;
+    }
+    // The bounds are approached from inside the interval.
+    let (lower, upper) = match (a.eval(&HashMap::new()), b.eval(&HashMap::new())) {
+        (Ok(a), Ok(b)) if a > b => (Direction::Left, Direction::Right),
+        (Ok(_), Ok(_)) => (Direction::Right, Direction::Left),
+        _ => (Direction::Both, Direction::Both),
+    };
+    let value = (at_bound(f, x, b, upper)? - at_bound(f, x, a, lower)?).simplify();
+    if value == Undefined { None } else { Some(value) }
+}
+
+/// The value of `f` at a bound, which is either finite or an infinity. If it can't be
+/// substituted, it's the limit from the direction `dir`.
+fn at_bound(f: &Expr, x: Symbol, bound: &Expr, dir: Direction) -> Option<Expr> {
+    let value = f.subs(x, bound.clone()).simplify();
+    match value {
+        Const(c) if c.is_infinite() => Some(value),
+        ref value if !is_finite(value) => f.limit(x, bound.clone(), dir),
+        value => Some(value),
+    }
+}
+
+/// Check that the parts of an expression without symbols evaluate to finite real numbers, unlike
+/// `ln(0)` or `1 / 0`, and don't contain infinities, like `atan(oo)`.
+fn is_finite(e: &Expr) -> bool {
+    if has_infinity(e) {
+        false
+    } else if e.free_symbols().is_empty() {
+        e.eval(&HashMap::new()).map(f64::is_finite) == Ok(true)
+    } else {
+        e.children().all(is_finite)
+    }
+}
+
+/// Check if an expression contains an infinity.
+pub(crate) fn has_infinity(e: &Expr) -> bool {
+    match *e {
+        Const(c) => c.is_infinite(),
+        _ => e.children().any(has_infinity),
+    }
+}
+
+/// Check that an antiderivative `f` is continuous between the bounds, so that it can be
+/// evaluated at them.
+///
+/// The denominators, and the arguments which must be positive like that of `ln`, must be
+/// polynomials in `x` without roots inside the interval, or depend on other symbols, in which
+/// case they're assumed to have none. Otherwise `f` isn't known to be continuous. If a bound
+/// isn't a number, they're only checked at the other bound, where they must hold strictly.
+fn is_continuous(f: &Expr, x: Symbol, a: &Expr, b: &Expr) -> bool {
+    let mut conds = vec![];
+    if !conditions(f, &mut conds) {
+        return false;
+    }
+    let env = HashMap::new();
+    let (a, b) = match (a.eval(&env), b.eval(&env)) {
+        (Ok(u), Ok(v)) if u <= v => ((u, a), (v, b)),
+        (Ok(u), Ok(v)) if u > v => ((v, b), (u, a)),
+        (Ok(_), Ok(_)) => return false,
+        (u, v) => {
+            return u.into_iter().chain(v).all(|c| {
+                conds.iter().all(|&(ref g, positive)| holds_at(g, x, c, positive))
+            })
+        }
+    };
+    conds.iter().all(|&(ref g, positive)| no_roots(g, x, a, b, positive))
+}
+
+/// Collect the expressions which must have no zeros for `e` to be continuous, and whether they
+/// must be positive too. Returns `false` if `e` can't be continuous, like an undefined function.
+fn conditions(e: &Expr, res: &mut Vec<(Expr, bool)>) -> bool {
+    match *e {
+        Ratio(_, ref d) => res.push((d.as_ref().clone(), false)),
+        Pow(ref b, ref p) => match **p {
+            Integer(ref n) if !n.is_negative() => {}
+            Integer(_) => res.push((b.as_ref().clone(), false)),
+            _ => res.push((b.as_ref().clone(), true)),
+        },
+        Apply(ref f, ref args) => match *f {
+            Func::Tan => res.push((Expr::cos(args[0].clone()), false)),
+            Func::Asin | Func::Acos => {
+                res.push(((Expr::integer(1) - args[0].clone().pow(2)).simplify(), true))
+            }
+            Func::Ln | Func::Log | Func::Sqrt => {
+                res.extend(args.iter().map(|arg| (arg.clone(), true)))
+            }
+            Func::Undef(..) => return false,
+            _ => {}
+        },
+        _ => {}
+    }
+    e.children().all(|c| conditions(c, res))
+}
+
+/// Check that `g` has no zeros strictly between the bounds, which are given by their values and
+/// expressions, and that it's positive there if `positive`.
+///
+/// A numeric root of a polynomial is at a bound if `g` vanishes there, and the disk of the root
+/// contains the bound and no other root.
+fn no_roots(g: &Expr, x: Symbol, a: (f64, &Expr), b: (f64, &Expr), positive: bool) -> bool {
+    if g.free_symbols().iter().any(|&s| s != x) {
+        return true;
+    }
+    let roots = match g.nroots(x) {
+        Ok(roots) => roots,
+        Err(_) => return false,
+    };
+    let at_bound = |i: usize| {
+        let (v, error) = (roots[i].value, roots[i].error);
+        let isolated = roots
+            .iter()
+            .enumerate()
+            .all(|(j, r)| j == i || (r.value - v).norm() > error + r.error);
+        isolated
+            && [a, b].iter().any(|&(c, bound)| {
+                (v - c).norm() <= error && g.subs(x, bound.clone()).simplify() == Expr::integer(0)
+            })
+    };
+    let inside = (0..roots.len()).any(|i| {
+        let (v, error) = (roots[i].value, roots[i].error);
+        let misses = v.im.abs() > error || v.re + error < a.0 || v.re - error > b.0;
+        !misses && !at_bound(i)
+    });
+    if inside {
+        return false;
+    }
+
+    // A point inside, where the sign is the same as everywhere inside.
+    let t = match (a.0.is_finite(), b.0.is_finite()) {
+        (true, true) => (a.0 + b.0) / 2.0,
+        (true, false) => a.0 + 1.0,
+        (false, true) => b.0 - 1.0,
+        (false, false) => 0.0,
+    };
+    let mut env = HashMap::new();
+    env.insert(x, t);
+    match g.eval(&env) {
+        Ok(y) => y.is_finite() && if positive { y > 0.0 } else { y != 0.0 },
+        Err(_) => false,
+    }
+}
+
+/// Check that `g` is nonzero at `c`, and positive if `positive`. It's assumed to be if it depends
+/// on other symbols.
+fn holds_at(g: &Expr, x: Symbol, c: f64, positive: bool) -> bool {
+    if g.free_symbols().iter().any(|&s| s != x) {
+        return true;
+    }
+    let mut env = HashMap::new();
+    env.insert(x, c);
+    match g.eval(&env) {
+        Ok(y) => !y.is_nan() && if positive { y > 0.0 } else { y != 0.0 },
+        Err(_) => false,
+    }
+}
+
+/// Integrate a canonical expression.
+fn antiderivative(e: &Expr, x: Symbol, depth: usize) -> Option<Expr> {
+    if !e.has_symbol(x) {
+        return Some(product(vec![e.clone(), Sym(x)]));
+    }
+
+    match *e {
+        Sum(ref args) => {
+            let terms = args.iter().map(|arg| antiderivative(arg, x, depth));
+            return terms.collect::<Option<Vec<_>>>().map(sum);
+        }
+        Product(ref args) if args.iter().any(|arg| !arg.has_symbol(x)) => {
+            let (mut consts, factors): (Vec<_>, Vec<_>) =
+                args.iter().cloned().partition(|arg| !arg.has_symbol(x));
+            consts.push(antiderivative(&product(factors), x, depth)?);
+            return Some(product(consts));
+        }
+        _ => {}
+    }
+
+    table(e, x)
+        .or_else(|| rational(e, x, depth))
+        .or_else(|| substitution(e, x, depth + 1))
+        .or_else(|| {
+            let expanded = e.expand();
+            if expanded != *e { antiderivative(&expanded, x, depth) } else { None }
+        })
+        .or_else(|| parts(e, x, depth + 1))
+}
+
+/// The coefficient `a` if `u` is `a x + b`.
+fn linear(u: &Expr, x: Symbol) -> Option<Expr> {
+    let a = u.diff(x);
+    if a.has_symbol(x) || a == Expr::integer(0) { None } else { Some(a) }
+}
+
+/// Look up a canonical expression in the table of integrals.
+fn table(e: &Expr, x: Symbol) -> Option<Expr> {
+    match *e {
+        Sym(_) => Some(Sym(x).pow(2) / 2),
+        Pow(ref b, ref p) if !p.has_symbol(x) => {
+            let (b, p) = ((**b).clone(), (**p).clone());
+            if let Some(a) = linear(&b, x) {
+                return Some(if p == Expr::integer(-1) {
+                    Expr::ln(b) / a
+                } else {
+                    b.pow(p.clone() + 1) / (a * (p + 1))
+                });
+            }
+            match b {
+                Apply(f, mut args) => {
+                    let u = args.pop().unwrap();
+                    let a = linear(&u, x)?;
+                    func_power(&f, u, &p).map(|res| res / a)
+                }
+                b => inverse_sqrt(&b, &p, x),
+            }
+        }
+        // c ^ u = e ^ (u ln(c))
+        Pow(ref b, ref p) if !b.has_symbol(x) => {
+            let a = linear(p, x)?;
+            Some(e.clone() / (a * Expr::ln((**b).clone())))
+        }
+        Apply(ref f, ref args) if f.arity() == Some(1) => {
+            let a = linear(&args[0], x)?;
+            func_antiderivative(f, &args[0]).map(|res| res / a)
+        }
+        _ => None,
+    }
+}
+
+/// An antiderivative of `f(u)` with respect to `u`.
+fn func_antiderivative(f: &Func, u: &Expr) -> Option<Expr> {
+    let u = u.clone();
+    let sqrt_1_u2 = (1i32 - u.clone().pow(2)).pow(Expr::rational(1, 2));
+    Some(match *f {
+        Func::Sin => -Expr::cos(u),
+        Func::Cos => Expr::sin(u),
+        Func::Tan => -Expr::ln(Expr::cos(u)),
+        Func::Sinh => Expr::cosh(u),
+        Func::Cosh => Expr::sinh(u),
+        Func::Tanh => Expr::ln(Expr::cosh(u)),
+        Func::Exp => Expr::exp(u),
+        // By parts, with the derivative of `x` as the other factor.
+        Func::Ln => u.clone() * Expr::ln(u.clone()) - u,
+        Func::Asin => u.clone() * Expr::asin(u) + sqrt_1_u2,
+        Func::Acos => u.clone() * Expr::acos(u) - sqrt_1_u2,
+        Func::Atan => u.clone() * Expr::atan(u.clone()) - Expr::ln(1i32 + u.pow(2)) / 2,
+        Func::Abs => u.clone() * Expr::abs(u) / 2,
+        _ => return None,
+    })
+}
+
+/// An antiderivative of `f(u) ^ p` with respect to `u`, for some powers of trigonometric
+/// functions.
+fn func_power(f: &Func, u: Expr, p: &Expr) -> Option<Expr> {
+    let sin_2u = || Expr::sin(2i32 * u.clone());
+    match (f, p) {
+        (&Func::Sin, &Integer(ref n)) if *n == 2.into() => Some(u.clone() / 2 - sin_2u() / 4),
+        (&Func::Cos, &Integer(ref n)) if *n == 2.into() => Some(u.clone() / 2 + sin_2u() / 4),
+        (&Func::Cos, &Integer(ref n)) if *n == (-2).into() => Some(Expr::tan(u)),
+        (&Func::Sin, &Integer(ref n)) if *n == (-2).into() => {
+            Some(-Expr::cos(u.clone()) / Expr::sin(u))
+        }
+        _ => None,
+    }
+}
+
+/// An antiderivative of `(c + d x ^ 2) ^ (-1/2)` with rational `c` and `d`.
+fn inverse_sqrt(b: &Expr, p: &Expr, x: Symbol) -> Option<Expr> {
+    if *p != Expr::rational(-1, 2) {
+        return None;
+    }
+    let (c, d) = match quadratic_coeffs(b, x)? {
+        (d, ref b, c) if b.is_zero() => (c, d),
+        _ => return None,
+    };
+    let sqrt = |r: BigRational| Expr::sqrt(Expr::from(r));
+    if d.is_negative() && c.is_positive() {
+        let ratio = -&d / &c;
+        Some(Expr::asin(sqrt(ratio) * x) / sqrt(-d))
+    } else if d.is_positive() {
+        Some(Expr::ln(sqrt(d.clone()) * x + b.clone().pow(Expr::rational(1, 2))) / sqrt(d))
+    } else {
+        None
+    }
+}
+
+/// The coefficients `(a, b, c)` of a quadratic polynomial `a x ^ 2 + b x + c` with rational
+/// coefficients.
+fn quadratic_coeffs(e: &Expr, x: Symbol) -> Option<(BigRational, BigRational, BigRational)> {
+    let p: Poly = Poly::from_expr(e, &[x]).ok()?;
+    if p.degree(x) != Some(2) {
+        return None;
+    }
+    Some((p.coeff(&[2]), p.coeff(&[1]), p.coeff(&[0])))
+}
+
+/// Integrate a rational function of `x` with rational coefficients by partial fractions.
+fn rational(e: &Expr, x: Symbol, depth: usize) -> Option<Expr> {
+    let (n, d) = e.numer_denom();
+    if !d.has_symbol(x) {
+        return None;
+    }
+    Poly::<BigRational>::from_expr(&n, &[x]).ok()?;
+    Poly::<BigRational>::from_expr(&d, &[x]).ok()?;
+
+    let terms = match e.apart(x) {
+        Sum(args) => args,
+        t => vec![t],
+    };
+    let terms = terms.iter().map(|t| partial_fraction(t, x, depth));
+    terms.collect::<Option<Vec<_>>>().map(sum)
+}
+
+/// Integrate a term `a / f ^ k` of a partial fraction decomposition, where `f` is irreducible
+/// and of degree at most two.
+fn partial_fraction(t: &Expr, x: Symbol, depth: usize) -> Option<Expr> {
+    let (n, d) = t.numer_denom();
+    let d: Poly = Poly::from_expr(&d, &[x]).ok()?;
+    let (c, factors) = d.factor_list();
+    if factors.is_empty() || factors[0].0.degree(x) == Some(1) {
+        return antiderivative(t, x, depth);
+    }
+    if factors.len() != 1 || factors[0].0.degree(x) != Some(2) {
+        return None;
+    }
+    let (ref f, k) = factors[0];
+    let n: Poly = Poly::from_expr(&(n / Expr::from(c)).simplify(), &[x]).ok()?;
+    Some(quadratic_fraction(n.coeff(&[1]), n.coeff(&[0]), f, k, x))
+}
+
+/// Integrate `(s x + t) / q ^ k` for an irreducible quadratic `q = a x ^ 2 + b x + c`.
+fn quadratic_fraction(s: BigRational, t: BigRational, q: &Poly, k: u32, x: Symbol) -> Expr {
+    let (a, b, c) = (q.coeff(&[2]), q.coeff(&[1]), q.coeff(&[0]));
+    let disc = BigRational::from_integer(4.into()) * &a * &c - &b * &b;
+    let two_a = &a + &a;
+    let q = q.to_expr();
+    // The derivative of `q`.
+    let dq = Expr::from(two_a.clone()) * x + Expr::from(b.clone());
+
+    // s x + t = s / (2 a) q' + (t - s b / (2 a))
+    let log_part = if k == 1 {
+        Expr::ln(q.clone())
+    } else {
+        let e = 1 - i64::from(k);
+        q.clone().pow(e) / e
+    };
+
+    // The integral of `1 / q`, and then of `1 / q ^ m` by the reduction formula.
+    let mut rest = if disc.is_positive() {
+        let root = Expr::sqrt(Expr::from(disc.clone()));
+        2i32 * Expr::atan(dq.clone() / root.clone()) / root
+    } else {
+        let root = Expr::sqrt(Expr::from(-disc.clone()));
+        Expr::ln((dq.clone() - root.clone()) / (dq.clone() + root.clone())) / root
+    };
+    for m in 2..=i64::from(k) {
+        let d = Expr::from(disc.clone()) * (m - 1);
+        rest = dq.clone() / (d.clone() * q.clone().pow(m - 1))
+            + Expr::from(two_a.clone()) * (2 * m - 3) / d * rest;
+    }
+
+    let t = &t - &s * &b / &two_a;
+    Expr::from(s / two_a) * log_part + Expr::from(t) * rest
+}
+
+/// Integrate by substituting `u` for a subexpression whose derivative divides the integrand, so
+/// that what's left only depends on `u`.
+fn substitution(e: &Expr, x: Symbol, depth: usize) -> Option<Expr> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let mut candidates = vec![];
+    subexpressions(e, x, &mut candidates);
+    // Nested substitutions are deeper, so their symbols differ.
+    let t = Symbol::dummy(&format!("u{}", depth));
+    for u in candidates {
+        if u == *e || linear(&u, x).is_some() {
+            continue;
+        }
+        let q = (e.clone() / u.diff(x)).simplify().replace(&u, &Sym(t)).simplify();
+        if q.has_symbol(x) {
+            continue;
+        }
+        if let Some(res) = antiderivative(&q, t, depth) {
+            return Some(res.subs(t, u));
+        }
+    }
+    None
+}
+
+/// Collect the distinct subexpressions depending on `x`, other than `x` itself.
+fn subexpressions(e: &Expr, x: Symbol, res: &mut Vec<Expr>) {
+    if !e.has_symbol(x) || *e == Sym(x) {
+        return;
+    }
+    if !res.contains(e) {
+        res.push(e.clone());
+    }
+    match *e {
+        Sum(ref args) | Product(ref args) | Apply(_, ref args) => {
+            for arg in args {
+                subexpressions(arg, x, res);
+            }
+        }
+        Pow(ref b, ref p) => {
+            subexpressions(b, x, res);
+            subexpressions(p, x, res);
+        }
+        _ => {}
+    }
+}
+
+/// Integrate by parts, `int(u v') = u v - int(u' v)`.
+///
+/// The factor `u` is chosen from logarithms and inverse trigonometric functions, polynomials and
+/// then trigonometric functions, in this order. If `u' v` becomes a multiple of the integrand
+/// after one or two steps, like for `e ^ x sin(x)`, the equation is solved for the integral.
+fn parts(e: &Expr, x: Symbol, depth: usize) -> Option<Expr> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    for (u, dv) in choices(e, x) {
+        let v = match antiderivative(&dv, x, depth) {
+            Some(v) => v.simplify(),
+            None => continue,
+        };
+        let uv = u.clone() * v.clone();
+        let w = (u.diff(x) * v).simplify();
+
+        if let Some(k) = multiple(&w, e, x) {
+            if k != Expr::integer(-1) {
+                return Some(uv / (1i32 + k));
+            }
+        }
+        if let Some((uv2, w2)) = step(&w, x, depth) {
+            if let Some(k) = multiple(&w2, e, x) {
+                if k != Expr::integer(1) {
+                    return Some((uv - uv2) / (1i32 - k));
+                }
+            }
+        }
+        if let Some(res) = antiderivative(&w, x, depth) {
+            return Some(uv - res);
+        }
+    }
+    None
+}
+
+/// One step of integration by parts, giving `u v` and `u' v`.
+fn step(e: &Expr, x: Symbol, depth: usize) -> Option<(Expr, Expr)> {
+    choices(e, x).into_iter().find_map(|(u, dv)| {
+        let v = antiderivative(&dv, x, depth)?.simplify();
+        Some((u.clone() * v.clone(), (u.diff(x) * v).simplify()))
+    })
+}
+
+/// The constant `k` if `e = k f`.
+fn multiple(e: &Expr, f: &Expr, x: Symbol) -> Option<Expr> {
+    let k = (e.clone() / f.clone()).simplify();
+    if k.has_symbol(x) { None } else { Some(k) }
+}
+
+/// The ways to split a canonical integrand into the factors `u` and `v'`.
+fn choices(e: &Expr, x: Symbol) -> Vec<(Expr, Expr)> {
+    let factors = match *e {
+        Product(ref args) => args.clone(),
+        ref e => vec![e.clone()],
+    };
+    let mut res = (0..factors.len())
+        .filter_map(|i| {
+            let rank = rank(&factors[i], x)?;
+            let mut rest = factors.clone();
+            let u = rest.remove(i);
+            let dv = product(rest);
+            // Only logarithms and inverse functions are integrated alone, and trigonometric
+            // functions only against exponentials.
+            match rank {
+                1 if factors.len() == 1 => None,
+                2 if !is_exponential(&dv, x) => None,
+                _ => Some((rank, u, dv)),
+            }
+        })
+        .collect::<Vec<_>>();
+    res.sort_by_key(|r| r.0);
+    res.into_iter().map(|(_, u, dv)| (u, dv)).collect()
+}
+
+/// How good a factor is as `u` in integration by parts, the lower the better, or `None` if it
+/// shouldn't be differentiated.
+fn rank(e: &Expr, x: Symbol) -> Option<u8> {
+    let is_inverse = |e: &Expr| match *e {
+        Apply(Func::Ln, _) | Apply(Func::Asin, _) | Apply(Func::Acos, _) | Apply(Func::Atan, _) => {
+            true
+        }
+        _ => false,
+    };
+    match *e {
+        ref e if is_inverse(e) => Some(0),
+        Pow(ref b, ref n) if is_inverse(b) && is_positive_integer(n) => Some(0),
+        ref e if is_polynomial(e, x) => Some(1),
+        Apply(Func::Sin, _) | Apply(Func::Cos, _) => Some(2),
+        _ => None,
+    }
+}
+
+fn is_exponential(e: &Expr, x: Symbol) -> bool {
+    match *e {
+        Apply(Func::Exp, _) => true,
+        Pow(ref b, _) => !b.has_symbol(x),
+        Product(ref args) => args.iter().all(|arg| !arg.has_symbol(x) || is_exponential(arg, x)),
+        _ => false,
+    }
+}
+
+fn is_polynomial(e: &Expr, x: Symbol) -> bool {
+    match *e {
+        _ if !e.has_symbol(x) => true,
+        Sym(_) => true,
+        Pow(ref b, ref n) => is_positive_integer(n) && is_polynomial(b, x),
+        Sum(ref args) | Product(ref args) => args.iter().all(|arg| is_polynomial(arg, x)),
+        _ => false,
+    }
+}
+
+fn is_positive_integer(e: &Expr) -> bool {
+    match *e {
+        Integer(ref n) => n.is_positive(),
+        _ => false,
+    }
+}
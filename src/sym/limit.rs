@@ -0,0 +1,246 @@
+use num::{BigRational, Signed, Zero};
+
+use super::{Constant, Expr, Func, Symbol};
+use super::integrate::has_infinity;
+use super::puiseux::{sign, Expander};
+use self::Expr::*;
+
+/// The orders up to which a series is tried before giving up on it.
+const SERIES_ORDERS: [i64; 4] = [2, 4, 8, 16];
+
+/// The maximum depth of nested indeterminate sums.
+const MAX_DEPTH: usize = 8;
+
+/// The direction from which a point is approached, given to `Expr::limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// From below, like `x -> 0-`.
+    Left,
+    /// From above, like `x -> 0+`.
+    Right,
+    /// From both sides, where the one-sided limits must agree.
+    Both,
+}
+
+/// Limits.
+impl Expr {
+    /// Find the limit as `x` goes to `point` from the direction `dir`, which may be an infinity.
+    ///
+    /// The expression is expanded into a series with rational powers and logarithms around the
+    /// point, like `x ^ (-1/2) + ln(x) + O(x)`, and the limit is read from the leading term.
+    /// Otherwise the limits of the parts are combined, where indeterminate products are rewritten
+    /// as exponentials of sums, and indeterminate sums like `exp(x) - x` take the limit of their
+    /// fastest growing term, which is found by comparing the terms by the limits of their ratios.
+    ///
+    /// Returns `None` if the limit doesn't exist, like when the one-sided limits differ, or it
+    /// isn't found. An infinite limit is `oo` or `-oo`. Infinities are only approached from one
+    /// side, so the direction doesn't matter for them.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Direction, Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!((Expr::sin(x) / x).limit(x, 0, Direction::Both), Some(Expr::integer(1)));
+    /// assert_eq!(
+    ///     (1i32 + Expr::from(x).pow(-1)).pow(x).limit(x, Expr::infinity(), Direction::Both),
+    ///     Some(Expr::e())
+    /// );
+    /// let inverse = Expr::from(x).pow(-1);
+    /// assert_eq!(inverse.limit(x, 0, Direction::Left), Some((-Expr::infinity()).simplify()));
+    /// assert_eq!(inverse.limit(x, 0, Direction::Both), None);
+    /// ```
+    pub fn limit<P: Into<Expr>>(&self, x: Symbol, point: P, dir: Direction) -> Option<Expr> {
+        let e = self.simplify();
+        let point = point.into().simplify();
+        if !e.has_symbol(x) {
+            return Some(e);
+        }
+
+        let t = Symbol::dummy("t");
+        let log = Symbol::dummy("t_log");
+        let one_sided = |sign: i32| {
+            let value = match point {
+                Const(Constant::Infinity) => Expr::from(t).pow(-1),
+                Const(Constant::NegInfinity) => -Expr::from(t).pow(-1),
+                ref c if has_infinity(c) || *c == Undefined => return None,
+                ref c => c.clone() + Expr::integer(sign) * t,
+            };
+            at_zero(&e.subs(x, value).simplify(), t, log, 0)
+        };
+        match (dir, &point) {
+            (_, &Const(c)) if c.is_infinite() => one_sided(1),
+            (Direction::Left, _) => one_sided(-1),
+            (Direction::Right, _) => one_sided(1),
+            (Direction::Both, _) => {
+                let right = one_sided(1)?;
+                if one_sided(-1)? == right { Some(right) } else { None }
+            }
+        }
+    }
+}
+
+/// The limit of a canonical expression as `t -> 0+`, where `log` stands for `ln(t)` in series,
+/// and `depth` is the number of enclosing indeterminate sums.
+fn at_zero(e: &Expr, t: Symbol, log: Symbol, depth: usize) -> Option<Expr> {
+    if !e.has_symbol(t) {
+        return Some(e.clone());
+    }
+    let expander = Expander::new(t, log);
+    for &n in &SERIES_ORDERS {
+        let s = match expander.expand(e, &BigRational::from_integer(n.into())) {
+            Some(s) => s,
+            None => break,
+        };
+        let (exponent, c) = match s.terms.first() {
+            Some(term) => term.clone(),
+            // What's left vanishes at zero.
+            None if s.order.is_positive() => return Some(Expr::integer(0)),
+            None => continue,
+        };
+
+        if exponent.is_positive() {
+            return Some(Expr::integer(0));
+        }
+        if exponent.is_zero() && !c.has_symbol(log) {
+            return Some(c.simplify());
+        }
+        // A negative power or a power of `ln(t)`, which goes to negative infinity.
+        if let Some(s) = sign(&c, log) {
+            return Some(Const(if s > 0 { Constant::Infinity } else { Constant::NegInfinity }));
+        }
+        break;
+    }
+    parts(e, t, log, depth)
+}
+
+/// Combine the limits of the parts of a canonical expression as `t -> 0+`.
+fn parts(e: &Expr, t: Symbol, log: Symbol, depth: usize) -> Option<Expr> {
+    let limit = |e: &Expr| at_zero(e, t, log, depth);
+    let res = match *e {
+        Sum(ref args) => {
+            let limits = args.iter().map(limit).collect::<Option<Vec<_>>>()?;
+            match Sum(limits.clone()).simplify() {
+                Undefined => indeterminate_sum(args, &limits, t, log, depth)?,
+                res => res,
+            }
+        }
+        Product(ref args) => {
+            let limits = args.iter().map(limit).collect::<Option<Vec<_>>>()?;
+            match Product(limits.clone()).simplify() {
+                Undefined => indeterminate_product(args, &limits, t, log, depth)?,
+                res => res,
+            }
+        }
+        Pow(ref b, ref p) if p.has_symbol(t) => {
+            limit(&Expr::exp((**p).clone() * Expr::ln((**b).clone())).simplify())?
+        }
+        Pow(ref b, ref p) => limit(b)?.pow((**p).clone()).simplify(),
+        Apply(ref f, ref args) if args.len() == 1 => apply(f, limit(&args[0])?)?,
+        _ => return None,
+    };
+    match res {
+        Const(c) if c.is_infinite() => Some(res),
+        ref res if has_infinity(res) || *res == Undefined => None,
+        res => Some(res),
+    }
+}
+
+/// The limit of a product whose factors have the limits `0` and an infinity, from the limit of
+/// its logarithm.
+fn indeterminate_product(
+    args: &[Expr],
+    limits: &[Expr],
+    t: Symbol,
+    log: Symbol,
+    depth: usize,
+) -> Option<Expr> {
+    let mut negative = false;
+    let mut logs = vec![];
+    for (arg, limit) in args.iter().zip(limits) {
+        let flip = match *limit {
+            Const(c) => c == Constant::NegInfinity,
+            ref l => l.eval(&Default::default()).ok()? < 0.0,
+        };
+        let arg = if flip {
+            negative = !negative;
+            (-arg.clone()).simplify()
+        } else {
+            arg.clone()
+        };
+        logs.push(ln(arg));
+    }
+    let res = Expr::exp(at_zero(&Sum(logs).simplify(), t, log, depth)?).simplify();
+    Some(if negative { (-res).simplify() } else { res })
+}
+
+/// The logarithm of a positive expression, where exponentials are taken apart.
+fn ln(e: Expr) -> Expr {
+    match e {
+        Apply(Func::Exp, mut args) => args.remove(0),
+        Pow(b, p) => *p * ln(*b),
+        e => Expr::ln(e),
+    }
+}
+
+/// The limit of a sum whose terms have the limits `oo` and `-oo`, from a term growing at least as
+/// fast as the others, which is one where the sum divided by it has a nonzero limit.
+///
+/// The terms are compared by the limits of their ratios, so that `exp(x) - x` is like `exp(x)`
+/// as `x -> oo`, like in the comparability classes of Gruntz's algorithm.
+fn indeterminate_sum(
+    args: &[Expr],
+    limits: &[Expr],
+    t: Symbol,
+    log: Symbol,
+    depth: usize,
+) -> Option<Expr> {
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+    for (arg, limit) in args.iter().zip(limits) {
+        match *limit {
+            Const(Constant::Infinity) | Const(Constant::NegInfinity) => {}
+            _ => continue,
+        }
+        let ratios = args.iter().map(|other| (other.clone() / arg.clone()).simplify());
+        let ratio = match at_zero(&Sum(ratios.collect()).simplify(), t, log, depth + 1) {
+            Some(ratio) => ratio,
+            None => continue,
+        };
+        match ratio.eval(&Default::default()) {
+            Ok(r) if r > 0.0 => return Some(limit.clone()),
+            Ok(r) if r < 0.0 => return Some((-limit.clone()).simplify()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The limit of `f(u)` where `u` goes to `limit`.
+fn apply(f: &Func, limit: Expr) -> Option<Expr> {
+    let infinity = |positive| {
+        Const(if positive { Constant::Infinity } else { Constant::NegInfinity })
+    };
+    let c = match limit {
+        Const(c) if c.is_infinite() => c,
+        Integer(ref n) if *f == Func::Ln && n.is_zero() => return Some(infinity(false)),
+        limit => return Some(Apply(f.clone(), vec![limit]).simplify()),
+    };
+    let positive = match c {
+        Constant::Infinity => true,
+        Constant::NegInfinity => false,
+        _ => return None,
+    };
+    Some(match *f {
+        Func::Exp if positive => infinity(true),
+        Func::Exp => Expr::integer(0),
+        Func::Ln if positive => infinity(true),
+        Func::Atan if positive => (Expr::pi() / 2).simplify(),
+        Func::Atan => (-Expr::pi() / 2).simplify(),
+        Func::Tanh => Expr::integer(if positive { 1 } else { -1 }),
+        Func::Sinh => infinity(positive),
+        Func::Cosh | Func::Abs => infinity(true),
+        _ => return None,
+    })
+}
@@ -18,6 +18,7 @@
 //! use symrs::sym::Expr;
 //!
 //! let x = Expr::symbol("x");
+//! let one_third = Expr::rational(1, 3);
 //! let about_one = Expr::approximate(1);
 //! let x_y = Expr::pow(x, about_one);
 //! ```
@@ -42,11 +43,54 @@
 //! // use `1i32` rather than `1`
 //! let e = 1i32 + x;
 //! ```
+//!
+//! # Simplifying a `Expr`
+//!
+//! Operators build the expression tree as it is written. Use `simplify` to bring it into a
+//! canonical form, so that structurally equal math compares equal.
+//!
+//! ```
+//! use symrs::sym::Symbol;
+//!
+//! let x = Symbol::new("x");
+//! assert_ne!(x + 1, 1i32 + x);
+//! assert_eq!((x + 1).simplify(), (1i32 + x).simplify());
+//! ```
 
 
 mod expr;
 mod symbol;
 mod impls;
+mod simplify;
+mod diff;
+mod subs;
+mod eval;
+mod func;
+mod constant;
+mod expand;
+mod together;
+mod equation;
+mod integrate;
+mod quad;
+mod puiseux;
+mod limit;
+mod series;
+mod power_series;
+mod rc_expr;
+mod visit;
+mod query;
+mod pattern;
 
 pub use self::expr::Expr;
 pub use self::symbol::Symbol;
+pub use self::eval::EvalError;
+pub use self::func::{Func, Function};
+pub use self::constant::Constant;
+pub use self::equation::Equation;
+pub use self::integrate::DefiniteIntegral;
+pub use self::limit::Direction;
+pub use self::series::{Order, Series};
+pub use self::power_series::PowerSeries;
+pub use self::rc_expr::{RcExpr, RcNode};
+pub use self::pattern::{Bindings, Pattern, RewriteRule, Wild};
+pub use self::visit::{Children, Folder, Postorder, Preorder, Visitor, VisitorMut};
@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{Expr, Folder, Symbol};
+use super::simplify::{power, product, sum};
+use self::Expr::*;
+
+/// The maximum number of ways tried to share the remaining terms of a sum or product among
+/// several wildcards.
+const MAX_ASSIGNMENTS: usize = 4096;
+
+/// The maximum number of rewrites done by `Expr::rewrite`, in case the rules don't terminate.
+const MAX_REWRITES: usize = 1000;
+
+/// The values of the wildcards of a pattern that matched, by their symbols.
+pub type Bindings = HashMap<Symbol, Expr>;
+
+type Predicate = Rc<dyn Fn(&Expr) -> bool>;
+
+type Condition = Rc<dyn Fn(&Bindings) -> bool>;
+
+/// Called with each way a pattern matches, and returns `true` to stop looking for more.
+type Found<'a> = &'a mut dyn FnMut(Bindings) -> bool;
+
+/// A wildcard of a `Pattern`, which matches any expression, or only the ones accepted by a
+/// predicate and not containing some symbols.
+///
+/// It's written in a pattern as its symbol, so the symbol shouldn't appear in the expressions
+/// being matched.
+#[derive(Clone)]
+pub struct Wild {
+    symbol: Symbol,
+    predicate: Option<Predicate>,
+    exclude: Vec<Symbol>,
+}
+
+impl Wild {
+    /// Construct a wildcard matching anything, written as the symbol `name`.
+    pub fn new(name: &str) -> Wild {
+        Wild { symbol: Symbol::new(name), predicate: None, exclude: vec![] }
+    }
+
+    /// Only match the expressions on which `f` returns `true`.
+    pub fn with_predicate<F: Fn(&Expr) -> bool + 'static>(self, f: F) -> Wild {
+        Wild { predicate: Some(Rc::new(f)), ..self }
+    }
+
+    /// Only match the expressions which don't contain any of `symbols`.
+    pub fn exclude(self, symbols: &[Symbol]) -> Wild {
+        Wild { exclude: symbols.to_vec(), ..self }
+    }
+
+    /// The symbol standing for the wildcard.
+    pub fn symbol(&self) -> Symbol {
+        self.symbol
+    }
+
+    fn accepts(&self, e: &Expr) -> bool {
+        self.exclude.iter().all(|&s| !e.has_symbol(s))
+            && self.predicate.as_ref().map_or(true, |f| f(e))
+    }
+}
+
+impl Debug for Wild {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wild")
+            .field("symbol", &self.symbol)
+            .field("predicate", &self.predicate.is_some())
+            .field("exclude", &self.exclude)
+            .finish()
+    }
+}
+
+impl<'a> From<&'a Wild> for Expr {
+    fn from(w: &Wild) -> Expr {
+        Sym(w.symbol)
+    }
+}
+
+/// An expression with wildcards, which is matched by `Expr::matches`.
+///
+/// The pattern is simplified, and so are the expressions matched against it, so they're
+/// compared in their canonical forms. Sums and products match in any order of their terms, where
+/// the other terms are matched first, and then the remaining ones are shared among the
+/// wildcards. A wildcard term may get no terms, and then it matches `0` in a sum or `1` in a
+/// product, so `a * sin(x)` matches `sin(x)` with `a = 1`. The number of a sum or product in
+/// the pattern is taken out of the one of the expression, so `2 * a` matches `6 * x` with
+/// `a = 3 * x`, and `a + 1` matches `x` with `a = x - 1`. The matching is structural otherwise.
+///
+/// A rule like `sin(2 * a) -> 2 * sin(a) * cos(a)` then applies to every sine, so a predicate is
+/// needed to make rewriting with it stop.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    expr: Expr,
+    wilds: Vec<Wild>,
+}
+
+impl Pattern {
+    /// Construct a pattern from an expression where the symbols of `wilds` are wildcards.
+    pub fn new<E: Into<Expr>>(e: E, wilds: &[Wild]) -> Pattern {
+        Pattern { expr: e.into().simplify(), wilds: wilds.to_vec() }
+    }
+
+    /// The simplified expression.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// The first way a canonical expression matches for which `accept` returns `true`. The ways
+    /// are tried lazily, and may be repeated.
+    fn find<F: Fn(&Bindings) -> bool>(&self, e: &Expr, accept: F) -> Option<Bindings> {
+        let mut res = None;
+        self.match_expr(&self.expr, e, Bindings::new(), &mut |b| {
+            if accept(&b) {
+                res = Some(b);
+            }
+            res.is_some()
+        });
+        res
+    }
+
+    fn wild(&self, p: &Expr) -> Option<&Wild> {
+        match *p {
+            Sym(s) => self.wilds.iter().find(|w| w.symbol == s),
+            _ => None,
+        }
+    }
+
+    fn bind(&self, w: &Wild, e: Expr, mut b: Bindings) -> Option<Bindings> {
+        match b.get(&w.symbol) {
+            Some(value) => return if *value == e { Some(b) } else { None },
+            None if !w.accepts(&e) => return None,
+            None => {}
+        }
+        b.insert(w.symbol, e);
+        Some(b)
+    }
+
+    /// Call `found` with each way `e` matches `p`, extending `b`, until it returns `true`, and
+    /// return whether it did.
+    fn match_expr(&self, p: &Expr, e: &Expr, b: Bindings, found: Found) -> bool {
+        if let Some(w) = self.wild(p) {
+            return match self.bind(w, e.clone(), b) {
+                Some(b) => found(b),
+                None => false,
+            };
+        }
+        let single = |e: &Expr| vec![e.clone()];
+        match (p, e) {
+            (&Sum(ref ps), &Sum(ref es)) => self.match_ac(ps, es, b, sum, negate, found),
+            (&Sum(ref ps), _) => self.match_ac(ps, &single(e), b, sum, negate, found),
+            (&Product(ref ps), &Product(ref es)) => {
+                self.match_ac(ps, es, b, product, recip, found)
+            }
+            (&Product(ref ps), _) => self.match_ac(ps, &single(e), b, product, recip, found),
+            (&Neg(ref p), &Neg(ref e)) => self.match_expr(p, e, b, found),
+            (&Ratio(ref p1, ref p2), &Ratio(ref e1, ref e2))
+            | (&Pow(ref p1, ref p2), &Pow(ref e1, ref e2)) => {
+                self.match_seq(&[&**p1, &**p2], &[&**e1, &**e2], b, found)
+            }
+            (&Apply(ref f, ref ps), &Apply(ref g, ref es)) if f == g && ps.len() == es.len() => {
+                let ps = ps.iter().collect::<Vec<_>>();
+                self.match_seq(&ps, &es.iter().collect::<Vec<_>>(), b, found)
+            }
+            _ if p == e => found(b),
+            _ => false,
+        }
+    }
+
+    /// Match the patterns with the expressions in order.
+    fn match_seq(&self, ps: &[&Expr], es: &[&Expr], b: Bindings, found: Found) -> bool {
+        match (ps.split_first(), es.split_first()) {
+            (Some((p, ps)), Some((e, es))) => {
+                self.match_expr(p, e, b, &mut |b| self.match_seq(ps, es, b, found))
+            }
+            _ => found(b),
+        }
+    }
+
+    /// Match the terms of a sum or a product in any order, where `combine` constructs the
+    /// canonical sum or product of the terms given to a wildcard, and `inverse` is the inverse of
+    /// a number for it.
+    fn match_ac(
+        &self,
+        ps: &[Expr],
+        es: &[Expr],
+        b: Bindings,
+        combine: fn(Vec<Expr>) -> Expr,
+        inverse: fn(Expr) -> Expr,
+        found: Found,
+    ) -> bool {
+        let (wilds, mut fixed): (Vec<&Expr>, Vec<&Expr>) = ps.iter().partition(|p| {
+            self.wild(p).is_some()
+        });
+        let wilds = wilds.into_iter().filter_map(|p| self.wild(p)).collect::<Vec<_>>();
+
+        // The number of the pattern is taken out of the one of the expression, and what's left
+        // goes to the wildcards.
+        let mut es = es.iter().cloned().collect::<Vec<_>>();
+        let number = fixed.iter().position(|p| p.is_number());
+        if let (Some(i), false) = (number, wilds.is_empty()) {
+            let c = fixed.remove(i).clone();
+            let d = match es.iter().position(Expr::is_number) {
+                Some(j) => es.remove(j),
+                None => combine(vec![]),
+            };
+            let rest = combine(vec![d, inverse(c)]);
+            if rest != combine(vec![]) {
+                es.push(rest);
+            }
+        }
+
+        self.match_fixed(&fixed, es.iter().collect(), b, &mut |rest, b| {
+            self.distribute(&wilds, &rest, b, combine, found)
+        })
+    }
+
+    /// Match every pattern with a different expression, and call `f` with the expressions left,
+    /// until it returns `true`.
+    fn match_fixed(
+        &self,
+        ps: &[&Expr],
+        es: Vec<&Expr>,
+        b: Bindings,
+        f: &mut dyn FnMut(Vec<&Expr>, Bindings) -> bool,
+    ) -> bool {
+        let (p, ps) = match ps.split_first() {
+            Some(split) => split,
+            None => return f(es, b),
+        };
+        (0..es.len()).any(|i| {
+            self.match_expr(p, es[i], b.clone(), &mut |b| {
+                let mut rest = es.clone();
+                rest.remove(i);
+                self.match_fixed(ps, rest, b, f)
+            })
+        })
+    }
+
+    /// Share the expressions among the wildcards in all the ways, trying first the ones where
+    /// fewer wildcards get nothing.
+    fn distribute(
+        &self,
+        wilds: &[&Wild],
+        es: &[&Expr],
+        b: Bindings,
+        combine: fn(Vec<Expr>) -> Expr,
+        found: Found,
+    ) -> bool {
+        if wilds.is_empty() {
+            return es.is_empty() && found(b);
+        }
+        let count = (0..es.len()).try_fold(1usize, |n, _| n.checked_mul(wilds.len()));
+        let count = match count {
+            Some(n) if n <= MAX_ASSIGNMENTS => n,
+            _ => return false,
+        };
+
+        // The `n`th way gives the `i`th expression to the wildcard of its `i`th digit in base
+        // `wilds.len()`.
+        let groups = |mut n: usize| {
+            let mut groups = vec![vec![]; wilds.len()];
+            for &e in es {
+                groups[n % wilds.len()].push(e.clone());
+                n /= wilds.len();
+            }
+            groups
+        };
+        // The number of wildcards which get nothing in the `n`th way.
+        let empty = |mut n: usize| {
+            let mut used = vec![false; wilds.len()];
+            for _ in es {
+                used[n % wilds.len()] = true;
+                n /= wilds.len();
+            }
+            used.iter().filter(|&&u| !u).count()
+        };
+        (0..=wilds.len()).any(|k| {
+            (0..count).filter(|&n| empty(n) == k).any(|n| {
+                let b = wilds.iter().zip(groups(n)).try_fold(b.clone(), |b, (w, group)| {
+                    self.bind(w, combine(group), b)
+                });
+                b.map_or(false, &mut *found)
+            })
+        })
+    }
+}
+
+fn negate(e: Expr) -> Expr {
+    product(vec![Expr::integer(-1), e])
+}
+
+fn recip(e: Expr) -> Expr {
+    power(e, Expr::integer(-1))
+}
+
+/// A rule which rewrites the expressions matching `lhs` into `rhs`, where the wildcards are
+/// replaced with their values, if a condition on them holds.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, Pattern, RewriteRule, Symbol, Wild};
+///
+/// let a = Wild::new("a");
+/// let b = Wild::new("b");
+/// let (sa, sb) = (a.symbol(), b.symbol());
+/// let sin_sum = RewriteRule::new(
+///     Pattern::new(Expr::sin(sa + sb), &[a, b]),
+///     Expr::sin(sa) * Expr::cos(sb) + Expr::cos(sa) * Expr::sin(sb),
+/// );
+///
+/// let x = Symbol::new("x");
+/// let y = Symbol::new("y");
+/// assert_eq!(
+///     Expr::sin(x + y).rewrite(&[sin_sum]),
+///     (Expr::sin(x) * Expr::cos(y) + Expr::cos(x) * Expr::sin(y)).simplify()
+/// );
+/// ```
+#[derive(Clone)]
+pub struct RewriteRule {
+    lhs: Pattern,
+    rhs: Expr,
+    condition: Option<Condition>,
+}
+
+impl RewriteRule {
+    /// Construct a rule without a condition.
+    pub fn new<E: Into<Expr>>(lhs: Pattern, rhs: E) -> RewriteRule {
+        RewriteRule { lhs, rhs: rhs.into(), condition: None }
+    }
+
+    /// Only rewrite when `f` returns `true` on the values of the wildcards.
+    pub fn with_condition<F: Fn(&Bindings) -> bool + 'static>(self, f: F) -> RewriteRule {
+        RewriteRule { condition: Some(Rc::new(f)), ..self }
+    }
+
+    /// The pattern.
+    pub fn lhs(&self) -> &Pattern {
+        &self.lhs
+    }
+
+    /// The replacement.
+    pub fn rhs(&self) -> &Expr {
+        &self.rhs
+    }
+
+    /// Rewrite the expression itself, but not its sub-expressions, or return `None` if it
+    /// doesn't match. The result is simplified.
+    pub fn apply(&self, e: &Expr) -> Option<Expr> {
+        self.apply_canonical(&e.simplify())
+    }
+
+    fn apply_canonical(&self, e: &Expr) -> Option<Expr> {
+        let holds = |b: &Bindings| self.condition.as_ref().map_or(true, |f| f(b));
+        let b = self.lhs.find(e, holds)?;
+        Some(self.rhs.subs_all(&b).simplify())
+    }
+}
+
+impl Debug for RewriteRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RewriteRule")
+            .field("lhs", &self.lhs)
+            .field("rhs", &self.rhs)
+            .field("condition", &self.condition.is_some())
+            .finish()
+    }
+}
+
+/// Rewrites every sub-expression from the bottom up.
+struct Rewriter<'a> {
+    rules: &'a [RewriteRule],
+    count: usize,
+}
+
+impl<'a> Folder for Rewriter<'a> {
+    fn fold(&mut self, e: &Expr) -> Expr {
+        let mut e = self.fold_children(e).simplify();
+        while self.count < MAX_REWRITES {
+            let mut results = self.rules.iter().filter_map(|r| r.apply_canonical(&e));
+            match results.find(|res| *res != e) {
+                Some(res) => {
+                    self.count += 1;
+                    e = res;
+                }
+                None => break,
+            }
+        }
+        e
+    }
+}
+
+/// Pattern matching.
+impl Expr {
+    /// Match the simplified expression against a pattern, and return the values of the
+    /// wildcards, or `None` if it doesn't match.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Pattern, Symbol, Wild};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let a = Wild::new("a").exclude(&[x]);
+    /// let b = Wild::new("b");
+    /// let (sa, sb) = (a.symbol(), b.symbol());
+    /// let pattern = Pattern::new(sa * Expr::sin(sb), &[a, b]);
+    ///
+    /// let bindings = (Expr::sin(x + 1) * y * 2).matches(&pattern).unwrap();
+    /// assert_eq!(bindings[&sa], (y * 2).simplify());
+    /// assert_eq!(bindings[&sb], (x + 1).simplify());
+    /// assert!((x * Expr::sin(y)).matches(&pattern).is_none());
+    /// ```
+    pub fn matches(&self, pattern: &Pattern) -> Option<Bindings> {
+        pattern.find(&self.simplify(), |_| true)
+    }
+
+    /// Rewrite with the rules until none applies, trying them in order on every sub-expression
+    /// from the bottom up. The result is simplified.
+    ///
+    /// At most a thousand rewrites are done, so rules which undo each other stop eventually.
+    pub fn rewrite(&self, rules: &[RewriteRule]) -> Expr {
+        let mut rewriter = Rewriter { rules, count: 0 };
+        let mut e = self.simplify();
+        loop {
+            let count = rewriter.count;
+            let next = rewriter.fold(&e);
+            if rewriter.count == count || next == e {
+                return next;
+            }
+            e = next;
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::rc::Rc;
+use num::{BigRational, Integer, One, Signed, Zero};
+
+use super::{Expr, Func, Series, Symbol};
+use super::puiseux::Expander;
+use super::simplify::to_rational;
+
+/// The number of terms shown by `Display`.
+const DISPLAY_TERMS: usize = 6;
+
+/// The order up to which `PowerSeries::from_expr` first expands an expression.
+const CHECK_ORDER: i64 = 4;
+
+/// The highest order up to which a leading term is looked for.
+const MAX_LEADING_ORDER: i64 = 64;
+
+/// Computes the `n`th coefficient from the previous ones.
+type Generator = Box<dyn Fn(usize, &[Expr]) -> Expr>;
+
+/// The coefficients computed so far, and how to compute the next one.
+struct Stream {
+    coeffs: RefCell<Vec<Expr>>,
+    next: Generator,
+}
+
+/// A formal power series `c_0 + c_1 x + c_2 x ^ 2 + ...` with infinitely many coefficients,
+/// which are computed on demand and then cached.
+///
+/// A series built from others refers to them, so their coefficients are shared, and cloning is
+/// cheap. The coefficients are expanded.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, PowerSeries, Symbol};
+///
+/// let x = Symbol::new("x");
+/// // The generating function of the Fibonacci numbers.
+/// let fib = PowerSeries::from_expr(&(1i32 - x - x.pow(2)).pow(-1), x).unwrap();
+/// assert_eq!(fib.coeff(10), Expr::integer(89));
+///
+/// let exp = PowerSeries::new(x, |n| if n == 1 { Expr::integer(1) } else { Expr::integer(0) })
+///     .exp();
+/// assert_eq!(exp.coeffs(4), vec![
+///     Expr::integer(1),
+///     Expr::integer(1),
+///     Expr::rational(1, 2),
+///     Expr::rational(1, 6),
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct PowerSeries {
+    var: Symbol,
+    stream: Rc<Stream>,
+}
+
+impl PowerSeries {
+    /// Construct a series in `var` whose `n`th coefficient is `f(n)`.
+    pub fn new<F>(var: Symbol, f: F) -> PowerSeries
+    where
+        F: Fn(usize) -> Expr + 'static,
+    {
+        PowerSeries::recursive(var, move |n, _| f(n))
+    }
+
+    /// Construct a series whose `n`th coefficient is computed from `n` and the previous ones.
+    fn recursive<F>(var: Symbol, f: F) -> PowerSeries
+    where
+        F: Fn(usize, &[Expr]) -> Expr + 'static,
+    {
+        let stream = Stream { coeffs: RefCell::new(vec![]), next: Box::new(f) };
+        PowerSeries { var, stream: Rc::new(stream) }
+    }
+
+    /// The Taylor series of `e` in `x` around zero, or `None` if it has none, like `sqrt(x)`,
+    /// `1 / x` or `abs(x)`, or it isn't known to have one. The coefficients are found by
+    /// expanding `e`, so removable singularities like in `sin(x) / x` are allowed.
+    ///
+    /// The whole series is checked before any coefficient is computed: the expression must have
+    /// no terms with negative exponents, and no powers or functions which bring in fractional
+    /// exponents or logarithms, however high their order.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = PowerSeries::from_expr(&(Expr::sin(x) / x), x).unwrap();
+    /// assert_eq!(s.coeff(2), Expr::rational(-1, 6));
+    /// assert!(PowerSeries::from_expr(&x.pow(Expr::rational(9, 2)), x).is_none());
+    /// assert!(PowerSeries::from_expr(&(x.pow(5) * Expr::ln(x)), x).is_none());
+    /// ```
+    pub fn from_expr(e: &Expr, x: Symbol) -> Option<PowerSeries> {
+        let e = e.simplify();
+        let log = Symbol::dummy("log");
+        let expander = Expander::new(x, log);
+        let s = expander.expand(&e, &BigRational::from_integer(CHECK_ORDER.into()))?;
+        let is_taylor = |&(ref k, ref c): &(BigRational, Expr)| {
+            k.is_integer() && !k.is_negative() && !c.has_symbol(log)
+        };
+        // The terms with negative exponents must all be known to be missing.
+        if !s.order.is_positive() || !s.terms.iter().all(is_taylor) {
+            return None;
+        }
+        if !is_laurent(&e, x, &expander) {
+            return None;
+        }
+
+        // The expansion up to the highest order needed so far.
+        let expansion = RefCell::new(s);
+        Some(PowerSeries::new(x, move |n| {
+            let n = BigRational::from_integer(n.into());
+            let mut s = expansion.borrow_mut();
+            while s.order <= n {
+                let doubled = &s.order * BigRational::from_integer(2.into());
+                let order = doubled.max(&n + BigRational::one());
+                *s = expander.expand(&e, &order).expect("the expansion exists to any order");
+            }
+            let term = s.terms.iter().find(|t| t.0 == n);
+            term.map_or_else(|| Expr::integer(0), |t| t.1.clone())
+        }))
+    }
+
+    /// The variable.
+    pub fn var(&self) -> Symbol {
+        self.var
+    }
+
+    /// The coefficient of `x ^ n`.
+    pub fn coeff(&self, n: usize) -> Expr {
+        loop {
+            let k = self.stream.coeffs.borrow().len();
+            if k > n {
+                break;
+            }
+            let c = (self.stream.next)(k, &self.stream.coeffs.borrow()).expand();
+            self.stream.coeffs.borrow_mut().push(c);
+        }
+        self.stream.coeffs.borrow()[n].clone()
+    }
+
+    /// The first `n` coefficients.
+    pub fn coeffs(&self, n: usize) -> Vec<Expr> {
+        (0..n).map(|k| self.coeff(k)).collect()
+    }
+
+    /// The polynomial of the first `n` terms.
+    pub fn to_expr(&self, n: usize) -> Expr {
+        let terms = (0..n).map(|k| self.coeff(k) * Expr::from(self.var).pow(k as i64));
+        Expr::Sum(terms.collect()).simplify()
+    }
+
+    /// The first `n` terms with the order term `O(x ^ n)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = PowerSeries::from_expr(&(1i32 - x).pow(-1), x).unwrap();
+    /// assert_eq!(s.to_series(3), (1i32 - x).pow(-1).series(x, 0, 3).unwrap());
+    /// ```
+    pub fn to_series(&self, n: usize) -> Series {
+        let terms = (0..n).map(|k| (BigRational::from_integer(k.into()), self.coeff(k)));
+        let order = BigRational::from_integer(n.into());
+        Series::from_terms(self.var, Expr::integer(0), terms.collect(), order)
+    }
+
+    /// The composition `self(g(x))`, or `None` if the constant term of `g` isn't zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let f = PowerSeries::from_expr(&(1i32 - x).pow(-1), x).unwrap();
+    /// let g = PowerSeries::from_expr(&(x / (1i32 + x)), x).unwrap();
+    /// assert_eq!(f.compose(&g).unwrap().to_expr(5), (1i32 + x).simplify());
+    /// ```
+    pub fn compose(&self, g: &PowerSeries) -> Option<PowerSeries> {
+        assert_eq!(self.var, g.var, "series in different variables");
+        if g.coeff(0) != Expr::integer(0) {
+            return None;
+        }
+        // The powers of `g`, where `g ^ k` has no terms below `x ^ k`.
+        let powers = RefCell::new(vec![PowerSeries::one(self.var)]);
+        let (f, g) = (self.clone(), g.clone());
+        Some(PowerSeries::new(self.var, move |n| {
+            let mut powers = powers.borrow_mut();
+            while powers.len() <= n {
+                let next = powers.last().unwrap() * &g;
+                powers.push(next);
+            }
+            Expr::Sum((0..=n).map(|k| f.coeff(k) * powers[k].coeff(n)).collect())
+        }))
+    }
+
+    /// The compositional inverse `g` with `self(g(x)) = g(self(x)) = x`, or `None` if the
+    /// constant term isn't zero or the linear term is.
+    ///
+    /// The coefficients are found by Lagrange inversion.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// // The generating function of the Catalan numbers.
+    /// let f = PowerSeries::from_expr(&(x - x.pow(2)), x).unwrap();
+    /// let catalan = f.reversion().unwrap();
+    /// let expected = vec![0, 1, 1, 2, 5, 14].into_iter().map(Expr::integer).collect::<Vec<_>>();
+    /// assert_eq!(catalan.coeffs(6), expected);
+    /// ```
+    pub fn reversion(&self) -> Option<PowerSeries> {
+        if self.coeff(0) != Expr::integer(0) || self.coeff(1) == Expr::integer(0) {
+            return None;
+        }
+        // g_n = [x ^ (n - 1)] h ^ n / n, where h = x / self.
+        let f = self.clone();
+        let h = PowerSeries::new(self.var, move |n| f.coeff(n + 1)).reciprocal()?;
+        let powers = RefCell::new(vec![PowerSeries::one(self.var)]);
+        Some(PowerSeries::new(self.var, move |n| {
+            if n == 0 {
+                return Expr::integer(0);
+            }
+            let mut powers = powers.borrow_mut();
+            while powers.len() <= n {
+                let next = powers.last().unwrap() * &h;
+                powers.push(next);
+            }
+            powers[n].coeff(n - 1) / Expr::integer(n)
+        }))
+    }
+
+    /// The multiplicative inverse, or `None` if the constant term is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = PowerSeries::from_expr(&(1i32 - x), x).unwrap().reciprocal().unwrap();
+    /// assert_eq!(s.coeff(100), Expr::integer(1));
+    /// ```
+    pub fn reciprocal(&self) -> Option<PowerSeries> {
+        let a0 = self.coeff(0);
+        if a0 == Expr::integer(0) {
+            return None;
+        }
+        // a_0 b_n = -(a_1 b_(n - 1) + ... + a_n b_0)
+        let inverse = a0.pow(-1);
+        let a = self.clone();
+        Some(PowerSeries::recursive(self.var, move |n, b| {
+            if n == 0 {
+                return inverse.clone();
+            }
+            let sum = (1..=n).map(|k| a.coeff(k) * b[n - k].clone()).collect();
+            -inverse.clone() * Expr::Sum(sum)
+        }))
+    }
+
+    /// The exponential.
+    pub fn exp(&self) -> PowerSeries {
+        // b' = a' b, so n b_n = 1 a_1 b_(n - 1) + ... + n a_n b_0.
+        let a = self.clone();
+        PowerSeries::recursive(self.var, move |n, b| {
+            if n == 0 {
+                return Expr::exp(a.coeff(0));
+            }
+            let sum = (1..=n).map(|k| Expr::integer(k) * a.coeff(k) * b[n - k].clone());
+            Expr::Sum(sum.collect()) / Expr::integer(n)
+        })
+    }
+
+    /// The natural logarithm, or `None` if the constant term is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, PowerSeries, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = PowerSeries::from_expr(&(1i32 + x), x).unwrap().ln().unwrap();
+    /// assert_eq!(s.to_expr(4), (x - x.pow(2) / 2 + x.pow(3) / 3).simplify());
+    /// ```
+    pub fn ln(&self) -> Option<PowerSeries> {
+        let a0 = self.coeff(0);
+        if a0 == Expr::integer(0) {
+            return None;
+        }
+        // a b' = a', so a_0 n b_n = n a_n - (1 a_1 b_1 (n - 1) + ... + a_(n - 1) 1 b_(n - 1)).
+        let a = self.clone();
+        Some(PowerSeries::recursive(self.var, move |n, b| {
+            if n == 0 {
+                return Expr::ln(a0.clone());
+            }
+            let sum = (1..n).map(|k| a.coeff(k) * Expr::integer(n - k) * b[n - k].clone());
+            let rest = Expr::integer(n) * a.coeff(n) - Expr::Sum(sum.collect());
+            rest / (Expr::integer(n) * a0.clone())
+        }))
+    }
+
+    /// The series `1`.
+    fn one(var: Symbol) -> PowerSeries {
+        PowerSeries::new(var, |n| Expr::integer(if n == 0 { 1 } else { 0 }))
+    }
+}
+
+/// Check that the Puiseux series of a canonical expression in `x` has only integer exponents and
+/// no logarithms, however high their order, and that it's the expansion on both sides of zero.
+///
+/// The base of a power `b ^ p` where `p` isn't an integer must have a leading term `c x ^ k`
+/// where `k` and `k p` are even, or `k` is zero if `p` isn't a number, like `(x ^ 4) ^ (1/2)`
+/// but not `(x ^ 2) ^ (1/2)`. The argument of `ln` must have a nonzero constant term, and that of
+/// `abs` an even leading exponent.
+fn is_laurent(e: &Expr, x: Symbol, expander: &Expander) -> bool {
+    if !e.has_symbol(x) {
+        return true;
+    }
+    if !e.children().all(|c| is_laurent(c, x, expander)) {
+        return false;
+    }
+    let leading = |b: &Expr| leading_exponent(b, expander);
+    match *e {
+        Expr::Pow(ref b, ref p) => match to_rational(p) {
+            Some(ref p) if p.is_integer() => true,
+            Some(ref p) => leading(b).filter(|k| is_even(k) && is_even(&(k * p))).is_some(),
+            None => leading(b) == Some(BigRational::zero()),
+        },
+        Expr::Apply(Func::Ln, ref args) => leading(&args[0]) == Some(BigRational::zero()),
+        Expr::Apply(Func::Abs, ref args) => leading(&args[0]).filter(is_even).is_some(),
+        _ => true,
+    }
+}
+
+/// The exponent of the leading term of the expansion of `e`, or `None` if it isn't found.
+fn leading_exponent(e: &Expr, expander: &Expander) -> Option<BigRational> {
+    let mut order = BigRational::from_integer(CHECK_ORDER.into());
+    while order <= BigRational::from_integer(MAX_LEADING_ORDER.into()) {
+        if let Some(t) = expander.expand(e, &order)?.terms.first() {
+            return Some(t.0.clone());
+        }
+        order = &order * BigRational::from_integer(2.into());
+    }
+    None
+}
+
+fn is_even(r: &BigRational) -> bool {
+    r.is_integer() && r.to_integer().is_even()
+}
+
+impl Debug for PowerSeries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PowerSeries")
+            .field("var", &self.var)
+            .field("coeffs", &*self.stream.coeffs.borrow())
+            .finish()
+    }
+}
+
+/// Shows the first terms with an order term.
+impl Display for PowerSeries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_series(DISPLAY_TERMS))
+    }
+}
+
+// The operators panic if the series are in different variables.
+
+impl<'a, 'b> Add<&'b PowerSeries> for &'a PowerSeries {
+    type Output = PowerSeries;
+
+    fn add(self, rhs: &PowerSeries) -> PowerSeries {
+        assert_eq!(self.var, rhs.var, "series in different variables");
+        let (a, b) = (self.clone(), rhs.clone());
+        PowerSeries::new(self.var, move |n| a.coeff(n) + b.coeff(n))
+    }
+}
+
+impl Add for PowerSeries {
+    type Output = PowerSeries;
+
+    fn add(self, rhs: PowerSeries) -> PowerSeries {
+        &self + &rhs
+    }
+}
+
+impl<'a, 'b> Sub<&'b PowerSeries> for &'a PowerSeries {
+    type Output = PowerSeries;
+
+    fn sub(self, rhs: &PowerSeries) -> PowerSeries {
+        self + &-rhs
+    }
+}
+
+impl Sub for PowerSeries {
+    type Output = PowerSeries;
+
+    fn sub(self, rhs: PowerSeries) -> PowerSeries {
+        &self - &rhs
+    }
+}
+
+impl<'a, 'b> Mul<&'b PowerSeries> for &'a PowerSeries {
+    type Output = PowerSeries;
+
+    fn mul(self, rhs: &PowerSeries) -> PowerSeries {
+        assert_eq!(self.var, rhs.var, "series in different variables");
+        let (a, b) = (self.clone(), rhs.clone());
+        PowerSeries::new(self.var, move |n| {
+            Expr::Sum((0..=n).map(|k| a.coeff(k) * b.coeff(n - k)).collect())
+        })
+    }
+}
+
+impl Mul for PowerSeries {
+    type Output = PowerSeries;
+
+    fn mul(self, rhs: PowerSeries) -> PowerSeries {
+        &self * &rhs
+    }
+}
+
+impl<'a> Neg for &'a PowerSeries {
+    type Output = PowerSeries;
+
+    fn neg(self) -> PowerSeries {
+        let a = self.clone();
+        PowerSeries::new(self.var, move |n| -a.coeff(n))
+    }
+}
+
+impl Neg for PowerSeries {
+    type Output = PowerSeries;
+
+    fn neg(self) -> PowerSeries {
+        -&self
+    }
+}
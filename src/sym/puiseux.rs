@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+use num::{BigInt, BigRational, One, Signed, Zero, ToPrimitive};
+
+use crate::poly::coeffs;
+use super::{Expr, Func, Symbol};
+use super::integrate::has_infinity;
+use super::simplify::to_rational;
+use self::Expr::*;
+
+/// A truncated Puiseux series `c_1 t ^ e_1 + ... + c_n t ^ e_n + O(t ^ order)` as `t -> 0+`.
+///
+/// The exponents are rational, increasing and less than `order`, and the coefficients are
+/// nonzero and expanded. The coefficients don't depend on `t` except through a symbol standing
+/// for `ln(t)`, which grows slower than any power of `t`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Puiseux {
+    pub terms: Vec<(BigRational, Expr)>,
+    pub order: BigRational,
+}
+
+impl Puiseux {
+    /// The series `c + O(t ^ order)`.
+    fn constant(c: Expr, order: BigRational) -> Puiseux {
+        Puiseux::from_map(Some((BigRational::zero(), c)).into_iter().collect(), order)
+    }
+
+    /// Build a series from the coefficients, dropping zeros and the terms beyond the order.
+    fn from_map(map: BTreeMap<BigRational, Expr>, order: BigRational) -> Puiseux {
+        let terms = map
+            .into_iter()
+            .map(|(e, c)| (e, c.expand()))
+            .filter(|&(ref e, ref c)| *e < order && *c != Expr::integer(0))
+            .collect();
+        Puiseux { terms, order }
+    }
+
+    /// The exponent of the leading term, or the order if there are no known terms.
+    pub fn valuation(&self) -> BigRational {
+        self.terms.first().map_or_else(|| self.order.clone(), |t| t.0.clone())
+    }
+
+    pub fn add(&self, other: &Puiseux) -> Puiseux {
+        let order = self.order.clone().min(other.order.clone());
+        let mut map = BTreeMap::new();
+        for &(ref e, ref c) in self.terms.iter().chain(&other.terms) {
+            add_term(&mut map, e.clone(), c.clone());
+        }
+        Puiseux::from_map(map, order)
+    }
+
+    pub fn mul(&self, other: &Puiseux) -> Puiseux {
+        let order = (&self.order + other.valuation()).min(&other.order + self.valuation());
+        let mut map = BTreeMap::new();
+        for &(ref e1, ref c1) in &self.terms {
+            for &(ref e2, ref c2) in &other.terms {
+                let e = e1 + e2;
+                if e < order {
+                    add_term(&mut map, e, c1.clone() * c2.clone());
+                }
+            }
+        }
+        Puiseux::from_map(map, order)
+    }
+
+    /// Multiply by `c t ^ e`.
+    pub fn scale(&self, c: &Expr, e: &BigRational) -> Puiseux {
+        let map = self.terms.iter().map(|&(ref f, ref d)| (f + e, c.clone() * d.clone()));
+        Puiseux::from_map(map.collect(), &self.order + e)
+    }
+
+    /// Drop the terms from `t ^ order` on.
+    pub fn truncate(self, order: &BigRational) -> Puiseux {
+        if self.order <= *order {
+            return self;
+        }
+        let terms = self.terms.into_iter().filter(|t| t.0 < *order).collect();
+        Puiseux { terms, order: order.clone() }
+    }
+
+    /// Raise to a constant power, or return `None` if no term is known, or the power is
+    /// symbolic and the leading exponent isn't zero.
+    pub fn pow(&self, p: &Expr) -> Option<Puiseux> {
+        let (c, e, r) = self.split()?;
+        let exponent = if e.is_zero() { BigRational::zero() } else { &e * to_rational(p)? };
+        // (1 + r) ^ p = sum(binomial(p, k) r ^ k)
+        let binomial = |k: usize| {
+            let numer = (0..k).map(|i| p.clone() - Expr::integer(i));
+            let numer = numer.fold(Expr::integer(1), |acc, f| acc * f);
+            (numer / factorial(k)).simplify()
+        };
+        let res = Puiseux::compose(&r, binomial, r.order.clone());
+        Some(res.scale(&Expr::pow(c, p.clone()).simplify(), &exponent))
+    }
+
+    /// Split into the leading term `c t ^ e` and the rest `r` relative to it, so that the series
+    /// is `c t ^ e (1 + r)`, or `None` if no term is known.
+    fn split(&self) -> Option<(Expr, BigRational, Puiseux)> {
+        let (e, c) = self.terms.first()?.clone();
+        let inv = Expr::pow(c.clone(), -1);
+        let rest = Puiseux {
+            terms: self.terms[1..].to_vec(),
+            order: self.order.clone(),
+        };
+        Some((c, e.clone(), rest.scale(&inv, &-e)))
+    }
+
+    /// Split into the constant term and the rest, or return `None` if there are terms with
+    /// negative exponents or the constant term isn't known.
+    fn split_constant(&self) -> Option<(Expr, Puiseux)> {
+        if !self.order.is_positive() || self.valuation().is_negative() {
+            return None;
+        }
+        let constant = self.terms.iter().find(|t| t.0.is_zero()).map(|t| t.1.clone());
+        let rest = Puiseux {
+            terms: self.terms.iter().filter(|t| t.0.is_positive()).cloned().collect(),
+            order: self.order.clone(),
+        };
+        Some((constant.unwrap_or_else(|| Expr::integer(0)), rest))
+    }
+
+    /// The sum of `a(k) r ^ k` for `k >= 0`, truncated at `order`, where `r` has a positive
+    /// valuation.
+    fn compose<F>(r: &Puiseux, a: F, order: BigRational) -> Puiseux
+    where
+        F: Fn(usize) -> Expr,
+    {
+        let order = order.min(r.order.clone());
+        let mut res = Puiseux::constant(a(0), order.clone());
+        let mut power = Puiseux::constant(Expr::integer(1), order.clone());
+        for k in 1.. {
+            power = power.mul(r).truncate(&order);
+            if power.terms.is_empty() {
+                break;
+            }
+            res = res.add(&power.scale(&a(k), &BigRational::zero()));
+        }
+        res
+    }
+}
+
+fn add_term(map: &mut BTreeMap<BigRational, Expr>, e: BigRational, c: Expr) {
+    let sum = match map.remove(&e) {
+        Some(d) => d + c,
+        None => c,
+    };
+    map.insert(e, sum);
+}
+
+pub(crate) fn factorial(k: usize) -> Expr {
+    Expr::integer((1..=k).fold(BigInt::one(), |acc, i| acc * BigInt::from(i)))
+}
+
+/// Expansions of expressions in `t` into Puiseux series.
+pub(crate) struct Expander {
+    t: Symbol,
+    log: Symbol,
+}
+
+impl Expander {
+    /// Expand in `t`, where `log` stands for `ln(t)`. Neither symbol should appear otherwise.
+    pub fn new(t: Symbol, log: Symbol) -> Expander {
+        Expander { t, log }
+    }
+
+    /// Expand a canonical expression up to `O(t ^ order)`, or return `None` if it has no
+    /// Puiseux series, like `exp(1 / t)`, or the series can't be found.
+    ///
+    /// Fewer terms may be known if some cancel, in which case the order of the result is lower.
+    pub fn expand(&self, e: &Expr, order: &BigRational) -> Option<Puiseux> {
+        if !e.has_symbol(self.t) {
+            return if has_infinity(e) || *e == Undefined {
+                None
+            } else {
+                Some(Puiseux::constant(e.clone(), order.clone()))
+            };
+        }
+
+        match *e {
+            Sym(_) => {
+                let map = Some((BigRational::one(), Expr::integer(1))).into_iter().collect();
+                Some(Puiseux::from_map(map, order.clone()))
+            }
+            Sum(ref args) => {
+                let mut res = Puiseux::constant(Expr::integer(0), order.clone());
+                for arg in args {
+                    res = res.add(&self.expand(arg, order)?);
+                }
+                Some(res)
+            }
+            Product(ref args) => {
+                // Each factor is expanded again up to the order divided by the others.
+                let first = args
+                    .iter()
+                    .map(|arg| self.expand(arg, order))
+                    .collect::<Option<Vec<_>>>()?;
+                let total = first.iter().fold(BigRational::zero(), |a, s| a + s.valuation());
+                let mut res: Option<Puiseux> = None;
+                for (arg, s) in args.iter().zip(first) {
+                    let needed = order - (&total - s.valuation());
+                    let s = if needed > s.order { self.expand(arg, &needed)? } else { s };
+                    res = Some(match res {
+                        Some(res) => res.mul(&s),
+                        None => s,
+                    });
+                }
+                res
+            }
+            Pow(ref b, ref p) if !p.has_symbol(self.t) => {
+                let mut base = self.expand(b, order)?;
+                if let Some(p) = to_rational(p) {
+                    // c t ^ e (1 + r) ^ p has the relative order of `r`.
+                    let e = base.valuation();
+                    let needed = order + &e * (BigRational::one() - p);
+                    if needed > base.order {
+                        base = self.expand(b, &needed)?;
+                    }
+                }
+                Some(base.pow(p)?.truncate(order))
+            }
+            Pow(ref b, ref p) => {
+                let exponent = (**p).clone() * Expr::ln((**b).clone());
+                self.expand(&Expr::exp(exponent).simplify(), order)
+            }
+            Apply(Func::Exp, ref args) => self.exp(&self.expand(&args[0], order)?, order),
+            Apply(Func::Ln, ref args) => {
+                let mut u = self.expand(&args[0], order)?;
+                let e = u.valuation();
+                if &e + order > u.order {
+                    u = self.expand(&args[0], &(&e + order))?;
+                }
+                self.ln(&u, order)
+            }
+            Apply(Func::Abs, ref args) => {
+                let u = self.expand(&args[0], order)?;
+                match sign(&u.terms.first()?.1, self.log)? {
+                    s if s > 0 => Some(u),
+                    _ => Some(u.scale(&Expr::integer(-1), &BigRational::zero())),
+                }
+            }
+            Apply(ref f, ref args) if f.is_builtin() && args.len() == 1 => {
+                self.taylor(f, &self.expand(&args[0], order)?, order)
+            }
+            _ => None,
+        }
+    }
+
+    fn exp(&self, s: &Puiseux, order: &BigRational) -> Option<Puiseux> {
+        let (constant, rest) = s.split_constant()?;
+
+        // exp(a log + b) = t ^ a exp(b)
+        let (shift, factor) = match constant {
+            c if c.has_symbol(self.log) => {
+                let cs = coeffs(&c, self.log).ok()?;
+                let a = match cs.get(1) {
+                    Some(a) => to_rational(a)?,
+                    None => BigRational::zero(),
+                };
+                if cs.len() > 2 {
+                    return None;
+                }
+                let b = cs.into_iter().next().unwrap_or_else(|| Expr::integer(0));
+                (a, Expr::exp(b).simplify())
+            }
+            c => (BigRational::zero(), Expr::exp(c).simplify()),
+        };
+        let res = Puiseux::compose(&rest, |k| factorial(k).pow(-1), order - &shift);
+        Some(res.scale(&factor, &shift))
+    }
+
+    fn ln(&self, s: &Puiseux, order: &BigRational) -> Option<Puiseux> {
+        let (c, e, r) = s.split()?;
+        if c.has_symbol(self.log) {
+            return None;
+        }
+        // ln(c t ^ e (1 + r)) = ln(c) + e ln(t) + sum((-1) ^ (k + 1) r ^ k / k)
+        let series = |k: usize| match k {
+            0 => Expr::integer(0),
+            k => Expr::rational(if k % 2 == 1 { 1 } else { -1 }, k as i64),
+        };
+        let res = Puiseux::compose(&r, series, order.clone());
+        let constant = Expr::ln(c) + Expr::from(e) * self.log;
+        Some(res.add(&Puiseux::constant(constant.simplify(), order.clone())))
+    }
+
+    /// Expand `f(a + h)` by Taylor's formula, where `a` is the constant term.
+    fn taylor(&self, f: &Func, s: &Puiseux, order: &BigRational) -> Option<Puiseux> {
+        let (a, h) = s.split_constant()?;
+        if a.has_symbol(self.log) {
+            return None;
+        }
+
+        // The argument of the differentiated function.
+        let y = Symbol::dummy("arg");
+        let mut derivative = Expr::Apply(f.clone(), vec![Sym(y)]);
+        let mut coeffs = vec![];
+        let count = match h.terms.first() {
+            Some(&(ref m, _)) => (order / m).ceil().to_integer().to_usize().unwrap_or(0),
+            None => 0,
+        };
+        for k in 0..=count {
+            let c = (derivative.subs(y, a.clone()) / factorial(k)).simplify();
+            if c == Undefined || has_infinity(&c) {
+                return None;
+            }
+            coeffs.push(c);
+            derivative = derivative.diff(y);
+        }
+        let a = |k: usize| coeffs.get(k).cloned().unwrap_or_else(|| Expr::integer(0));
+        Some(Puiseux::compose(&h, a, order.clone()))
+    }
+}
+
+/// The sign of a coefficient as `t -> 0+`, where `log` goes to negative infinity, or `None` if
+/// it's unknown.
+pub(crate) fn sign(c: &Expr, log: Symbol) -> Option<i32> {
+    let cs = coeffs(c, log).ok()?;
+    let degree = cs.len().checked_sub(1)?;
+    let value = cs[degree].eval(&Default::default()).ok()?;
+    let s = if value > 0.0 { 1 } else if value < 0.0 { -1 } else { return None };
+    Some(if degree % 2 == 0 { s } else { -s })
+}
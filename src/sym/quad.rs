@@ -0,0 +1,152 @@
+use std::f64;
+
+use super::EvalError;
+
+/// The nodes of the 15-point Kronrod rule on `[-1, 1]`, from the outermost to the center. The
+/// odd ones are the nodes of the 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_5,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_48,
+    0.0,
+];
+
+/// The weights of the 15-point Kronrod rule.
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_224,
+    0.063_092_092_629_978_56,
+    0.104_790_010_322_250_19,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_42,
+    0.204_432_940_075_298_89,
+    0.209_482_141_084_727_82,
+];
+
+/// The weights of the 7-point Gauss rule, at the odd Kronrod nodes.
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_64,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// The maximum number of subintervals.
+const MAX_INTERVALS: usize = 2000;
+
+/// The requested relative error.
+const RELATIVE_TOLERANCE: f64 = 1e-12;
+
+/// The requested absolute error.
+const ABSOLUTE_TOLERANCE: f64 = 1e-14;
+
+/// Integrate `f` over `[a, b]` by adaptive Gauss-Kronrod quadrature, returning the value and an
+/// estimate of its absolute error, or `NoConvergence` if the requested error isn't reached.
+///
+/// Infinite bounds are mapped to finite ones by substitution. The integrand is never evaluated
+/// at the bounds, so it may be singular there.
+pub(crate) fn gauss_kronrod<F>(f: F, a: f64, b: f64) -> Result<(f64, f64), EvalError>
+where
+    F: Fn(f64) -> Result<f64, EvalError>,
+{
+    if a.is_nan() || b.is_nan() {
+        return Err(EvalError::Undefined);
+    }
+    if a > b {
+        return gauss_kronrod(f, b, a).map(|(value, error)| (-value, error));
+    }
+    if a == b {
+        return Ok((0.0, 0.0));
+    }
+
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => adaptive(&f, a, b),
+        // x = a + t / (1 - t)
+        (true, false) => adaptive(
+            &|t: f64| Ok(f(a + t / (1.0 - t))? / ((1.0 - t) * (1.0 - t))),
+            0.0,
+            1.0,
+        ),
+        // x = b - t / (1 - t)
+        (false, true) => adaptive(
+            &|t: f64| Ok(f(b - t / (1.0 - t))? / ((1.0 - t) * (1.0 - t))),
+            0.0,
+            1.0,
+        ),
+        // x = t / (1 - t ^ 2)
+        (false, false) => adaptive(
+            &|t: f64| {
+                let s = 1.0 - t * t;
+                Ok(f(t / s)? * (1.0 + t * t) / (s * s))
+            },
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+/// Bisect the subinterval with the greatest error until the total error is small enough.
+fn adaptive<F>(f: &F, a: f64, b: f64) -> Result<(f64, f64), EvalError>
+where
+    F: Fn(f64) -> Result<f64, EvalError>,
+{
+    let (value, error) = kronrod(f, a, b)?;
+    let mut intervals = vec![(a, b, value, error)];
+    let (mut value, mut error) = (value, error);
+
+    while error > ABSOLUTE_TOLERANCE.max(RELATIVE_TOLERANCE * value.abs()) {
+        if intervals.len() >= MAX_INTERVALS {
+            return Err(EvalError::NoConvergence);
+        }
+        let i = (0..intervals.len())
+            .max_by(|&i, &j| intervals[i].3.total_cmp(&intervals[j].3))
+            .unwrap();
+        let (a, b, _, _) = intervals.swap_remove(i);
+        let mid = (a + b) / 2.0;
+        if mid <= a || mid >= b {
+            // The interval can't be split any further.
+            return Err(EvalError::NoConvergence);
+        }
+        let (v1, e1) = kronrod(f, a, mid)?;
+        let (v2, e2) = kronrod(f, mid, b)?;
+        intervals.push((a, mid, v1, e1));
+        intervals.push((mid, b, v2, e2));
+
+        value = intervals.iter().map(|i| i.2).sum();
+        error = intervals.iter().map(|i| i.3).sum();
+    }
+    Ok((value, error))
+}
+
+/// Apply the 15-point Kronrod rule, and estimate the error by the difference from the 7-point
+/// Gauss rule.
+fn kronrod<F>(f: &F, a: f64, b: f64) -> Result<(f64, f64), EvalError>
+where
+    F: Fn(f64) -> Result<f64, EvalError>,
+{
+    let center = (a + b) / 2.0;
+    let half = (b - a) / 2.0;
+    let value_at = |x: f64| {
+        let y = f(x)?;
+        if y.is_finite() { Ok(y) } else { Err(EvalError::Undefined) }
+    };
+
+    let mut kronrod = 0.0;
+    let mut gauss = 0.0;
+    for (i, (&node, &weight)) in KRONROD_NODES.iter().zip(&KRONROD_WEIGHTS).enumerate() {
+        let y = if node == 0.0 {
+            value_at(center)?
+        } else {
+            value_at(center - half * node)? + value_at(center + half * node)?
+        };
+        kronrod += weight * y;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * y;
+        }
+    }
+    Ok((kronrod * half, ((kronrod - gauss) * half).abs()))
+}
@@ -0,0 +1,228 @@
+use std::collections::BTreeSet;
+use num::{Signed, ToPrimitive};
+
+use super::{Expr, Symbol};
+use self::Expr::*;
+
+/// Structural queries.
+impl Expr {
+    /// The symbols in the expression, sorted by name.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let symbols = (Expr::sin(y) + x * y).free_symbols();
+    /// assert_eq!(symbols.into_iter().collect::<Vec<_>>(), vec![x, y]);
+    /// ```
+    pub fn free_symbols(&self) -> BTreeSet<Symbol> {
+        self.iter_preorder()
+            .filter_map(|e| match *e {
+                Sym(s) => Some(s),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check if a sub-expression, or the expression itself, is structurally equal to `e`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let e = Expr::sin(x + 1) * 2;
+    /// assert!(e.contains(&(x + 1)));
+    /// assert!(!e.contains(&(1i32 + x)));
+    /// ```
+    pub fn contains(&self, e: &Expr) -> bool {
+        self.iter_preorder().any(|sub| sub == e)
+    }
+
+    /// Check if the symbol `s` appears in the expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert!(Expr::exp(x * y).has_symbol(x));
+    /// assert!(!Expr::exp(y).has_symbol(x));
+    /// ```
+    pub fn has_symbol(&self, s: Symbol) -> bool {
+        self.iter_preorder().any(|e| match *e {
+            Sym(t) => t == s,
+            _ => false,
+        })
+    }
+
+    /// The number of nodes on the longest path from the root to a leaf, which is `1` for a
+    /// primitive.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!(Expr::from(x).depth(), 1);
+    /// assert_eq!((Expr::sin(x) + 1).depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        1 + self.children().map(Expr::depth).max().unwrap_or(0)
+    }
+
+    /// The number of nodes in the tree, where repeated sub-expressions are counted every time.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!((Expr::sin(x) + x).node_count(), 4);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        self.iter_preorder().count()
+    }
+
+    /// The number of operations weighted by their kind, as a measure of how costly the
+    /// expression is to evaluate.
+    ///
+    /// Every negation and addition counts `1`, every multiplication `2`, every division and
+    /// power `3`, and every function application `5`. Numbers, symbols and constants count
+    /// nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!((x + y + 1).count_ops(), 2);
+    /// assert_eq!((x * y).pow(2).count_ops(), 5);
+    /// assert_eq!(Expr::sin(x / y).count_ops(), 8);
+    /// ```
+    pub fn count_ops(&self) -> usize {
+        self.iter_preorder()
+            .map(|e| match *e {
+                Integer(_) | Rational(_) | Sym(_) | Approx(_) | Const(_) | Undefined => 0,
+                Neg(_) => 1,
+                Sum(ref args) => args.len().saturating_sub(1),
+                Product(ref args) => 2 * args.len().saturating_sub(1),
+                Ratio(..) | Pow(..) => 3,
+                Apply(..) => 5,
+            })
+            .sum()
+    }
+
+    /// Check if the expression is a polynomial in `vars` as it's written, where the
+    /// coefficients may be any expressions not containing `vars`.
+    ///
+    /// The expression isn't simplified, so `x ^ 2 / x` isn't a polynomial in `x` but its
+    /// simplified form is.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let e = x.pow(2) * Expr::sin(y) + x / 2;
+    /// assert!(e.is_polynomial_in(&[x]));
+    /// assert!(!e.is_polynomial_in(&[x, y]));
+    /// assert!(!(x / (x + 1)).is_polynomial_in(&[x]));
+    /// ```
+    pub fn is_polynomial_in(&self, vars: &[Symbol]) -> bool {
+        if !self.has_any(vars) {
+            return true;
+        }
+        match *self {
+            Sym(_) => true,
+            Neg(_) | Sum(_) | Product(_) => self.children().all(|c| c.is_polynomial_in(vars)),
+            Ratio(ref n, ref d) => n.is_polynomial_in(vars) && !d.has_any(vars),
+            Pow(ref b, ref p) => match **p {
+                Integer(ref n) if !n.is_negative() => b.is_polynomial_in(vars),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Check if the expression is a ratio of polynomials in `vars` as it's written, where the
+    /// coefficients may be any expressions not containing `vars`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert!((x / (x + y) + x.pow(-2)).is_rational_function_in(&[x, y]));
+    /// assert!(!x.pow(Expr::rational(1, 2)).is_rational_function_in(&[x]));
+    /// ```
+    pub fn is_rational_function_in(&self, vars: &[Symbol]) -> bool {
+        if !self.has_any(vars) {
+            return true;
+        }
+        match *self {
+            Sym(_) => true,
+            Neg(_) | Sum(_) | Product(_) | Ratio(..) => {
+                self.children().all(|c| c.is_rational_function_in(vars))
+            }
+            Pow(ref b, ref p) => match **p {
+                Integer(_) => b.is_rational_function_in(vars),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The degree in `s` of the expanded expression, or `None` if it isn't a polynomial in `s`
+    /// or it's zero. Like in `is_polynomial_in`, the coefficients may contain other symbols.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!(((x + y).pow(3) - x.pow(3)).degree_in(x), Some(2));
+    /// assert_eq!(Expr::sin(y).degree_in(x), Some(0));
+    /// assert_eq!(Expr::sin(x).degree_in(x), None);
+    /// assert_eq!((x - x).degree_in(x), None);
+    /// ```
+    pub fn degree_in(&self, s: Symbol) -> Option<u32> {
+        match self.expand() {
+            ref e if *e == Expr::integer(0) => None,
+            e => degree(&e, s),
+        }
+    }
+
+    fn has_any(&self, vars: &[Symbol]) -> bool {
+        vars.iter().any(|&v| self.has_symbol(v))
+    }
+}
+
+/// The degree in `s` of an expression as it's written.
+fn degree(e: &Expr, s: Symbol) -> Option<u32> {
+    if !e.has_symbol(s) {
+        return Some(0);
+    }
+    match *e {
+        Sym(_) => Some(1),
+        Neg(ref e) => degree(e, s),
+        Sum(ref args) => {
+            let degrees = args.iter().map(|arg| degree(arg, s)).collect::<Option<Vec<_>>>()?;
+            degrees.into_iter().max()
+        }
+        Product(ref args) => args.iter().map(|arg| degree(arg, s)).sum(),
+        Ratio(ref n, ref d) if !d.has_symbol(s) => degree(n, s),
+        Pow(ref b, ref p) => match **p {
+            Integer(ref n) => degree(b, s)?.checked_mul(n.to_u32()?),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,473 @@
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::mem;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::{Rc, Weak};
+use num::{BigInt, BigRational, Zero};
+
+use super::{Constant, Expr, Func, Symbol};
+use super::expr::approx_bits;
+
+/// The number of interned nodes above which dead entries are removed from the table.
+const MIN_PURGE_SIZE: usize = 1024;
+
+/// A node of a `RcExpr`. It's like `Expr`, except that the children are shared.
+#[derive(Debug, Clone)]
+pub enum RcNode {
+    /// An integer.
+    Integer(BigInt),
+    /// An exact fraction in lowest terms.
+    Rational(BigRational),
+    /// A symbol.
+    Sym(Symbol),
+    /// A float approximate value.
+    Approx(f64),
+    /// An exact constant.
+    Const(Constant),
+    /// A negative.
+    Neg(RcExpr),
+    /// A sum.
+    Sum(Vec<RcExpr>),
+    /// A product.
+    Product(Vec<RcExpr>),
+    /// A ratio.
+    Ratio(RcExpr, RcExpr),
+    /// A power.
+    Pow(RcExpr, RcExpr),
+    /// A function application.
+    Apply(Func, Vec<RcExpr>),
+    /// An undefined value.
+    Undefined,
+}
+
+use self::RcNode::*;
+
+/// The children are compared by identity, which is structural equality for interned nodes.
+/// `Approx` values are compared like in `Expr`.
+impl PartialEq for RcNode {
+    fn eq(&self, other: &RcNode) -> bool {
+        match (self, other) {
+            (Integer(a), Integer(b)) => a == b,
+            (Rational(a), Rational(b)) => a == b,
+            (Sym(a), Sym(b)) => a == b,
+            (Approx(a), Approx(b)) => approx_bits(*a) == approx_bits(*b),
+            (Const(a), Const(b)) => a == b,
+            (Neg(a), Neg(b)) => a == b,
+            (Sum(a), Sum(b)) | (Product(a), Product(b)) => a == b,
+            (Ratio(a1, b1), Ratio(a2, b2)) | (Pow(a1, b1), Pow(a2, b2)) => a1 == a2 && b1 == b2,
+            (Apply(f, a), Apply(g, b)) => f == g && a == b,
+            (Undefined, Undefined) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RcNode {}
+
+impl Hash for RcNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match *self {
+            Integer(ref i) => i.hash(state),
+            Rational(ref r) => r.hash(state),
+            Sym(s) => s.hash(state),
+            Approx(f) => approx_bits(f).hash(state),
+            Const(c) => c.hash(state),
+            Neg(ref e) => e.hash(state),
+            Sum(ref args) | Product(ref args) => args.hash(state),
+            Ratio(ref a, ref b) | Pow(ref a, ref b) => {
+                a.hash(state);
+                b.hash(state);
+            }
+            Apply(ref f, ref args) => {
+                f.hash(state);
+                args.hash(state);
+            }
+            Undefined => {}
+        }
+    }
+}
+
+struct Inner {
+    node: RcNode,
+    hash: u64,
+}
+
+/// The children which aren't shared are dropped in a loop instead of recursively, so that deep
+/// expressions don't overflow the stack.
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        take_children(&mut self.node, &mut stack);
+        while let Some(e) = stack.pop() {
+            if let Ok(mut inner) = Rc::try_unwrap(e.0) {
+                take_children(&mut inner.node, &mut stack);
+            }
+        }
+    }
+}
+
+/// Move the children out of a node, which is left without them.
+fn take_children(node: &mut RcNode, stack: &mut Vec<RcExpr>) {
+    match mem::replace(node, Undefined) {
+        Neg(e) => stack.push(e),
+        Sum(args) | Product(args) | Apply(_, args) => stack.extend(args),
+        Ratio(a, b) | Pow(a, b) => {
+            stack.push(a);
+            stack.push(b);
+        }
+        _ => {}
+    }
+}
+
+/// A reference-counted expression, where identical subexpressions are shared.
+///
+/// Every node is interned in a table of the current thread when it's constructed, so there's
+/// only one copy of each distinct subexpression, and large expressions with repeated parts are
+/// stored as directed acyclic graphs. Cloning only increments a reference count, comparison
+/// only compares pointers, and hashing uses a structural hash computed on construction.
+///
+/// The operators build the same structure as the ones of `Expr`, where a sum or a product is
+/// copied every time a term is added to it, so build long ones at once with `RcExpr::sum` and
+/// `RcExpr::product`. Convert it into an `Expr` to simplify it or to do anything else.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, RcExpr, Symbol};
+///
+/// let x = Symbol::new("x");
+/// let mut e = RcExpr::from(x);
+/// for _ in 0..100 {
+///     e = &e * &e + &e;
+/// }
+/// // There are 2 ^ 100 leaves in the tree, but only 201 distinct nodes.
+/// assert_eq!(e.node_count(), 201);
+///
+/// let f = RcExpr::from(&((x + 1) * (x + 1)));
+/// assert_eq!(f, (RcExpr::from(x) + RcExpr::from(1)) * (RcExpr::from(x) + RcExpr::from(1)));
+/// assert_eq!(f.to_expr(), (x + 1) * (x + 1));
+/// ```
+#[derive(Clone)]
+pub struct RcExpr(Rc<Inner>);
+
+/// The interned nodes by their hashes.
+#[derive(Default)]
+struct Table {
+    nodes: HashMap<u64, Vec<Weak<Inner>>>,
+    /// The number of entries, some of which may have been dropped.
+    len: usize,
+    /// The number of entries after the last purge.
+    live: usize,
+}
+
+thread_local! {
+    static TABLE: RefCell<Table> = RefCell::new(Table::default());
+}
+
+impl RcExpr {
+    /// Intern a node.
+    pub fn new(node: RcNode) -> RcExpr {
+        let mut hasher = DefaultHasher::new();
+        node.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        TABLE.with(|table| {
+            let mut table = table.borrow_mut();
+            let found = table.nodes.get(&hash).and_then(|bucket| {
+                bucket.iter().filter_map(Weak::upgrade).find(|e| e.node == node)
+            });
+            if let Some(e) = found {
+                return RcExpr(e);
+            }
+
+            let e = Rc::new(Inner { node, hash });
+            table.nodes.entry(hash).or_insert_with(Vec::new).push(Rc::downgrade(&e));
+            table.len += 1;
+            table.maybe_purge();
+            RcExpr(e)
+        })
+    }
+
+    /// The root node.
+    pub fn node(&self) -> &RcNode {
+        &self.0.node
+    }
+
+    /// The number of distinct nodes, which are shared.
+    pub fn node_count(&self) -> usize {
+        fn visit(e: &RcExpr, seen: &mut HashSet<RcExpr>) {
+            if !seen.insert(e.clone()) {
+                return;
+            }
+            match *e.node() {
+                Neg(ref e) => visit(e, seen),
+                Sum(ref args) | Product(ref args) | Apply(_, ref args) => {
+                    for arg in args {
+                        visit(arg, seen);
+                    }
+                }
+                Ratio(ref a, ref b) | Pow(ref a, ref b) => {
+                    visit(a, seen);
+                    visit(b, seen);
+                }
+                _ => {}
+            }
+        }
+        let mut seen = HashSet::new();
+        visit(self, &mut seen);
+        seen.len()
+    }
+
+    /// Convert into an owned tree, where shared subexpressions are copied.
+    pub fn to_expr(&self) -> Expr {
+        let all = |args: &[RcExpr]| args.iter().map(RcExpr::to_expr).collect();
+        match *self.node() {
+            Integer(ref i) => Expr::Integer(i.clone()),
+            Rational(ref r) => Expr::Rational(r.clone()),
+            Sym(s) => Expr::Sym(s),
+            Approx(f) => Expr::Approx(f),
+            Const(c) => Expr::Const(c),
+            Neg(ref e) => Expr::Neg(Box::new(e.to_expr())),
+            Sum(ref args) => Expr::Sum(all(args)),
+            Product(ref args) => Expr::Product(all(args)),
+            Ratio(ref a, ref b) => Expr::Ratio(Box::new(a.to_expr()), Box::new(b.to_expr())),
+            Pow(ref a, ref b) => Expr::Pow(Box::new(a.to_expr()), Box::new(b.to_expr())),
+            Apply(ref f, ref args) => Expr::Apply(f.clone(), all(args)),
+            Undefined => Expr::Undefined,
+        }
+    }
+
+    /// Construct the sum of `args`, as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, RcExpr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let terms = (1..=3).map(|k| RcExpr::from(x).pow(k)).collect();
+    /// assert_eq!(RcExpr::sum(terms).to_expr(), Expr::Sum(vec![x.pow(1), x.pow(2), x.pow(3)]));
+    /// ```
+    pub fn sum(args: Vec<RcExpr>) -> RcExpr {
+        RcExpr::new(Sum(args))
+    }
+
+    /// Construct the product of `args`, as they are.
+    pub fn product(args: Vec<RcExpr>) -> RcExpr {
+        RcExpr::new(Product(args))
+    }
+
+    /// Construct a power.
+    pub fn pow<E: Into<RcExpr>>(&self, e: E) -> RcExpr {
+        RcExpr::new(Pow(self.clone(), e.into()))
+    }
+
+    fn to_rational(&self) -> Option<BigRational> {
+        match *self.node() {
+            Integer(ref i) => Some(BigRational::from_integer(i.clone())),
+            Rational(ref r) => Some(r.clone()),
+            _ => None,
+        }
+    }
+
+    fn from_big_rational(r: BigRational) -> RcExpr {
+        RcExpr::new(if r.is_integer() { Integer(r.to_integer()) } else { Rational(r) })
+    }
+}
+
+impl Table {
+    /// Remove the nodes which have been dropped, when there are twice as many as after the last
+    /// time.
+    fn maybe_purge(&mut self) {
+        if self.len < cmp::max(MIN_PURGE_SIZE, 2 * self.live) {
+            return;
+        }
+        self.nodes.retain(|_, bucket| {
+            bucket.retain(|e| e.upgrade().is_some());
+            !bucket.is_empty()
+        });
+        self.len = self.nodes.values().map(Vec::len).sum();
+        self.live = self.len;
+    }
+}
+
+impl PartialEq for RcExpr {
+    fn eq(&self, other: &RcExpr) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RcExpr {}
+
+impl Hash for RcExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+
+impl Debug for RcExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.node().fmt(f)
+    }
+}
+
+impl Display for RcExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.to_expr(), f)
+    }
+}
+
+impl<'a> From<&'a Expr> for RcExpr {
+    fn from(e: &Expr) -> RcExpr {
+        let all = |args: &[Expr]| args.iter().map(RcExpr::from).collect();
+        RcExpr::new(match *e {
+            Expr::Integer(ref i) => Integer(i.clone()),
+            Expr::Rational(ref r) => Rational(r.clone()),
+            Expr::Sym(s) => Sym(s),
+            Expr::Approx(f) => Approx(f),
+            Expr::Const(c) => Const(c),
+            Expr::Neg(ref e) => Neg(RcExpr::from(&**e)),
+            Expr::Sum(ref args) => Sum(all(args)),
+            Expr::Product(ref args) => Product(all(args)),
+            Expr::Ratio(ref a, ref b) => Ratio(RcExpr::from(&**a), RcExpr::from(&**b)),
+            Expr::Pow(ref a, ref b) => Pow(RcExpr::from(&**a), RcExpr::from(&**b)),
+            Expr::Apply(ref f, ref args) => Apply(f.clone(), all(args)),
+            Expr::Undefined => Undefined,
+        })
+    }
+}
+
+impl From<Expr> for RcExpr {
+    fn from(e: Expr) -> RcExpr {
+        RcExpr::from(&e)
+    }
+}
+
+impl From<Symbol> for RcExpr {
+    fn from(s: Symbol) -> RcExpr {
+        RcExpr::new(Sym(s))
+    }
+}
+
+impl From<i32> for RcExpr {
+    fn from(i: i32) -> RcExpr {
+        RcExpr::new(Integer(i.into()))
+    }
+}
+
+impl<'a> From<&'a RcExpr> for Expr {
+    fn from(e: &RcExpr) -> Expr {
+        e.to_expr()
+    }
+}
+
+// The operators fold exact numbers and flatten sums and products on the left like the ones of
+// `Expr`, without copying the operands.
+
+fn add(lhs: &RcExpr, rhs: &RcExpr) -> RcExpr {
+    if let (Some(a), Some(b)) = (lhs.to_rational(), rhs.to_rational()) {
+        return RcExpr::from_big_rational(a + b);
+    }
+    RcExpr::new(Sum(match *lhs.node() {
+        Sum(ref args) => args.iter().chain(iter::once(rhs)).cloned().collect(),
+        _ => vec![lhs.clone(), rhs.clone()],
+    }))
+}
+
+fn sub(lhs: &RcExpr, rhs: &RcExpr) -> RcExpr {
+    if let (Some(a), Some(b)) = (lhs.to_rational(), rhs.to_rational()) {
+        return RcExpr::from_big_rational(a - b);
+    }
+    add(lhs, &neg(rhs))
+}
+
+fn mul(lhs: &RcExpr, rhs: &RcExpr) -> RcExpr {
+    if let (Some(a), Some(b)) = (lhs.to_rational(), rhs.to_rational()) {
+        return RcExpr::from_big_rational(a * b);
+    }
+    RcExpr::new(Product(match *lhs.node() {
+        Product(ref args) => args.iter().chain(iter::once(rhs)).cloned().collect(),
+        _ => vec![lhs.clone(), rhs.clone()],
+    }))
+}
+
+fn div(lhs: &RcExpr, rhs: &RcExpr) -> RcExpr {
+    if let (Some(a), Some(b)) = (lhs.to_rational(), rhs.to_rational()) {
+        if b.is_zero() {
+            return RcExpr::new(Undefined);
+        }
+        return RcExpr::from_big_rational(a / b);
+    }
+    RcExpr::new(Ratio(lhs.clone(), rhs.clone()))
+}
+
+fn neg(e: &RcExpr) -> RcExpr {
+    match e.to_rational() {
+        Some(r) => RcExpr::from_big_rational(-r),
+        None => RcExpr::new(Neg(e.clone())),
+    }
+}
+
+/// Implement an operator for all combinations of owned and borrowed operands.
+macro_rules! rc_op_impls {
+    ($($trait_:ident, $method:ident;)*) => {$(
+        impl<'a, 'b> $trait_<&'b RcExpr> for &'a RcExpr {
+            type Output = RcExpr;
+
+            fn $method(self, rhs: &RcExpr) -> RcExpr {
+                $method(self, rhs)
+            }
+        }
+
+        impl<'a> $trait_<&'a RcExpr> for RcExpr {
+            type Output = RcExpr;
+
+            fn $method(self, rhs: &RcExpr) -> RcExpr {
+                $method(&self, rhs)
+            }
+        }
+
+        impl<'a> $trait_<RcExpr> for &'a RcExpr {
+            type Output = RcExpr;
+
+            fn $method(self, rhs: RcExpr) -> RcExpr {
+                $method(self, &rhs)
+            }
+        }
+
+        impl $trait_ for RcExpr {
+            type Output = RcExpr;
+
+            fn $method(self, rhs: RcExpr) -> RcExpr {
+                $method(&self, &rhs)
+            }
+        }
+    )*};
+}
+
+rc_op_impls! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
+
+impl<'a> Neg for &'a RcExpr {
+    type Output = RcExpr;
+
+    fn neg(self) -> RcExpr {
+        neg(self)
+    }
+}
+
+impl Neg for RcExpr {
+    type Output = RcExpr;
+
+    fn neg(self) -> RcExpr {
+        neg(&self)
+    }
+}
@@ -0,0 +1,377 @@
+use std::cmp;
+use std::fmt::{self, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+use num::{BigRational, Zero};
+
+use super::{Constant, Expr, Symbol};
+use super::integrate::has_infinity;
+use super::puiseux::{Expander, Puiseux};
+use self::Expr::*;
+
+/// The number of times an expansion is retried at a higher order when terms cancel.
+const MAX_RETRIES: usize = 4;
+
+/// The highest order up to which an expression is expanded to find its leading term.
+const MAX_LEADING_ORDER: i64 = 64;
+
+/// The order term `O(u ^ n)` of a series in the local variable `u`, which stands for any
+/// function bounded by a multiple of `u ^ n` near the point.
+///
+/// The local variable is `x - a` around a finite point `a`, `1 / x` around `oo`, and `-1 / x`
+/// around `-oo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    var: Symbol,
+    point: Expr,
+    exponent: BigRational,
+}
+
+impl Order {
+    /// Construct the order term `O(u ^ exponent)` as `var` goes to `point`.
+    pub fn new<P: Into<Expr>>(var: Symbol, point: P, exponent: BigRational) -> Order {
+        Order { var, point: point.into().simplify(), exponent }
+    }
+
+    /// The variable.
+    pub fn var(&self) -> Symbol {
+        self.var
+    }
+
+    /// The point of expansion.
+    pub fn point(&self) -> &Expr {
+        &self.point
+    }
+
+    /// The exponent of the local variable.
+    pub fn exponent(&self) -> &BigRational {
+        &self.exponent
+    }
+
+    /// Multiply by `u ^ e`.
+    fn shift(self, e: &BigRational) -> Order {
+        Order { exponent: self.exponent + e, ..self }
+    }
+}
+
+impl Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "O({})", local_power(self.var, &self.point, &self.exponent))
+    }
+}
+
+// The operators panic if the operands have different variables or points.
+
+impl Add for Order {
+    type Output = Order;
+
+    fn add(self, rhs: Order) -> Order {
+        assert_same(self.var, &self.point, rhs.var, &rhs.point);
+        Order { exponent: cmp::min(self.exponent, rhs.exponent), ..self }
+    }
+}
+
+impl Add<Series> for Order {
+    type Output = Series;
+
+    fn add(self, rhs: Series) -> Series {
+        rhs + self
+    }
+}
+
+impl Mul<Series> for Order {
+    type Output = Order;
+
+    fn mul(self, rhs: Series) -> Order {
+        rhs * self
+    }
+}
+
+/// Multiplies by the leading term of the expression's series, so `O(x ^ n) * x ^ k` is
+/// `O(x ^ (n + k))` around `0`. Panics if the expression is zero or has no series around the
+/// point.
+impl Mul<Expr> for Order {
+    type Output = Order;
+
+    fn mul(self, rhs: Expr) -> Order {
+        let mut n = 1;
+        while n <= MAX_LEADING_ORDER {
+            // Expanding to a low order may fail before the leading term is reached.
+            if let Some(s) = rhs.series(self.var, self.point.clone(), n) {
+                if !s.terms().is_empty() {
+                    return s * self;
+                }
+            }
+            n *= 2;
+        }
+        panic!("the factor has no leading term around the point");
+    }
+}
+
+/// A truncated series `c_1 u ^ e_1 + ... + c_n u ^ e_n + O(u ^ n)` in the local variable `u` of
+/// a point, returned by `Expr::series`.
+///
+/// The exponents are rational and may be negative, so Taylor, Laurent and Puiseux series are all
+/// represented. The coefficients don't depend on the variable except through logarithms, like in
+/// `x ^ x = 1 + x ln(x) + O(x ^ 2)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    var: Symbol,
+    point: Expr,
+    series: Puiseux,
+}
+
+impl Series {
+    /// Construct a series from its known terms, which must be sorted by the exponent and have
+    /// expanded coefficients.
+    pub(crate) fn from_terms(
+        var: Symbol,
+        point: Expr,
+        terms: Vec<(BigRational, Expr)>,
+        order: BigRational,
+    ) -> Series {
+        let terms = terms.into_iter().filter(|t| t.0 < order && t.1 != Expr::integer(0));
+        Series { var, point, series: Puiseux { terms: terms.collect(), order } }
+    }
+
+    /// The variable.
+    pub fn var(&self) -> Symbol {
+        self.var
+    }
+
+    /// The point of expansion.
+    pub fn point(&self) -> &Expr {
+        &self.point
+    }
+
+    /// The known terms as pairs of an exponent and a nonzero coefficient, in increasing order of
+    /// the exponents.
+    pub fn terms(&self) -> &[(BigRational, Expr)] {
+        &self.series.terms
+    }
+
+    /// The coefficient of `u ^ e`, or `None` if it's beyond the order term.
+    pub fn coeff(&self, e: &BigRational) -> Option<Expr> {
+        if *e >= self.series.order {
+            return None;
+        }
+        let term = self.series.terms.iter().find(|t| t.0 == *e);
+        Some(term.map_or_else(|| Expr::integer(0), |t| t.1.clone()))
+    }
+
+    /// The order term.
+    pub fn order(&self) -> Order {
+        Order {
+            var: self.var,
+            point: self.point.clone(),
+            exponent: self.series.order.clone(),
+        }
+    }
+
+    /// The sum of the known terms, without the order term.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = Expr::exp(x).series(x, 0, 3).unwrap();
+    /// assert_eq!(s.to_expr(), (1i32 + x + x.pow(2) / 2).simplify());
+    /// ```
+    pub fn to_expr(&self) -> Expr {
+        let terms = self.series.terms.iter().map(|&(ref e, ref c)| {
+            c.clone() * local_power(self.var, &self.point, e)
+        });
+        Sum(terms.collect()).simplify()
+    }
+
+    /// Raise to a constant power, or return `None` if no term is known, or the power isn't a
+    /// number and the leading exponent isn't zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = (x + x.pow(2)).series(x, 0, 3).unwrap();
+    /// let inverse = s.pow(-1).unwrap();
+    /// assert_eq!(inverse.to_expr(), (x.pow(-1) - 1i32).simplify());
+    /// assert_eq!(inverse.order().to_string(), "O(x)");
+    /// ```
+    pub fn pow<E: Into<Expr>>(&self, p: E) -> Option<Series> {
+        let series = self.series.pow(&p.into().simplify())?;
+        Some(Series { series, ..self.clone() })
+    }
+}
+
+impl Display for Series {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.series.terms.is_empty() {
+            write!(f, "{} + ", self.to_expr())?;
+        }
+        write!(f, "{}", self.order())
+    }
+}
+
+impl<'a, 'b> Add<&'b Series> for &'a Series {
+    type Output = Series;
+
+    fn add(self, rhs: &Series) -> Series {
+        assert_same(self.var, &self.point, rhs.var, &rhs.point);
+        Series { series: self.series.add(&rhs.series), ..self.clone() }
+    }
+}
+
+impl Add for Series {
+    type Output = Series;
+
+    fn add(self, rhs: Series) -> Series {
+        &self + &rhs
+    }
+}
+
+impl Add<Order> for Series {
+    type Output = Series;
+
+    fn add(self, rhs: Order) -> Series {
+        assert_same(self.var, &self.point, rhs.var, &rhs.point);
+        Series { series: self.series.truncate(&rhs.exponent), ..self }
+    }
+}
+
+impl Mul<Order> for Series {
+    type Output = Order;
+
+    fn mul(self, rhs: Order) -> Order {
+        assert_same(self.var, &self.point, rhs.var, &rhs.point);
+        rhs.shift(&self.series.valuation())
+    }
+}
+
+impl<'a, 'b> Sub<&'b Series> for &'a Series {
+    type Output = Series;
+
+    fn sub(self, rhs: &Series) -> Series {
+        self + &-rhs
+    }
+}
+
+impl Sub for Series {
+    type Output = Series;
+
+    fn sub(self, rhs: Series) -> Series {
+        &self - &rhs
+    }
+}
+
+impl<'a, 'b> Mul<&'b Series> for &'a Series {
+    type Output = Series;
+
+    fn mul(self, rhs: &Series) -> Series {
+        assert_same(self.var, &self.point, rhs.var, &rhs.point);
+        Series { series: self.series.mul(&rhs.series), ..self.clone() }
+    }
+}
+
+impl Mul for Series {
+    type Output = Series;
+
+    fn mul(self, rhs: Series) -> Series {
+        &self * &rhs
+    }
+}
+
+impl<'a> Neg for &'a Series {
+    type Output = Series;
+
+    fn neg(self) -> Series {
+        let series = self.series.scale(&Expr::integer(-1), &BigRational::zero());
+        Series { series, ..self.clone() }
+    }
+}
+
+impl Neg for Series {
+    type Output = Series;
+
+    fn neg(self) -> Series {
+        -&self
+    }
+}
+
+/// Series expansion.
+impl Expr {
+    /// Expand into a series in `x` around `point`, which may be an infinity, up to the order term
+    /// `O(u ^ n)` in the local variable `u`. See `Series` and `Order`.
+    ///
+    /// Around a finite point, the series holds to the right of it. Fewer terms are known if too
+    /// many cancel, and then the order term is lower. Returns `None` if the expression has no
+    /// such series, like `exp(1 / x)` around `0`, or it isn't found.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let s = Expr::sin(x).series(x, 0, 6).unwrap();
+    /// assert_eq!(s.to_expr(), (x - x.pow(3) / 6 + x.pow(5) / 120).simplify());
+    /// assert_eq!(s.order().to_string(), "O(x ^ 6)");
+    ///
+    /// // Laurent series
+    /// let s = (Expr::cos(x) / x).series(x, 0, 2).unwrap();
+    /// assert_eq!(s.to_expr(), (x.pow(-1) - x / 2).simplify());
+    ///
+    /// // Puiseux series
+    /// let s = (x + x.pow(2)).pow(Expr::rational(1, 2)).series(x, 0, 2).unwrap();
+    /// assert_eq!(s.terms().len(), 2);
+    ///
+    /// // Around infinity in powers of `1 / x`
+    /// let s = (x / (x + 1)).series(x, Expr::infinity(), 3).unwrap();
+    /// assert_eq!(s.to_expr(), (1i32 - x.pow(-1) + x.pow(-2)).simplify());
+    /// ```
+    pub fn series<P: Into<Expr>>(&self, x: Symbol, point: P, n: i64) -> Option<Series> {
+        let point = point.into().simplify();
+        let t = Symbol::dummy("t");
+        let log = Symbol::dummy("t_log");
+        let value = match point {
+            Const(Constant::Infinity) => Expr::from(t).pow(-1),
+            Const(Constant::NegInfinity) => -Expr::from(t).pow(-1),
+            ref c if has_infinity(c) || *c == Undefined => return None,
+            ref c => c.clone() + t,
+        };
+        let e = self.simplify().subs(x, value).simplify();
+
+        let expander = Expander::new(t, log);
+        let n = BigRational::from_integer(n.into());
+        let mut order = n.clone();
+        let mut s = expander.expand(&e, &order)?;
+        for _ in 0..MAX_RETRIES {
+            if s.order >= n {
+                break;
+            }
+            order = &order + (&n - &s.order);
+            s = expander.expand(&e, &order)?;
+        }
+        let s = s.truncate(&n);
+
+        let ln = Expr::ln(local_var(x, &point));
+        let terms = s.terms.into_iter().map(|(e, c)| (e, c.subs(log, ln.clone()).expand()));
+        let series = Puiseux { terms: terms.collect(), order: s.order };
+        Some(Series { var: x, point, series })
+    }
+}
+
+/// The local variable of `x` around a point.
+fn local_var(x: Symbol, point: &Expr) -> Expr {
+    match *point {
+        Const(Constant::Infinity) => Expr::from(x).pow(-1),
+        Const(Constant::NegInfinity) => -Expr::from(x).pow(-1),
+        ref c => Expr::from(x) - c.clone(),
+    }
+}
+
+fn local_power(x: Symbol, point: &Expr, e: &BigRational) -> Expr {
+    local_var(x, point).pow(Expr::from(e.clone())).simplify()
+}
+
+fn assert_same(x: Symbol, a: &Expr, y: Symbol, b: &Expr) {
+    assert!(x == y && a == b, "series in different variables or around different points");
+}
@@ -0,0 +1,589 @@
+use std::cmp::Ordering;
+use std::slice;
+use num::{BigInt, BigRational, Zero, One, Signed, ToPrimitive};
+use num::Integer as _;
+
+use super::{Constant, Expr, Func};
+use self::Expr::*;
+
+/// Simplification.
+impl Expr {
+    /// Bring the expression into its canonical form.
+    ///
+    /// Two expressions that are structurally equal math compare equal after simplification. The
+    /// canonical form is defined as follows:
+    ///
+    /// - `Neg` and `Ratio` never appear; `- a` becomes `-1 * a` and `a / b` becomes `a * b ^ -1`.
+    /// - Nested `Sum`s and `Product`s are flattened, and contain at least two arguments.
+    /// - Numeric constants are folded, and appear as the first argument of a `Sum` or `Product`.
+    ///   Exact numbers stay exact, so `1 / 3` becomes a `Rational`, and exact roots like `4 ^ (1/2)`
+    ///   are evaluated.
+    /// - Like terms of a `Sum` are collected (`x + 2 * x` becomes `3 * x`), and like bases of a
+    ///   `Product` are collected (`x * x ^ y` becomes `x ^ (1 + y)`).
+    /// - Arguments are sorted, so `1 + x` and `x + 1` are the same.
+    /// - Trivial powers like `x ^ 0`, `x ^ 1` and `1 ^ x` are removed, and integer powers of
+    ///   powers and products are distributed.
+    /// - `sqrt(x)` becomes `x ^ (1/2)`, and `log(b, x)` becomes `ln(x) / ln(b)`. Functions are
+    ///   evaluated at approximate values and at simple exact values like `sin(0)`, and a negative
+    ///   sign is taken out of odd and even functions, so `sin(-x)` becomes `-1 * sin(x)`.
+    /// - Finite numbers are absorbed by infinities, so `1 + oo` becomes `oo` and `-2 * oo` becomes
+    ///   `-oo`, and integer powers of `i` are evaluated. Indeterminate forms like `oo - oo` and
+    ///   `0 * oo` are `Undefined`.
+    /// - Anything containing `Undefined` is `Undefined`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// assert_eq!((x + x).simplify(), (2i32 * x).simplify());
+    /// assert_eq!((x + 1).simplify(), (1i32 + x).simplify());
+    /// assert_eq!((x - x).simplify(), Expr::integer(0));
+    /// assert_eq!((2i32 * Expr::integer(3)).simplify(), Expr::integer(6));
+    /// ```
+    pub fn simplify(&self) -> Expr {
+        match *self {
+            Integer(_) | Sym(_) | Approx(_) | Const(_) | Undefined => self.clone(),
+            Rational(ref r) => Expr::from_big_rational(r.clone()),
+            Neg(ref e) => product(vec![Expr::integer(-1), e.simplify()]),
+            Sum(ref args) => sum(args.iter().map(Expr::simplify).collect()),
+            Product(ref args) => product(args.iter().map(Expr::simplify).collect()),
+            Ratio(ref n, ref d) => product(vec![n.simplify(), power(d.simplify(), Expr::integer(-1))]),
+            Pow(ref b, ref e) => power(b.simplify(), e.simplify()),
+            Apply(ref f, ref args) => apply(f.clone(), args.iter().map(Expr::simplify).collect()),
+        }
+    }
+}
+
+// Canonical constructors. The arguments are assumed to be canonical already.
+
+/// Construct the canonical sum of `args`.
+pub(crate) fn sum(args: Vec<Expr>) -> Expr {
+    let mut constant = Expr::integer(0);
+    let mut infinity = None;
+    let mut terms = vec![];
+    for arg in flatten(args, |e| if let Sum(args) = e { Ok(args) } else { Err(e) }) {
+        match arg {
+            Undefined => return Undefined,
+            ref n if n.is_number() => constant = num_add(&constant, n),
+            Const(c) if c.is_infinite() => match add_infinities(infinity, c) {
+                Some(c) => infinity = Some(c),
+                None => return Undefined,
+            },
+            term => terms.push(split_coeff(term)),
+        }
+    }
+    // Finite numbers are absorbed by the infinity.
+    if let Some(c) = infinity {
+        constant = Expr::integer(0);
+        terms.push((Expr::integer(1), Const(c)));
+    }
+
+    // Like terms are next to each other after sorting.
+    terms.sort_by(|a, b| order(&a.1, &b.1));
+    let mut res: Vec<Expr> = vec![];
+    let mut terms = terms.into_iter().peekable();
+    while let Some((mut coeff, rest)) = terms.next() {
+        while terms.peek().map_or(false, |t| t.1 == rest) {
+            coeff = num_add(&coeff, &terms.next().unwrap().0);
+        }
+        if !is_zero(&coeff) {
+            // The coefficient is absorbed by an infinite factor.
+            res.push(if has_infinite_factor(&rest) {
+                product(vec![coeff, rest])
+            } else {
+                with_coeff(coeff, rest)
+            });
+        }
+    }
+
+    if !is_zero(&constant) {
+        res.insert(0, constant);
+    }
+    match res.len() {
+        0 => Expr::integer(0),
+        1 => res.pop().unwrap(),
+        _ => Sum(res),
+    }
+}
+
+/// Construct the canonical product of `args`.
+pub(crate) fn product(args: Vec<Expr>) -> Expr {
+    let mut coeff = Expr::integer(1);
+    let mut infinity = None;
+    let mut powers = vec![];
+    for arg in flatten(args, |e| if let Product(args) = e { Ok(args) } else { Err(e) }) {
+        match arg {
+            Undefined => return Undefined,
+            ref n if n.is_number() => coeff = num_mul(&coeff, n),
+            Const(c) if c.is_infinite() => infinity = Some(mul_infinities(infinity, c)),
+            Pow(b, e) => powers.push((*b, *e)),
+            e => powers.push((e, Expr::integer(1))),
+        }
+    }
+    // The sign of the coefficient goes into the infinity.
+    if let Some(c) = infinity {
+        if is_zero(&coeff) {
+            return Undefined;
+        }
+        let c = if is_negative_term(&coeff) { negate_infinity(c) } else { c };
+        coeff = Expr::integer(1);
+        powers.push((Const(c), Expr::integer(1)));
+    }
+    if is_zero(&coeff) {
+        return Expr::integer(0);
+    }
+
+    // Like bases are next to each other after sorting.
+    powers.sort_by(|a, b| order(&a.0, &b.0));
+    let mut factors = vec![];
+    let mut powers = powers.into_iter().peekable();
+    while let Some((base, exp)) = powers.next() {
+        let mut exps = vec![exp];
+        while powers.peek().map_or(false, |p| p.0 == base) {
+            exps.push(powers.next().unwrap().1);
+        }
+        let exp = if exps.len() == 1 { exps.pop().unwrap() } else { sum(exps) };
+        factors.push(power(base, exp));
+    }
+
+    // Collecting may bring up new numbers or products, e.g. `2 ^ x * 2 ^ (1 - x)`.
+    if factors.iter().any(|f| f.is_number() || f == &Undefined || is_product(f)) {
+        factors.push(coeff);
+        return product(factors);
+    }
+
+    factors.sort_by(order);
+    if !is_one(&coeff) {
+        factors.insert(0, coeff);
+    }
+    match factors.len() {
+        0 => Expr::integer(1),
+        1 => factors.pop().unwrap(),
+        _ => Product(factors),
+    }
+}
+
+/// Construct the canonical power `base ^ exp`.
+pub(crate) fn power(base: Expr, exp: Expr) -> Expr {
+    if base == Undefined || exp == Undefined {
+        return Undefined;
+    }
+    if is_zero(&exp) {
+        return Expr::integer(1);
+    }
+    if is_one(&base) && has_infinite_factor(&exp) {
+        return Undefined;
+    }
+    if is_one(&exp) || is_one(&base) {
+        return base;
+    }
+    if is_zero(&base) && exp.is_number() {
+        return if is_positive(&exp) { base } else { Undefined };
+    }
+    if base.is_number() && exp.is_number() {
+        if let Some(res) = num_pow(&base, &exp) {
+            return res;
+        }
+    }
+    if let Const(c) = base {
+        if let Some(res) = const_pow(c, &exp) {
+            return res;
+        }
+    }
+
+    match (base, exp) {
+        // (x ^ a) ^ n = x ^ (a * n) for integer n.
+        (Pow(b, e), Integer(n)) => power(*b, product(vec![*e, Integer(n)])),
+        // (x * y) ^ n = x ^ n * y ^ n for integer n.
+        (Product(args), Integer(n)) => product(
+            args.into_iter()
+                .map(|arg| power(arg, Integer(n.clone())))
+                .collect(),
+        ),
+        (base, exp) => Pow(Box::new(base), Box::new(exp)),
+    }
+}
+
+/// Construct the canonical application of `f` to `args`.
+pub(crate) fn apply(f: Func, mut args: Vec<Expr>) -> Expr {
+    // A function applied to the wrong number of arguments has no value either.
+    if args.contains(&Undefined) || !f.accepts(args.len()) {
+        return Undefined;
+    }
+    match f {
+        // Nothing is known about undefined functions.
+        Func::Undef(..) => return Apply(f, args),
+        Func::Sqrt => return power(args.pop().unwrap(), Expr::rational(1, 2)),
+        Func::Log => {
+            let x = args.pop().unwrap();
+            let base = args.pop().unwrap();
+            return product(vec![
+                apply(Func::Ln, vec![x]),
+                power(apply(Func::Ln, vec![base]), Expr::integer(-1)),
+            ]);
+        }
+        _ => {}
+    }
+
+    let x = args.pop().unwrap();
+    if let Approx(_) = x {
+        let res = f.eval_f64(&[to_f64(&x)]);
+        if !res.is_nan() {
+            return Approx(res);
+        }
+    }
+    if is_zero(&x) {
+        match f {
+            Func::Sin | Func::Tan | Func::Asin | Func::Atan | Func::Sinh | Func::Tanh => {
+                return Expr::integer(0)
+            }
+            Func::Cos | Func::Cosh | Func::Exp => return Expr::integer(1),
+            _ => {}
+        }
+    }
+    if is_one(&x) {
+        match f {
+            Func::Acos | Func::Ln => return Expr::integer(0),
+            Func::Exp => return Const(Constant::E),
+            _ => {}
+        }
+    }
+    if let Const(c) = x {
+        match (&f, c) {
+            (&Func::Sin, Constant::Pi) | (&Func::Tan, Constant::Pi) => return Expr::integer(0),
+            (&Func::Cos, Constant::Pi) => return Expr::integer(-1),
+            (&Func::Ln, Constant::E) => return Expr::integer(1),
+            (&Func::Exp, Constant::Infinity) | (&Func::Ln, Constant::Infinity) => return x,
+            (&Func::Exp, Constant::NegInfinity) => return Expr::integer(0),
+            _ => {}
+        }
+    }
+
+    match (f, x) {
+        (Func::Abs, ref n) if to_rational(n).is_some() => {
+            Expr::from_big_rational(to_rational(n).unwrap().abs())
+        }
+        (Func::Abs, Apply(Func::Abs, args)) => Apply(Func::Abs, args),
+        (Func::Exp, Apply(Func::Ln, mut args)) => args.pop().unwrap(),
+        (f, x) => {
+            if is_negative_term(&x) && (f.is_odd() || f.is_even()) {
+                let is_odd = f.is_odd();
+                let fx = apply(f, vec![product(vec![Expr::integer(-1), x])]);
+                if is_odd { product(vec![Expr::integer(-1), fx]) } else { fx }
+            } else {
+                Apply(f, vec![x])
+            }
+        }
+    }
+}
+
+/// Add two infinities, or return `None` if the sum is indeterminate.
+fn add_infinities(a: Option<Constant>, b: Constant) -> Option<Constant> {
+    match a {
+        None => Some(b),
+        Some(a) if a == b && a != Constant::ComplexInfinity => Some(a),
+        _ => None,
+    }
+}
+
+/// Multiply two infinities.
+fn mul_infinities(a: Option<Constant>, b: Constant) -> Constant {
+    use self::Constant::*;
+
+    match (a, b) {
+        (None, b) => b,
+        (Some(ComplexInfinity), _) | (_, ComplexInfinity) => ComplexInfinity,
+        (Some(a), b) => if a == b { Infinity } else { NegInfinity },
+    }
+}
+
+fn negate_infinity(c: Constant) -> Constant {
+    match c {
+        Constant::Infinity => Constant::NegInfinity,
+        Constant::NegInfinity => Constant::Infinity,
+        c => c,
+    }
+}
+
+/// Check if a canonical expression is an infinity or a product with an infinite factor.
+fn has_infinite_factor(e: &Expr) -> bool {
+    match *e {
+        Const(c) => c.is_infinite(),
+        Product(ref args) => args.iter().any(has_infinite_factor),
+        _ => false,
+    }
+}
+
+/// Evaluate a power of a constant, or return `None` if it's kept as a power.
+fn const_pow(c: Constant, exp: &Expr) -> Option<Expr> {
+    match (c, exp) {
+        // i ^ 2 = -1
+        (Constant::ImaginaryUnit, &Integer(ref n)) => {
+            let i = || Const(Constant::ImaginaryUnit);
+            Some(match n.mod_floor(&BigInt::from(4)).to_u8() {
+                Some(0) => Expr::integer(1),
+                Some(1) => i(),
+                Some(2) => Expr::integer(-1),
+                _ => product(vec![Expr::integer(-1), i()]),
+            })
+        }
+        (c, exp) if c.is_infinite() && exp.is_number() => Some(if !is_positive(exp) {
+            // The exponent isn't zero here.
+            Expr::integer(0)
+        } else if c != Constant::NegInfinity {
+            Const(c)
+        } else {
+            match *exp {
+                Integer(ref n) if n.is_even() => Const(Constant::Infinity),
+                Integer(_) => Const(Constant::NegInfinity),
+                _ => return None,
+            }
+        }),
+        _ => None,
+    }
+}
+
+/// Check if a canonical expression has a negative numeric coefficient.
+pub(crate) fn is_negative_term(e: &Expr) -> bool {
+    match *e {
+        Product(ref args) => is_negative_term(&args[0]),
+        ref n if n.is_number() => !is_positive(n) && !is_zero(n),
+        _ => false,
+    }
+}
+
+/// Flatten the nested arguments which `unwrap` accepts.
+fn flatten<F>(args: Vec<Expr>, unwrap: F) -> Vec<Expr>
+where
+    F: Fn(Expr) -> Result<Vec<Expr>, Expr> + Copy,
+{
+    let mut res = vec![];
+    for arg in args {
+        match unwrap(arg) {
+            Ok(inner) => res.extend(flatten(inner, unwrap)),
+            Err(arg) => res.push(arg),
+        }
+    }
+    res
+}
+
+/// Split a canonical term into its numeric coefficient and the rest.
+fn split_coeff(term: Expr) -> (Expr, Expr) {
+    match term {
+        Product(mut args) => {
+            if args[0].is_number() {
+                let coeff = args.remove(0);
+                let rest = if args.len() == 1 { args.pop().unwrap() } else { Product(args) };
+                (coeff, rest)
+            } else {
+                (Expr::integer(1), Product(args))
+            }
+        }
+        term => (Expr::integer(1), term),
+    }
+}
+
+/// The inverse of `split_coeff`.
+fn with_coeff(coeff: Expr, rest: Expr) -> Expr {
+    if is_one(&coeff) {
+        return rest;
+    }
+    match rest {
+        Product(mut args) => {
+            args.insert(0, coeff);
+            Product(args)
+        }
+        rest => Product(vec![coeff, rest]),
+    }
+}
+
+fn is_product(e: &Expr) -> bool {
+    if let Product(_) = *e { true } else { false }
+}
+
+// Numeric helpers.
+
+fn is_zero(e: &Expr) -> bool {
+    match *e {
+        Integer(ref i) => i.is_zero(),
+        Approx(f) => f == 0.0,
+        _ => false,
+    }
+}
+
+fn is_one(e: &Expr) -> bool {
+    match *e {
+        Integer(ref i) => i.is_one(),
+        _ => false,
+    }
+}
+
+fn is_positive(e: &Expr) -> bool {
+    match *e {
+        Integer(ref i) => i.is_positive(),
+        Rational(ref r) => r.is_positive(),
+        Approx(f) => f > 0.0,
+        _ => false,
+    }
+}
+
+/// The exact value of a number.
+pub(crate) fn to_rational(e: &Expr) -> Option<BigRational> {
+    match *e {
+        Integer(ref i) => Some(BigRational::from_integer(i.clone())),
+        Rational(ref r) => Some(r.clone()),
+        _ => None,
+    }
+}
+
+pub(crate) fn to_f64(e: &Expr) -> f64 {
+    match *e {
+        Integer(ref i) => i.to_f64().unwrap_or(::std::f64::NAN),
+        Rational(ref r) => {
+            let n = r.numer().to_f64().unwrap_or(::std::f64::NAN);
+            let d = r.denom().to_f64().unwrap_or(::std::f64::NAN);
+            n / d
+        }
+        Approx(f) => f,
+        _ => unreachable!("{} is not a number", e),
+    }
+}
+
+fn num_add(a: &Expr, b: &Expr) -> Expr {
+    match (to_rational(a), to_rational(b)) {
+        (Some(a), Some(b)) => Expr::from_big_rational(a + b),
+        _ => Approx(to_f64(a) + to_f64(b)),
+    }
+}
+
+fn num_mul(a: &Expr, b: &Expr) -> Expr {
+    match (to_rational(a), to_rational(b)) {
+        (Some(a), Some(b)) => Expr::from_big_rational(a * b),
+        _ => Approx(to_f64(a) * to_f64(b)),
+    }
+}
+
+/// Evaluate a power of nonzero numbers, or return `None` if it can't be represented exactly.
+fn num_pow(base: &Expr, exp: &Expr) -> Option<Expr> {
+    let (b, e) = match (to_rational(base), to_rational(exp)) {
+        (Some(b), Some(e)) => (b, e),
+        _ => {
+            let res = to_f64(base).powf(to_f64(exp));
+            return if res.is_nan() { None } else { Some(Approx(res)) };
+        }
+    };
+
+    if e.is_integer() {
+        let n = e.to_integer().abs().to_usize()?;
+        let res = num::pow(b, n);
+        return Some(Expr::from_big_rational(if e.is_negative() { res.recip() } else { res }));
+    }
+
+    // b ^ (n + p / q) = b ^ n * b ^ (p / q), where 0 < p < q.
+    let n = e.floor();
+    if !n.is_zero() {
+        let rest = Expr::from_big_rational(&e - &n);
+        let int_part = Expr::from_big_rational(n);
+        return Some(product(vec![power(base.clone(), int_part), power(base.clone(), rest)]));
+    }
+
+    // The root is exact only if both the numerator and denominator are perfect powers.
+    let q = e.denom().to_u32()?;
+    if b.is_negative() {
+        return None;
+    }
+    let root = |i: &BigInt| {
+        let r = i.nth_root(q);
+        if num::pow(r.clone(), q as usize) == *i { Some(r) } else { None }
+    };
+    let r = BigRational::new(root(b.numer())?, root(b.denom())?);
+    Some(power(Expr::from_big_rational(r), Integer(e.numer().clone())))
+}
+
+fn cmp_numbers(a: &Expr, b: &Expr) -> Ordering {
+    match (to_rational(a), to_rational(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => to_f64(a).partial_cmp(&to_f64(b)).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// The order of arguments in canonical `Sum`s and `Product`s.
+///
+/// Numbers come first and are ordered by value, symbols are ordered by name, and compound
+/// expressions are ordered by their last differing arguments, so that `1 + x + x ^ 2` and
+/// `x * y ^ 2` are in order.
+pub(crate) fn order(u: &Expr, v: &Expr) -> Ordering {
+    use std::cmp::Ordering::*;
+
+    match (u.is_number(), v.is_number()) {
+        (true, true) => return cmp_numbers(u, v),
+        (true, false) => return Less,
+        (false, true) => return Greater,
+        (false, false) => {}
+    }
+
+    match (u, v) {
+        (Undefined, Undefined) => Equal,
+        (Undefined, _) => Greater,
+        (_, Undefined) => Less,
+
+        (Sym(a), Sym(b)) => a.to_string().cmp(&b.to_string()),
+        // Constants come before symbols and functions.
+        (Const(a), Const(b)) => a.cmp(b),
+        (Const(_), Sym(_)) | (Const(_), Apply(..)) => Less,
+        (Sym(_), Const(_)) | (Apply(..), Const(_)) => Greater,
+        (Sum(a), Sum(b)) | (Product(a), Product(b)) => order_args(a, b),
+        (Pow(b1, e1), Pow(b2, e2)) => order(b1, b2).then_with(|| order(e1, e2)),
+
+        (Product(a), v) => order_args(a, slice::from_ref(v)),
+        (u, Product(b)) => order_args(slice::from_ref(u), b),
+        (Pow(b, e), v) => order(b, v).then_with(|| order(e, &Expr::integer(1))),
+        (u, Pow(b, e)) => order(u, b).then_with(|| order(&Expr::integer(1), e)),
+        (Sum(a), v) => order_args(a, slice::from_ref(v)),
+        (u, Sum(b)) => order_args(slice::from_ref(u), b),
+
+        // Functions are ordered by name, then by derivatives and then by arguments, and after a
+        // symbol of the same name.
+        (Apply(f, a), Apply(g, b)) => f.name().cmp(&g.name())
+            .then_with(|| derivative_orders(f).cmp(derivative_orders(g)))
+            .then_with(|| {
+                a.iter()
+                    .zip(b)
+                    .map(|(x, y)| order(x, y))
+                    .find(|&o| o != Equal)
+                    .unwrap_or_else(|| a.len().cmp(&b.len()))
+            }),
+        (Apply(f, _), Sym(s)) => f.name().cmp(&s.to_string()).then(Greater),
+        (Sym(s), Apply(f, _)) => s.to_string().cmp(&f.name()).then(Less),
+
+        // Non-canonical forms are ordered last.
+        (Neg(a), Neg(b)) => order(a, b),
+        (Ratio(n1, d1), Ratio(n2, d2)) => {
+            order(n1, n2).then_with(|| order(d1, d2))
+        }
+        (Neg(_), _) | (Ratio(..), _) => Greater,
+        (_, Neg(_)) | (_, Ratio(..)) => Less,
+
+        _ => unreachable!("numbers are compared above"),
+    }
+}
+
+/// Compare argument lists from the last argument.
+fn order_args(a: &[Expr], b: &[Expr]) -> Ordering {
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        match order(x, y) {
+            Ordering::Equal => continue,
+            o => return o,
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// The derivative orders of a function, which are empty for built-in functions.
+fn derivative_orders(f: &Func) -> &[usize] {
+    match *f {
+        Func::Undef(_, ref orders) => orders,
+        _ => &[],
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use super::{Expr, Symbol};
+use self::Expr::*;
+
+/// Substitution.
+impl Expr {
+    /// Replace every occurrence of the symbol `s` with `e`.
+    ///
+    /// The substitution is structural, and the result is not simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!((x + 1).subs(x, y * 2), y * 2 + 1);
+    /// assert_eq!((x + 1).subs(x, 2).simplify(), Expr::integer(3));
+    /// ```
+    pub fn subs<E: Into<Expr>>(&self, s: Symbol, e: E) -> Expr {
+        let mut map = HashMap::new();
+        map.insert(s, e.into());
+        self.subs_all(&map)
+    }
+
+    /// Replace every symbol in `map` with its image, simultaneously.
+    ///
+    /// The substitution is structural, and the result is not simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let mut map = HashMap::new();
+    /// map.insert(x, Expr::from(y));
+    /// map.insert(y, Expr::from(x));
+    /// assert_eq!((x - y).subs_all(&map), y - x);
+    /// ```
+    pub fn subs_all(&self, map: &HashMap<Symbol, Expr>) -> Expr {
+        self.map_leaves(&|e| match *e {
+            Sym(s) => map.get(&s).cloned(),
+            _ => None,
+        })
+    }
+
+    /// Replace every sub-expression structurally equal to `from` with `to`.
+    ///
+    /// The expression is searched from the root, and the replacements are not searched again.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// let e = (x + 1).pow(2) + (x + 1);
+    /// assert_eq!(e.replace(&(x + 1), &y.into()), y.pow(2) + y);
+    /// ```
+    pub fn replace(&self, from: &Expr, to: &Expr) -> Expr {
+        self.map_leaves(&|e| if e == from { Some(to.clone()) } else { None })
+    }
+
+    /// Rebuild the expression, replacing the outermost sub-expressions on which `f` returns
+    /// `Some`.
+    fn map_leaves<F>(&self, f: &F) -> Expr
+    where
+        F: Fn(&Expr) -> Option<Expr>,
+    {
+        if let Some(e) = f(self) {
+            return e;
+        }
+
+        self.map_children(|c| c.map_leaves(f))
+    }
+}
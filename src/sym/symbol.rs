@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::sync::Mutex;
 use std::fmt::{self, Display};
 use symtern::prelude::*;
@@ -13,7 +14,7 @@ lazy_static! {
 }
 
 /// The symbol type.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Symbol(InlineSym);
 
 impl Symbol {
@@ -23,6 +24,14 @@ impl Symbol {
         Symbol(sym)
     }
 
+    /// Construct a symbol reserved for internal use, named `_name#`, which the parser doesn't
+    /// accept.
+    ///
+    /// The same name gives the same symbol, so nested uses need different names.
+    pub(crate) fn dummy(name: &str) -> Symbol {
+        Symbol::new(&format!("_{}#", name))
+    }
+
     /// Construct a power expression.
     ///
     /// # Examples
@@ -38,6 +47,24 @@ impl Symbol {
     }
 }
 
+/// Symbols are ordered by name.
+impl Ord for Symbol {
+    fn cmp(&self, other: &Symbol) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        let pool = SYMPOOL.lock().unwrap();
+        // The symbols are put into the pool when they're created, so it's safe to unwrap.
+        pool.resolve(&self.0).unwrap().cmp(pool.resolve(&other.0).unwrap())
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Symbol) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Display for Symbol {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -0,0 +1,118 @@
+use super::Expr;
+use super::simplify::{sum, product, power, apply, is_negative_term, to_rational};
+use self::Expr::*;
+
+/// Rational expressions.
+impl Expr {
+    /// Combine sums of fractions over a common denominator, recursively. The result is
+    /// simplified.
+    ///
+    /// The common denominator is a product of the denominator factors with the greatest
+    /// exponents. Nothing is expanded or cancelled, see `cancel` for that.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let y = Symbol::new("y");
+    /// assert_eq!((x.pow(-1) + y.pow(-1)).together(), ((x + y) / (x * y)).simplify());
+    /// assert_eq!(
+    ///     (Expr::integer(1) / 2 + x / (x + 1)).together(),
+    ///     ((3i32 * x + 1) / (2i32 * (x + 1))).simplify()
+    /// );
+    /// ```
+    pub fn together(&self) -> Expr {
+        together(&self.simplify())
+    }
+
+    /// Split into the numerator and the denominator, after combining it with `together`.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let (n, d) = (2i32 * x / 3 + x.pow(-2)).numer_denom();
+    /// assert_eq!(n, (2i32 * x.pow(3) + 3).simplify());
+    /// assert_eq!(d, (3i32 * x.pow(2)).simplify());
+    /// ```
+    pub fn numer_denom(&self) -> (Expr, Expr) {
+        let (numer, denom) = split(&self.together());
+        (product(numer), product(denom.into_iter().map(|(b, p)| power(b, p)).collect()))
+    }
+}
+
+/// Combine a canonical expression.
+fn together(e: &Expr) -> Expr {
+    match *e {
+        Sum(ref args) => combine(args.iter().map(together).collect()),
+        Product(ref args) => product(args.iter().map(together).collect()),
+        Pow(ref b, ref p) => power(together(b), together(p)),
+        Apply(ref f, ref args) => apply(f.clone(), args.iter().map(together).collect()),
+        _ => e.clone(),
+    }
+}
+
+/// Combine canonical terms over a common denominator.
+fn combine(terms: Vec<Expr>) -> Expr {
+    let parts = terms.iter().map(split).collect::<Vec<_>>();
+
+    // The common denominator, as bases and positive exponents.
+    let mut common: Vec<(Expr, Expr)> = vec![];
+    for &(_, ref denom) in &parts {
+        for &(ref b, ref p) in denom {
+            let i = common.iter().position(|c| c.0 == *b);
+            match i.map(|i| (i, to_rational(&common[i].1), to_rational(p))) {
+                Some((i, _, _)) if common[i].1 == *p => {}
+                Some((i, Some(q), Some(r))) => {
+                    if r > q {
+                        common[i].1 = p.clone();
+                    }
+                }
+                _ => common.push((b.clone(), p.clone())),
+            }
+        }
+    }
+    if common.is_empty() {
+        return sum(terms);
+    }
+
+    // Each numerator is multiplied by the common denominator over its own.
+    let numers = parts
+        .into_iter()
+        .map(|(mut numer, denom)| {
+            numer.extend(common.iter().map(|&(ref b, ref p)| power(b.clone(), p.clone())));
+            numer.extend(denom.into_iter().map(|(b, p)| power(b, negate(p))));
+            product(numer)
+        })
+        .collect();
+    let denom = common.into_iter().map(|(b, p)| power(b, negate(p))).collect::<Vec<_>>();
+    product(vec![sum(numers), product(denom)])
+}
+
+/// Split a canonical term into the factors of the numerator, and the bases and positive
+/// exponents of the denominator.
+fn split(e: &Expr) -> (Vec<Expr>, Vec<(Expr, Expr)>) {
+    let factors = match *e {
+        Product(ref args) => args.clone(),
+        ref e => vec![e.clone()],
+    };
+    let mut numer = vec![];
+    let mut denom = vec![];
+    for f in factors {
+        match f {
+            Rational(r) => {
+                numer.push(Integer(r.numer().clone()));
+                denom.push((Integer(r.denom().clone()), Expr::integer(1)));
+            }
+            Pow(b, p) if is_negative_term(&p) => denom.push((*b, negate(*p))),
+            f => numer.push(f),
+        }
+    }
+    (numer, denom)
+}
+
+fn negate(e: Expr) -> Expr {
+    product(vec![Expr::integer(-1), e])
+}
@@ -0,0 +1,288 @@
+use std::slice;
+
+use super::Expr;
+use self::Expr::*;
+
+/// Traversal.
+impl Expr {
+    /// Iterate over the direct sub-expressions, from left to right.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let e = x.pow(2) + 1;
+    /// assert_eq!(e.children().collect::<Vec<_>>(), vec![&x.pow(2), &Expr::integer(1)]);
+    /// assert_eq!(Expr::from(x).children().count(), 0);
+    /// ```
+    pub fn children(&self) -> Children<'_> {
+        let (pair, args): (_, &[Expr]) = match *self {
+            Integer(_) | Rational(_) | Sym(_) | Approx(_) | Const(_) | Undefined => {
+                ([None, None], &[])
+            }
+            Neg(ref e) => ([Some(&**e), None], &[]),
+            Ratio(ref a, ref b) | Pow(ref a, ref b) => ([Some(&**a), Some(&**b)], &[]),
+            Sum(ref args) | Product(ref args) | Apply(_, ref args) => ([None, None], args),
+        };
+        Children { pair, next: 0, args: args.iter() }
+    }
+
+    /// The direct sub-expressions, mutably, from left to right.
+    pub fn children_mut(&mut self) -> Vec<&mut Expr> {
+        match *self {
+            Integer(_) | Rational(_) | Sym(_) | Approx(_) | Const(_) | Undefined => vec![],
+            Neg(ref mut e) => vec![&mut **e],
+            Ratio(ref mut a, ref mut b) | Pow(ref mut a, ref mut b) => vec![&mut **a, &mut **b],
+            Sum(ref mut args) | Product(ref mut args) | Apply(_, ref mut args) => {
+                args.iter_mut().collect()
+            }
+        }
+    }
+
+    /// Iterate over all sub-expressions, including itself, where each one comes before its
+    /// children.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let e = Expr::sin(x) * 2;
+    /// let all = e.iter_preorder().cloned().collect::<Vec<_>>();
+    /// assert_eq!(all, vec![e.clone(), Expr::sin(x), x.into(), Expr::integer(2)]);
+    /// ```
+    pub fn iter_preorder(&self) -> Preorder<'_> {
+        Preorder { stack: vec![self] }
+    }
+
+    /// Iterate over all sub-expressions, including itself, where each one comes after its
+    /// children.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let e = Expr::sin(x) * 2;
+    /// let all = e.iter_postorder().cloned().collect::<Vec<_>>();
+    /// assert_eq!(all, vec![x.into(), Expr::sin(x), Expr::integer(2), e.clone()]);
+    /// ```
+    pub fn iter_postorder(&self) -> Postorder<'_> {
+        Postorder { stack: vec![(self, self.children())] }
+    }
+
+    /// Rebuild the expression with the same root, where every direct sub-expression is replaced
+    /// with its image by `f`. The result is not simplified.
+    ///
+    /// # Examples
+    /// ```
+    /// use symrs::sym::{Expr, Symbol};
+    ///
+    /// let x = Symbol::new("x");
+    /// let e = (x + 1) * x;
+    /// assert_eq!(e.map_children(|c| c.clone().pow(2)), (x + 1).pow(2) * x.pow(2));
+    /// ```
+    pub fn map_children<F>(&self, mut f: F) -> Expr
+    where
+        F: FnMut(&Expr) -> Expr,
+    {
+        let mut map_all = |args: &Vec<Expr>| args.iter().map(&mut f).collect();
+        match *self {
+            Integer(_) | Rational(_) | Sym(_) | Approx(_) | Const(_) | Undefined => self.clone(),
+            Neg(ref e) => Neg(Box::new(f(e))),
+            Sum(ref args) => Sum(map_all(args)),
+            Product(ref args) => Product(map_all(args)),
+            Apply(ref func, ref args) => Apply(func.clone(), map_all(args)),
+            Ratio(ref a, ref b) => {
+                let a = f(a);
+                Ratio(Box::new(a), Box::new(f(b)))
+            }
+            Pow(ref a, ref b) => {
+                let a = f(a);
+                Pow(Box::new(a), Box::new(f(b)))
+            }
+        }
+    }
+}
+
+/// An iterator over the direct sub-expressions, returned by `Expr::children`.
+#[derive(Debug, Clone)]
+pub struct Children<'a> {
+    pair: [Option<&'a Expr>; 2],
+    next: usize,
+    args: slice::Iter<'a, Expr>,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Expr;
+
+    fn next(&mut self) -> Option<&'a Expr> {
+        if let Some(e) = self.pair.get(self.next).and_then(|&e| e) {
+            self.next += 1;
+            return Some(e);
+        }
+        self.args.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.pair[self.next.min(2)..].iter().filter(|e| e.is_some()).count();
+        let n = n + self.args.len();
+        (n, Some(n))
+    }
+}
+
+impl<'a> ExactSizeIterator for Children<'a> {}
+
+/// A pre-order iterator over all sub-expressions, returned by `Expr::iter_preorder`.
+#[derive(Debug, Clone)]
+pub struct Preorder<'a> {
+    stack: Vec<&'a Expr>,
+}
+
+impl<'a> Iterator for Preorder<'a> {
+    type Item = &'a Expr;
+
+    fn next(&mut self) -> Option<&'a Expr> {
+        let e = self.stack.pop()?;
+        let start = self.stack.len();
+        self.stack.extend(e.children());
+        self.stack[start..].reverse();
+        Some(e)
+    }
+}
+
+/// A post-order iterator over all sub-expressions, returned by `Expr::iter_postorder`.
+#[derive(Debug, Clone)]
+pub struct Postorder<'a> {
+    stack: Vec<(&'a Expr, Children<'a>)>,
+}
+
+impl<'a> Iterator for Postorder<'a> {
+    type Item = &'a Expr;
+
+    fn next(&mut self) -> Option<&'a Expr> {
+        loop {
+            let child = self.stack.last_mut()?.1.next();
+            match child {
+                Some(c) => self.stack.push((c, c.children())),
+                None => return self.stack.pop().map(|(e, _)| e),
+            }
+        }
+    }
+}
+
+/// A traversal of an expression, which visits every sub-expression in pre-order by default.
+///
+/// Override `visit` to handle some sub-expressions, and call `visit_children` from it to keep
+/// going down.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, Func, Symbol, Visitor};
+///
+/// struct CountSines(usize);
+///
+/// impl Visitor for CountSines {
+///     fn visit(&mut self, e: &Expr) {
+///         if let Expr::Apply(Func::Sin, _) = *e {
+///             self.0 += 1;
+///         }
+///         self.visit_children(e);
+///     }
+/// }
+///
+/// let x = Symbol::new("x");
+/// let mut count = CountSines(0);
+/// count.visit(&(Expr::sin(Expr::sin(x)) + Expr::cos(x)));
+/// assert_eq!(count.0, 2);
+/// ```
+pub trait Visitor {
+    /// Visit an expression. By default, only its children are visited.
+    fn visit(&mut self, e: &Expr) {
+        self.visit_children(e);
+    }
+
+    /// Visit the direct sub-expressions from left to right.
+    fn visit_children(&mut self, e: &Expr) {
+        for c in e.children() {
+            self.visit(c);
+        }
+    }
+}
+
+/// A traversal of an expression which modifies it in place, visiting every sub-expression in
+/// pre-order by default.
+///
+/// Override `visit_mut` to handle some sub-expressions, and call `visit_children_mut` from it to
+/// keep going down.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, Symbol, VisitorMut};
+///
+/// struct Double;
+///
+/// impl VisitorMut for Double {
+///     fn visit_mut(&mut self, e: &mut Expr) {
+///         match *e {
+///             Expr::Integer(ref mut i) => *i *= 2,
+///             _ => self.visit_children_mut(e),
+///         }
+///     }
+/// }
+///
+/// let x = Symbol::new("x");
+/// let mut e = x.pow(2) + 3;
+/// Double.visit_mut(&mut e);
+/// assert_eq!(e, x.pow(4) + 6);
+/// ```
+pub trait VisitorMut {
+    /// Visit an expression. By default, only its children are visited.
+    fn visit_mut(&mut self, e: &mut Expr) {
+        self.visit_children_mut(e);
+    }
+
+    /// Visit the direct sub-expressions from left to right.
+    fn visit_children_mut(&mut self, e: &mut Expr) {
+        for c in e.children_mut() {
+            self.visit_mut(c);
+        }
+    }
+}
+
+/// A transformation of an expression, which rebuilds it from the bottom up by default.
+///
+/// Override `fold` to replace some sub-expressions, and call `fold_children` from it to
+/// transform their children too. The result is not simplified.
+///
+/// # Examples
+/// ```
+/// use symrs::sym::{Expr, Folder, Func, Symbol};
+///
+/// struct SinToCos;
+///
+/// impl Folder for SinToCos {
+///     fn fold(&mut self, e: &Expr) -> Expr {
+///         match *e {
+///             Expr::Apply(Func::Sin, ref args) => Expr::cos(self.fold(&args[0])),
+///             _ => self.fold_children(e),
+///         }
+///     }
+/// }
+///
+/// let x = Symbol::new("x");
+/// let e = Expr::sin(Expr::sin(x)) * 2;
+/// assert_eq!(SinToCos.fold(&e), Expr::cos(Expr::cos(x)) * 2);
+/// ```
+pub trait Folder {
+    /// Transform an expression. By default, only its children are transformed.
+    fn fold(&mut self, e: &Expr) -> Expr {
+        self.fold_children(e)
+    }
+
+    /// Rebuild an expression with the same root from the transformed direct sub-expressions.
+    fn fold_children(&mut self, e: &Expr) -> Expr {
+        e.map_children(|c| self.fold(c))
+    }
+}
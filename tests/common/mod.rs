@@ -0,0 +1,18 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate uses only some of the helpers.
+#![allow(dead_code)]
+
+use num::{BigInt, BigRational};
+use symrs::poly::Poly;
+use symrs::sym::{Expr, Symbol};
+
+/// The rational number `n / d`.
+pub fn ratio(n: i64, d: i64) -> BigRational {
+    BigRational::new(n.into(), d.into())
+}
+
+/// Convert to a polynomial with integer coefficients in `vars`, panicking if it isn't one.
+pub fn int_poly(e: Expr, vars: &[Symbol]) -> Poly<BigInt> {
+    Poly::from_expr(&e, vars).unwrap()
+}
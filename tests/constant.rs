@@ -0,0 +1,78 @@
+extern crate num;
+extern crate symrs;
+
+use std::collections::HashMap;
+use std::f64::consts::{E, PI};
+use num::BigRational;
+use symrs::sym::*;
+
+#[test]
+fn display() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::pi().to_string(), "pi");
+    assert_eq!(format!("{:#}", Expr::pi()), "π");
+    assert_eq!(format!("{}", Expr::from(Constant::NegInfinity)), "-oo");
+    assert_eq!(format!("{:#}", Expr::from(Constant::NegInfinity)), "-∞");
+    assert_eq!(format!("{}", Expr::from(Constant::ComplexInfinity)), "zoo");
+    assert_eq!(format!("{}", 2i32 * Expr::pi() * x), "2 * pi * x");
+    assert_eq!(format!("{:#}", Expr::sin(2i32 * Expr::pi()) + Expr::infinity()), "sin(2 * π) + ∞");
+    assert_eq!(format!("{}", x.pow(Constant::NegInfinity)), "x ^ (-oo)");
+}
+
+#[test]
+fn simplify() {
+    let x = Symbol::new("x");
+    let oo = Expr::infinity;
+    let neg_oo = || Expr::from(Constant::NegInfinity);
+
+    assert_eq!((Expr::pi() + Expr::pi()).simplify(), (2i32 * Expr::pi()).simplify());
+    assert_eq!((x * Expr::pi()).simplify(), Expr::Product(vec![Expr::pi(), x.into()]));
+    assert_eq!((Expr::i() * Expr::i()).simplify(), Expr::integer(-1));
+    assert_eq!(Expr::i().pow(7).simplify(), (-1i32 * Expr::i()).simplify());
+    assert_eq!(Expr::i().pow(8).simplify(), Expr::integer(1));
+
+    assert_eq!((oo() + 1).simplify(), oo());
+    assert_eq!((oo() + oo()).simplify(), oo());
+    assert_eq!((oo() - oo()).simplify(), Expr::Undefined);
+    assert_eq!((-2i32 * oo()).simplify(), neg_oo());
+    assert_eq!((oo() * neg_oo()).simplify(), neg_oo());
+    assert_eq!((Expr::integer(0) * oo()).simplify(), Expr::Undefined);
+    assert_eq!((1i32 / oo()).simplify(), Expr::integer(0));
+    assert_eq!(neg_oo().pow(3).simplify(), neg_oo());
+    assert_eq!(Expr::pow(1.into(), oo()).simplify(), Expr::Undefined);
+    assert_eq!((x + oo()).simplify(), Expr::Sum(vec![oo(), x.into()]));
+}
+
+#[test]
+fn special_values() {
+    assert_eq!(Expr::sin(Expr::pi()).simplify(), Expr::integer(0));
+    assert_eq!(Expr::cos(Expr::pi()).simplify(), Expr::integer(-1));
+    assert_eq!(Expr::ln(Expr::e()).simplify(), Expr::integer(1));
+    assert_eq!(Expr::exp(1).simplify(), Expr::e());
+    assert_eq!(Expr::exp(-Expr::infinity()).simplify(), Expr::integer(0));
+}
+
+#[test]
+fn eval() {
+    let x = Symbol::new("x");
+    let mut env = HashMap::new();
+    env.insert(x, 2.0);
+
+    assert_eq!((x * Expr::pi()).eval(&env), Ok(2.0 * PI));
+    assert_eq!(Expr::e().eval(&env), Ok(E));
+    assert_eq!(Expr::infinity().eval(&env), Ok(std::f64::INFINITY));
+    assert_eq!(Expr::i().eval(&env), Err(EvalError::NonReal));
+
+    let env: HashMap<Symbol, BigRational> = HashMap::new();
+    assert_eq!(Expr::pi().eval_exact(&env), Err(EvalError::Inexact));
+    assert_eq!(Expr::i().eval_exact(&env), Err(EvalError::NonReal));
+}
+
+#[test]
+fn diff() {
+    let x = Symbol::new("x");
+
+    assert_eq!((Expr::pi() * x).diff(x), Expr::pi());
+    assert_eq!(Expr::e().pow(x).diff(x), Expr::e().pow(x));
+}
@@ -0,0 +1,77 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+#[test]
+fn diff_constants() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(Expr::integer(3).diff(x), Expr::integer(0));
+    assert_eq!(Expr::approximate(3.5).diff(x), Expr::integer(0));
+    assert_eq!(Expr::Sym(y).diff(x), Expr::integer(0));
+    assert_eq!(Expr::Sym(x).diff(x), Expr::integer(1));
+    assert_eq!(Expr::Undefined.diff(x), Expr::Undefined);
+}
+
+#[test]
+fn diff_operators() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    // Sum and Neg
+    assert_eq!((x + y).diff(x), Expr::integer(1));
+    assert_eq!((-x).diff(x), Expr::integer(-1));
+    assert_eq!((y - 4 * x).diff(x), Expr::integer(-4));
+    // Product
+    assert_eq!((x * y * x).diff(x), (2i32 * x * y).simplify());
+    // Ratio
+    assert_eq!((1i32 / x).diff(x), (-1i32 / x.pow(2)).simplify());
+    assert_eq!((x / (x + 1)).diff(x), (1i32 / (x + 1) - x / (x + 1).pow(2)).simplify());
+    // Pow with a symbolic exponent
+    assert_eq!(x.pow(y).diff(x), (y * x.pow(y - 1)).simplify());
+    assert_eq!((x.pow(2) + 1).pow(3).diff(x), (6i32 * x * (x.pow(2) + 1).pow(2)).simplify());
+    // The exponent depends on the variable
+    assert_eq!(x.pow(x).diff(x), (x.pow(x) * (1i32 + Expr::ln(x))).simplify());
+    assert_eq!(Expr::pow(2.into(), x).diff(x), (Expr::pow(2.into(), x) * Expr::ln(2)).simplify());
+}
+
+#[test]
+fn diff_functions() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::sin(x).diff(x), Expr::cos(x));
+    assert_eq!(Expr::cos(x).diff(x), (-Expr::sin(x)).simplify());
+    assert_eq!(Expr::tan(x).diff(x), (1i32 + Expr::tan(x).pow(2)).simplify());
+    assert_eq!(Expr::exp(x).diff(x), Expr::exp(x));
+    assert_eq!(Expr::ln(x).diff(x), x.pow(-1));
+    assert_eq!(Expr::sqrt(x).diff(x), (x.pow(Expr::rational(-1, 2)) / 2).simplify());
+    assert_eq!(Expr::atan(x).diff(x), (1i32 + x.pow(2)).pow(-1).simplify());
+    assert_eq!(Expr::asin(x).diff(x), (1i32 - x.pow(2)).pow(Expr::rational(-1, 2)).simplify());
+    assert_eq!(Expr::sinh(x).diff(x), Expr::cosh(x));
+    assert_eq!(Expr::cosh(x).diff(x), Expr::sinh(x));
+    assert_eq!(Expr::abs(x).diff(x), (x / Expr::abs(x)).simplify());
+    assert_eq!(Expr::log(2, x).diff(x), (1i32 / (x * Expr::ln(2))).simplify());
+
+    // Chain rule
+    assert_eq!(Expr::sin(x.pow(2)).diff(x), (2i32 * x * Expr::cos(x.pow(2))).simplify());
+    assert_eq!(
+        Expr::exp(Expr::sin(x)).diff(x),
+        (Expr::cos(x) * Expr::exp(Expr::sin(x))).simplify()
+    );
+}
+
+#[test]
+fn diff_higher_order() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let e = x.pow(4) + x * y;
+    assert_eq!(e.diff_n(x, 1), e.diff(x));
+    assert_eq!(e.diff_n(x, 2), (12i32 * x.pow(2)).simplify());
+    assert_eq!(e.diff_n(x, 5), Expr::integer(0));
+
+    assert_eq!(e.diff_many(&[(x, 1), (y, 1)]), Expr::integer(1));
+    assert_eq!(e.diff_many(&[(y, 1), (x, 1)]), Expr::integer(1));
+    assert_eq!(e.diff_many(&[]), e.simplify());
+}
@@ -0,0 +1,91 @@
+extern crate num;
+extern crate symrs;
+
+mod common;
+
+use std::collections::HashMap;
+use symrs::sym::*;
+use common::ratio;
+
+#[test]
+fn eval_float() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let mut env = HashMap::new();
+    env.insert(x, 2.0);
+    env.insert(y, 0.5);
+
+    assert_eq!(Expr::integer(3).eval(&env), Ok(3.0));
+    assert_eq!(Expr::approximate(1.5).eval(&env), Ok(1.5));
+    assert_eq!((x + y).eval(&env), Ok(2.5));
+    assert_eq!((x - y).eval(&env), Ok(1.5));
+    assert_eq!((x * y * 3).eval(&env), Ok(3.0));
+    assert_eq!((x / y).eval(&env), Ok(4.0));
+    assert_eq!(x.pow(y).eval(&env), Ok(2f64.sqrt()));
+    assert_eq!(x.pow(-2).eval(&env), Ok(0.25));
+    assert_eq!((-x).pow(3).eval(&env), Ok(-8.0));
+}
+
+#[test]
+fn eval_float_errors() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let mut env = HashMap::new();
+    env.insert(x, 0.0);
+
+    assert_eq!((x + y).eval(&env), Err(EvalError::UnboundSymbol(y)));
+    assert_eq!((1i32 / x).eval(&env), Err(EvalError::DivisionByZero));
+    assert_eq!(x.pow(-1).eval(&env), Err(EvalError::DivisionByZero));
+    assert_eq!((x + Expr::Undefined).eval(&env), Err(EvalError::Undefined));
+    assert_eq!((x - 1).pow(Expr::rational(1, 2)).eval(&env), Err(EvalError::NonReal));
+    assert_eq!(Expr::sqrt(x - 1).eval(&env), Err(EvalError::NonReal));
+    assert_eq!(Expr::ln(x - 1).eval(&env), Err(EvalError::NonReal));
+    assert_eq!(Expr::ln(x).eval(&env), Ok(std::f64::NEG_INFINITY));
+}
+
+#[test]
+fn eval_exact() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let mut env = HashMap::new();
+    env.insert(x, ratio(1, 3));
+    env.insert(y, ratio(-2, 1));
+
+    assert_eq!((x + x + x).eval_exact(&env), Ok(ratio(1, 1)));
+    assert_eq!((x / y).eval_exact(&env), Ok(ratio(-1, 6)));
+    assert_eq!(x.pow(y).eval_exact(&env), Ok(ratio(9, 1)));
+    assert_eq!(y.pow(3).eval_exact(&env), Ok(ratio(-8, 1)));
+    assert_eq!((-x).eval_exact(&env), Ok(ratio(-1, 3)));
+    // Rational powers of rational numbers.
+    let half = Expr::rational(1, 2);
+    assert_eq!(Expr::pow(4.into(), half.clone()).eval_exact(&env), Ok(ratio(2, 1)));
+    assert_eq!(Expr::pow(27.into(), Expr::rational(-2, 3)).eval_exact(&env), Ok(ratio(1, 9)));
+    assert_eq!((x * 3).pow(Expr::rational(3, 2)).eval_exact(&env), Ok(ratio(1, 1)));
+    assert_eq!((x / 12).pow(half).eval_exact(&env), Ok(ratio(1, 6)));
+}
+
+#[test]
+fn eval_exact_errors() {
+    let x = Symbol::new("x");
+    let z = Symbol::new("z");
+    let mut env = HashMap::new();
+    env.insert(x, ratio(0, 1));
+
+    assert_eq!(z.pow(2).eval_exact(&env), Err(EvalError::UnboundSymbol(z)));
+    assert_eq!((1i32 / x).eval_exact(&env), Err(EvalError::DivisionByZero));
+    assert_eq!(x.pow(-2).eval_exact(&env), Err(EvalError::DivisionByZero));
+    assert_eq!((x + 0.5).eval_exact(&env), Err(EvalError::Inexact));
+    assert_eq!(Expr::pow(2.into(), Expr::integer(1) / 2).eval_exact(&env), Err(EvalError::Inexact));
+    let half = Expr::rational(1, 2);
+    assert_eq!(Expr::pow((-4).into(), half).eval_exact(&env), Err(EvalError::NonReal));
+    assert_eq!(x.pow(Expr::rational(-1, 2)).eval_exact(&env), Err(EvalError::DivisionByZero));
+    assert_eq!(Expr::Undefined.eval_exact(&env), Err(EvalError::Undefined));
+}
+
+#[test]
+fn display_error() {
+    let x = Symbol::new("x");
+
+    assert_eq!(format!("{}", EvalError::UnboundSymbol(x)), "symbol `x` is unbound");
+    assert_eq!(format!("{}", EvalError::DivisionByZero), "division by zero");
+}
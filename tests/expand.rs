@@ -0,0 +1,75 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+#[test]
+fn distribute() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    assert_eq!((2i32 * (x + 1)).expand(), (2i32 * x + 2).simplify());
+    assert_eq!(((x + y) * (x - y)).expand(), (x.pow(2) - y.pow(2)).simplify());
+    assert_eq!(
+        ((x + 1) * (y + 1) * z).expand(),
+        (x * y * z + x * z + y * z + z).simplify()
+    );
+}
+
+#[test]
+fn multinomial() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    assert_eq!(
+        (x + y).pow(3).expand(),
+        (x.pow(3) + 3i32 * x.pow(2) * y + 3i32 * x * y.pow(2) + y.pow(3)).simplify()
+    );
+    assert_eq!(
+        (x + y + z).pow(2).expand(),
+        (x.pow(2) + y.pow(2) + z.pow(2) + 2i32 * x * y + 2i32 * x * z + 2i32 * y * z).simplify()
+    );
+    assert_eq!((x - 1).pow(5).expand().subs(x, 3).simplify(), Expr::integer(32));
+    assert_eq!((x + 1).pow(-2).expand(), (x.pow(2) + 2i32 * x + 1).pow(-1).simplify());
+}
+
+#[test]
+fn nested() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(((x + 1).pow(2) + 1).pow(2).subs(x, 1).expand(), Expr::integer(25));
+    assert_eq!(((x * (y + 1)).pow(2)).expand(), (x.pow(2) * y.pow(2) + 2i32 * x.pow(2) * y + x.pow(2)).simplify());
+    assert_eq!(Expr::sin(x * (x + 1)).expand(), Expr::sin(x + x.pow(2)).simplify());
+    assert_eq!((x + 1).pow(y).expand(), (x + 1).pow(y).simplify());
+}
+
+#[test]
+fn expand_numer() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(
+        (x * (x + 1) / (y * (y + 1))).expand_numer(),
+        ((x.pow(2) + x) / (y * (y + 1))).simplify()
+    );
+}
+
+#[test]
+fn expand_power_base() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((2i32 * x).pow(0.5).expand_power_base(), (Expr::pow(2.into(), 0.5) * x.pow(0.5)).simplify());
+    assert_eq!((x * y).pow(x).expand_power_base(), (x.pow(x) * y.pow(x)).simplify());
+}
+
+#[test]
+fn idempotent() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let e = ((x + y).pow(3) * (x - 2)).expand();
+    assert_eq!(e.expand(), e);
+}
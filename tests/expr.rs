@@ -56,6 +56,66 @@ fn equality() {
 
     assert_eq!(x - 1, x - 1);
     assert_eq!(- x, - x);
+
+    assert_eq!(Expr::approximate(0.0), Expr::approximate(-0.0));
+    assert_eq!(Expr::approximate(std::f64::NAN), Expr::approximate(-std::f64::NAN));
+    assert_ne!(Expr::approximate(std::f64::NAN), Expr::approximate(1.0));
+}
+
+#[test]
+fn hash_and_order() {
+    use std::collections::{BTreeSet, HashMap};
+
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let mut map = HashMap::new();
+    map.insert((x + 1).simplify(), 1);
+    map.insert(Expr::approximate(0.0), 2);
+    map.insert(Expr::approximate(std::f64::NAN), 3);
+    assert_eq!(map.get(&(1i32 + x).simplify()), Some(&1));
+    assert_eq!(map.get(&Expr::approximate(-0.0)), Some(&2));
+    assert_eq!(map.get(&Expr::approximate(std::f64::NAN)), Some(&3));
+    assert_eq!(map.get(&Expr::from(x)), None);
+
+    // Variants in the order they're declared.
+    let mut v = vec![
+        Expr::Undefined,
+        Expr::sin(x),
+        x.pow(2),
+        x + 1,
+        Expr::pi(),
+        Expr::approximate(std::f64::NAN),
+        Expr::approximate(1.5),
+        y.into(),
+        x.into(),
+        Expr::rational(1, 2),
+        Expr::integer(3),
+        Expr::integer(-3),
+    ];
+    v.sort();
+    assert_eq!(v, vec![
+        Expr::integer(-3),
+        Expr::integer(3),
+        Expr::rational(1, 2),
+        x.into(),
+        y.into(),
+        Expr::approximate(1.5),
+        Expr::approximate(std::f64::NAN),
+        Expr::pi(),
+        x + 1,
+        x.pow(2),
+        Expr::sin(x),
+        Expr::Undefined,
+    ]);
+    assert!(Expr::sin(x) < Expr::cos(x));
+    assert!(x + 1 < x + 2);
+    assert!(x + 1 < x + 1 + y);
+
+    assert!(x < y);
+    assert!(Symbol::new("ab") < Symbol::new("abcde"));
+    assert!(Symbol::new("gamma") > Symbol::new("beta"));
+    assert_eq!(vec![y, x].into_iter().collect::<BTreeSet<_>>().into_iter().next(), Some(x));
 }
 
 #[test]
@@ -0,0 +1,148 @@
+extern crate num;
+extern crate symrs;
+
+mod common;
+
+use num::BigInt;
+use symrs::poly::Poly;
+use symrs::sym::*;
+use common::{int_poly, ratio};
+
+/// Check that the factors multiply back to `p`, and return them as expressions.
+fn factors(p: &Poly<BigInt>) -> Vec<(Expr, u32)> {
+    let (c, factors) = p.factor_list();
+    let product = factors
+        .iter()
+        .fold(Poly::constant(p.vars(), c), |acc, (f, k)| acc * f.pow(*k));
+    assert_eq!(&product, p);
+    factors.iter().map(|(f, k)| (f.to_expr(), *k)).collect()
+}
+
+#[test]
+fn sqf_list() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let p = int_poly(x.pow(2) + 1, &[x]);
+    assert_eq!(p.sqf_list(), (BigInt::from(1), vec![(p.clone(), 1)]));
+
+    // -3 (x - 1) ^ 3 (x + 2) ^ 3 (x ^ 2 + 1)
+    let p = int_poly(-3i32 * (x - 1).pow(3) * (x + 2).pow(3) * (x.pow(2) + 1), &[x]);
+    let (c, factors) = p.sqf_list();
+    assert_eq!(c, BigInt::from(-3));
+    assert_eq!(factors, vec![
+        (int_poly(x.pow(2) + 1, &[x]), 1),
+        (int_poly((x - 1) * (x + 2), &[x]), 3),
+    ]);
+
+    // (y + 1) ^ 2 (x + y) ^ 2
+    let p = int_poly((y + 1).pow(2) * (x + y).pow(2), &[x, y]);
+    let (_, factors) = p.sqf_list();
+    assert_eq!(factors, vec![(int_poly((y + 1) * (x + y), &[x, y]), 2)]);
+
+    assert_eq!(int_poly(x * 0, &[x]).sqf_list(), (BigInt::from(0), vec![]));
+}
+
+#[test]
+fn univariate() {
+    let x = Symbol::new("x");
+    let vars = [x];
+
+    assert_eq!(factors(&int_poly(x.pow(8) - 1, &vars)), vec![
+        ((x - 1).simplify(), 1),
+        ((x + 1).simplify(), 1),
+        ((x.pow(2) + 1).simplify(), 1),
+        ((x.pow(4) + 1).simplify(), 1),
+    ]);
+
+    // Irreducible, but it splits modulo every prime.
+    let p = int_poly(x.pow(4) - 10i32 * x.pow(2) + 1, &vars);
+    assert_eq!(factors(&p), vec![(p.to_expr(), 1)]);
+
+    // Factors with leading coefficients, and a repeated one.
+    let p = int_poly(6i32 * x.pow(3) + 13i32 * x.pow(2) + 9i32 * x + 2, &vars)
+        * int_poly(x - 1, &vars).pow(2);
+    assert_eq!(factors(&p), vec![
+        ((x - 1).simplify(), 2),
+        ((x + 1).simplify(), 1),
+        ((2i32 * x + 1).simplify(), 1),
+        ((3i32 * x + 2).simplify(), 1),
+    ]);
+
+    // Large coefficients.
+    let p = int_poly((1234i32 * x - 5678).pow(2) * (x.pow(3) + 999i32 * x + 1001), &vars);
+    assert_eq!(factors(&p), vec![
+        ((617i32 * x - 2839).simplify(), 2),
+        ((x.pow(3) + 999i32 * x + 1001).simplify(), 1),
+    ]);
+
+    // Cyclotomic polynomials.
+    let p = int_poly(x.pow(15) - 1, &vars);
+    assert_eq!(factors(&p).len(), 4);
+}
+
+#[test]
+fn content() {
+    let x = Symbol::new("x");
+    let p: Poly = Poly::from_expr(&(x.pow(2) * 3 / 4 - Expr::rational(3, 4)), &[x]).unwrap();
+    let (c, factors) = p.factor_list();
+    assert_eq!(c, ratio(3, 4));
+    assert_eq!(factors.len(), 2);
+
+    let p = int_poly((-6i32).into(), &[x]);
+    assert_eq!(p.factor_list(), (BigInt::from(-6), vec![]));
+}
+
+#[test]
+fn multivariate() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    assert_eq!(factors(&int_poly(x.pow(2) - y.pow(2), &[x, y])), vec![
+        ((x - y).simplify(), 1),
+        ((x + y).simplify(), 1),
+    ]);
+
+    // The content in `x` is factored too.
+    let p = int_poly((y.pow(2) - 1) * (x.pow(2) + y), &[x, y]);
+    assert_eq!(factors(&p), vec![
+        ((y - 1).simplify(), 1),
+        ((y + 1).simplify(), 1),
+        ((x.pow(2) + y).simplify(), 1),
+    ]);
+
+    let p = int_poly((x * y + z).pow(2) * (x + y + z), &[x, y, z]);
+    assert_eq!(factors(&p), vec![
+        ((x + y + z).simplify(), 1),
+        ((x * y + z).simplify(), 2),
+    ]);
+
+    // Irreducible.
+    let p = int_poly(x.pow(2) + y.pow(2), &[x, y]);
+    assert_eq!(factors(&p), vec![(p.to_expr(), 1)]);
+}
+
+#[test]
+fn factor_expr() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((x.pow(2) - 1).factor(), ((x - 1) * (x + 1)).simplify());
+    assert_eq!(
+        (x.pow(3) - 3i32 * x.pow(2) + 3i32 * x - 1).factor(),
+        (x - 1).pow(3).simplify()
+    );
+    assert_eq!((-2i32 * x * y - 2i32 * y.pow(2)).factor(), (-2i32 * y * (x + y)).simplify());
+    assert_eq!((x.pow(2) / 2 - Expr::rational(1, 2)).factor(), ((x - 1) * (x + 1) / 2).simplify());
+
+    // Not polynomial.
+    let e = Expr::sin(x) + 1;
+    assert_eq!(e.factor(), e.simplify());
+    assert_eq!(Expr::from(12).factor(), 12.into());
+
+    assert_eq!(
+        (x.pow(2) - 1).factor_list(&[x, y]),
+        Ok((1.into(), vec![((x - 1).simplify(), 1), ((x + 1).simplify(), 1)]))
+    );
+}
@@ -0,0 +1,179 @@
+extern crate num;
+extern crate symrs;
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use num::BigRational;
+use symrs::sym::*;
+
+#[test]
+fn construct_func() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::sin(x), Expr::Apply(Func::Sin, vec![x.into()]));
+    assert_eq!(Expr::ln(2), Expr::Apply(Func::Ln, vec![2.into()]));
+    assert_eq!(Expr::log(2, x), Expr::Apply(Func::Log, vec![2.into(), x.into()]));
+    assert_eq!(Expr::apply(Func::Log, vec![x.into()]), None);
+    assert_eq!(Func::from_name("sqrt"), Some(Func::Sqrt));
+    assert_eq!(Func::Log.arity(), Some(2));
+}
+
+#[test]
+fn display_func() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(format!("{}", Expr::sin(x)), "sin(x)");
+    assert_eq!(format!("{}", Expr::sin(x).pow(2)), "sin(x) ^ 2");
+    assert_eq!(format!("{}", Expr::atan(x + y)), "atan(x + y)");
+    assert_eq!(format!("{}", 2 * Expr::exp(-x)), "2 * exp(- x)");
+    assert_eq!(format!("{}", Expr::log(10, x)), "log(10, x)");
+}
+
+#[test]
+fn simplify_func() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::sin(0).simplify(), Expr::integer(0));
+    assert_eq!(Expr::cos(0).simplify(), Expr::integer(1));
+    assert_eq!(Expr::exp(0).simplify(), Expr::integer(1));
+    assert_eq!(Expr::ln(1).simplify(), Expr::integer(0));
+    assert_eq!(Expr::acos(1).simplify(), Expr::integer(0));
+    assert_eq!(Expr::abs(-3).simplify(), Expr::integer(3));
+    assert_eq!(Expr::abs(Expr::rational(-1, 2)).simplify(), Expr::rational(1, 2));
+    assert_eq!(Expr::sqrt(4).simplify(), Expr::integer(2));
+    assert_eq!(Expr::sqrt(x).simplify(), x.pow(Expr::rational(1, 2)));
+    assert_eq!(Expr::exp(Expr::ln(x)).simplify(), Expr::Sym(x));
+    assert_eq!(Expr::sin(0.5).simplify(), Expr::approximate(0.5f64.sin()));
+    assert_eq!(Expr::sin(x + Expr::Undefined).simplify(), Expr::Undefined);
+
+    // Signs
+    assert_eq!(Expr::sin(-x).simplify(), (-Expr::sin(x)).simplify());
+    assert_eq!(Expr::cos(-2 * Expr::from(x)).simplify(), Expr::cos(2 * x).simplify());
+    assert_eq!(Expr::abs(-x).simplify(), Expr::abs(x));
+
+    assert_eq!(
+        Expr::log(2, x).simplify(),
+        (Expr::ln(x) / Expr::ln(2)).simplify()
+    );
+    assert_eq!(
+        (Expr::sin(x) + Expr::sin(x)).simplify(),
+        (2i32 * Expr::sin(x)).simplify()
+    );
+    assert_eq!((Expr::sin(x) * x).simplify(), (x * Expr::sin(x)).simplify());
+}
+
+#[test]
+fn eval_func() {
+    let x = Symbol::new("x");
+    let mut env = HashMap::new();
+    env.insert(x, PI / 2.0);
+
+    assert_eq!(Expr::sin(x).eval(&env), Ok(1.0));
+    assert_eq!(Expr::log(2, 8).eval(&env), Ok(3.0));
+    assert_eq!(Expr::sqrt(x * 8 / PI).eval(&env), Ok(2.0));
+    assert_eq!(Expr::exp(Expr::ln(x)).eval(&env), Ok(PI / 2.0));
+
+    assert_eq!(Expr::sqrt(9).eval_exact(&HashMap::new()), Ok(BigRational::from_integer(3.into())));
+    assert_eq!(Expr::cos(0).eval_exact(&HashMap::new()), Ok(BigRational::from_integer(1.into())));
+    assert_eq!(Expr::cos(1).eval_exact(&HashMap::new()), Err(EvalError::Inexact));
+}
+
+#[test]
+fn wrong_arity_func() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let none = Expr::Apply(Func::Sin, vec![]);
+    let two = Expr::Apply(Func::Sin, vec![x.into(), y.into()]);
+    let log = Expr::Apply(Func::Log, vec![x.into()]);
+
+    assert_eq!(none.simplify(), Expr::Undefined);
+    assert_eq!(two.simplify(), Expr::Undefined);
+    assert_eq!(log.simplify(), Expr::Undefined);
+    assert_eq!(two.diff(x), Expr::Undefined);
+    assert_eq!(log.diff(x), Expr::Undefined);
+
+    let mut env = HashMap::new();
+    env.insert(x, 1.0);
+    env.insert(y, 2.0);
+    assert_eq!(none.eval(&env), Err(EvalError::WrongArity(Func::Sin)));
+    assert_eq!(two.eval(&env), Err(EvalError::WrongArity(Func::Sin)));
+    assert_eq!(log.eval(&env), Err(EvalError::WrongArity(Func::Log)));
+    assert_eq!(none.eval_exact(&HashMap::new()), Err(EvalError::WrongArity(Func::Sin)));
+}
+
+#[test]
+fn subs_func() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(Expr::sin(x).subs(x, y), Expr::sin(y));
+    assert_eq!(Expr::sin(x).replace(&Expr::sin(x), &y.into()), Expr::Sym(y));
+}
+
+#[test]
+fn display_undef_func() {
+    let f = Function::new("f");
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(f.call(vec![x.into()]).to_string(), "f(x)");
+    assert_eq!(f.call(vec![x.into(), y.into()]).to_string(), "f(x, y)");
+    assert_eq!(f.to_string(), "f");
+}
+
+#[test]
+fn interned_undef_func() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Function::new("f"), Function::new("f"));
+    assert_ne!(Function::new("f"), Function::new("g"));
+    assert_eq!(
+        Function::new("f").call(vec![x.into()]),
+        Function::new("f").call(vec![x.into()])
+    );
+}
+
+#[test]
+fn simplify_undef_func() {
+    let f = Function::new("f");
+    let x = Symbol::new("x");
+
+    assert_eq!(
+        f.call(vec![x + x]).simplify(),
+        f.call(vec![(2i32 * x).simplify()])
+    );
+    assert_eq!(f.call(vec![Expr::Undefined]).simplify(), Expr::Undefined);
+    assert_eq!((f.call(vec![x.into()]) - f.call(vec![x.into()])).simplify(), Expr::integer(0));
+}
+
+#[test]
+fn diff_undef_func() {
+    let f = Function::new("f");
+    let g = Function::new("g");
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let fx = f.call(vec![x.into()]);
+    assert_eq!(fx.diff(x).to_string(), "f'(x)");
+    assert_eq!(fx.diff_n(x, 2).to_string(), "f''(x)");
+    assert_eq!(fx.diff(y), Expr::integer(0));
+
+    let fxy = f.call(vec![x.into(), y.into()]);
+    assert_eq!(fxy.diff(x).to_string(), "f^(1, 0)(x, y)");
+    assert_eq!(fxy.diff_many(&[(x, 1), (y, 1)]).to_string(), "f^(1, 1)(x, y)");
+
+    // The chain rule.
+    let gx2 = g.call(vec![x.pow(2)]);
+    assert_eq!(gx2.diff(x), (2i32 * x * Expr::Apply(Func::Undef(g, vec![1]), vec![x.pow(2)])).simplify());
+}
+
+#[test]
+fn eval_undef_func() {
+    let f = Function::new("f");
+    let x = Symbol::new("x");
+    let mut env = HashMap::new();
+    env.insert(x, 1.0);
+
+    assert_eq!(f.call(vec![x.into()]).eval(&env), Err(EvalError::UndefinedFunction(f)));
+}
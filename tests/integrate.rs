@@ -0,0 +1,226 @@
+extern crate symrs;
+
+use std::collections::HashMap;
+use symrs::sym::*;
+
+/// Check that the derivative of the antiderivative is `e` at some points, and return it.
+fn check(e: Expr, x: Symbol) -> Expr {
+    let res = e.integrate(x).unwrap_or_else(|| panic!("can't integrate {}", e));
+    let d = res.diff(x);
+    for &v in &[0.3, 0.5, 0.9] {
+        let mut env = HashMap::new();
+        env.insert(x, v);
+        let (expected, actual) = (e.eval(&env).unwrap(), d.eval(&env).unwrap());
+        assert!(
+            (expected - actual).abs() < 1e-9 * expected.abs().max(1.0),
+            "{} isn't an antiderivative of {}",
+            res,
+            e
+        );
+    }
+    res
+}
+
+#[test]
+fn polynomials() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    assert_eq!(Expr::integer(3).integrate(x), Some((3i32 * x).simplify()));
+    assert_eq!(Expr::from(a).integrate(x), Some((a * x).simplify()));
+    assert_eq!(
+        (3i32 * x.pow(2) + 2i32 * x + 1).integrate(x),
+        Some((x.pow(3) + x.pow(2) + x).simplify())
+    );
+    assert_eq!((a * x.pow(3)).integrate(x), Some((a * x.pow(4) / 4).simplify()));
+    assert_eq!(x.pow(-1).integrate(x), Some(Expr::ln(x)));
+    assert_eq!(
+        (2i32 * x + 1).pow(3).integrate(x),
+        Some(((2i32 * x + 1).pow(4) / 8).simplify())
+    );
+    check((x + 1).pow(2) * (x - 1), x);
+    check(x.pow(Expr::rational(1, 2)), x);
+}
+
+#[test]
+fn rational_functions() {
+    let x = Symbol::new("x");
+
+    assert_eq!((1i32 / (x.pow(2) + 1)).integrate(x), Some(Expr::atan(x)));
+    assert_eq!(
+        (1i32 / (x.pow(2) - 1)).integrate(x),
+        Some((Expr::ln(x - 1) / 2 - Expr::ln(x + 1) / 2).simplify())
+    );
+    assert_eq!(
+        (x / (x.pow(2) + 1)).integrate(x),
+        Some((Expr::ln(x.pow(2) + 1) / 2).simplify())
+    );
+    check(1i32 / (x.pow(2) + 2i32 * x + 5), x);
+    check((x.pow(3) + 1) / (x.pow(2) - 2), x);
+    check(1i32 / (x.pow(2) + 1).pow(2), x);
+    check((2i32 * x + 3) / (x.pow(2) + x + 1).pow(3), x);
+    check(1i32 / (x * (x + 1).pow(2)), x);
+}
+
+#[test]
+fn functions() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::cos(x).integrate(x), Some(Expr::sin(x)));
+    assert_eq!(
+        Expr::exp(3i32 * x + 1).integrate(x),
+        Some((Expr::exp(3i32 * x + 1) / 3).simplify())
+    );
+    assert_eq!(
+        Expr::ln(x).integrate(x),
+        Some((x * Expr::ln(x) - x).simplify())
+    );
+    check(Expr::sin(2i32 * x), x);
+    check(Expr::tan(x), x);
+    check(Expr::pow(2.into(), x), x);
+    check(Expr::atan(x), x);
+    check(Expr::asin(x / 2), x);
+    check(Expr::cosh(x) + Expr::tanh(x), x);
+    check(Expr::sin(x).pow(2), x);
+    check(Expr::cos(x).pow(-2), x);
+    check((1i32 - x.pow(2)).pow(Expr::rational(-1, 2)), x);
+    check((x.pow(2) + 4).pow(Expr::rational(-1, 2)), x);
+}
+
+#[test]
+fn substitution() {
+    let x = Symbol::new("x");
+
+    assert_eq!(
+        (x * Expr::exp(x.pow(2))).integrate(x),
+        Some((Expr::exp(x.pow(2)) / 2).simplify())
+    );
+    check(Expr::sin(x) * Expr::cos(x), x);
+    check(Expr::ln(x) / x, x);
+    check(Expr::cos(x) * Expr::exp(Expr::sin(x)), x);
+    check(1i32 / (x * Expr::ln(x)), x);
+    check(x.pow(3) * Expr::exp(x.pow(2)), x);
+    check(x * (x.pow(2) + 1).pow(Expr::rational(1, 2)), x);
+
+    // A symbol can't be confused with the substituted one.
+    for &name in &["_u", "_u0", "_u1", "_u#0"] {
+        let u = Symbol::new(name);
+        let e = Expr::cos(x) * Expr::exp(Expr::sin(x) * u);
+        let res = e.integrate(x).unwrap();
+        let mut env = HashMap::new();
+        env.insert(x, 0.5);
+        env.insert(u, 2.0);
+        let (expected, actual) = (e.eval(&env).unwrap(), res.diff(x).eval(&env).unwrap());
+        assert!((expected - actual).abs() < 1e-9, "{} isn't an antiderivative of {}", res, e);
+    }
+}
+
+#[test]
+fn parts() {
+    let x = Symbol::new("x");
+
+    check(x * Expr::sin(x), x);
+    check(x.pow(3) * Expr::exp(x), x);
+    check(x * Expr::ln(x), x);
+    check(Expr::ln(x).pow(2), x);
+    check(x * Expr::atan(x), x);
+    // The integral appears again after two steps.
+    check(Expr::exp(x) * Expr::sin(x), x);
+    check(Expr::exp(2i32 * x) * Expr::cos(3i32 * x), x);
+}
+
+#[test]
+fn no_closed_form() {
+    let x = Symbol::new("x");
+    let f = Function::new("f");
+
+    assert_eq!(Expr::exp(x.pow(2)).integrate(x), None);
+    assert_eq!((Expr::sin(x) / x).integrate(x), None);
+    assert_eq!(f.call(vec![x.into()]).integrate(x), None);
+    assert_eq!((1i32 / (x.pow(3) + x + 1)).integrate(x), None);
+}
+
+/// Check that a definite integral is approximated within the error estimate.
+fn check_numeric(res: Result<DefiniteIntegral, EvalError>, expected: f64) {
+    match res {
+        Ok(DefiniteIntegral::Numeric { value, error }) => {
+            assert!(error < 1e-9, "error {} is too large", error);
+            assert!((value - expected).abs() <= error.max(1e-12), "{} isn't {}", value, expected);
+        }
+        res => panic!("expected a numeric value, got {:?}", res),
+    }
+}
+
+#[test]
+fn definite_exact() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+    let exact = |e: Expr| Ok(DefiniteIntegral::Exact(e.simplify()));
+
+    assert_eq!((x.pow(2) + 1).integrate_between(x, 0, 3), exact(Expr::integer(12)));
+    assert_eq!((x.pow(2) + 1).integrate_between(x, 3, 0), exact(Expr::integer(-12)));
+    assert_eq!(Expr::from(x).integrate_between(x, 0, a), exact(a.pow(2) / 2));
+    assert_eq!((a * x).integrate_between(x, 0, 2), exact(2i32 * a));
+    assert_eq!(x.pow(-1).integrate_between(x, 1, a), exact(Expr::ln(a)));
+    assert_eq!(x.pow(-2).integrate_between(x, a, Expr::infinity()), exact(a.pow(-1)));
+    assert_eq!(Expr::cos(x).integrate_between(x, 0, Expr::pi()), exact(Expr::integer(0)));
+    assert_eq!(x.pow(-1).integrate_between(x, 1, Expr::e()), exact(Expr::integer(1)));
+    // Singular at a bound.
+    assert_eq!(
+        x.pow(Expr::rational(-1, 2)).integrate_between(x, 0, 1),
+        exact(Expr::integer(2))
+    );
+    // Infinite bounds.
+    assert_eq!(
+        Expr::exp(-2i32 * x).integrate_between(x, 0, Expr::infinity()),
+        exact(Expr::rational(1, 2))
+    );
+    assert_eq!(
+        x.pow(-1).integrate_between(x, 1, Expr::infinity()),
+        exact(Expr::infinity())
+    );
+    // Limits at the bounds.
+    assert_eq!(
+        (x.pow(2) + 1).pow(-1).integrate_between(x, -Expr::infinity(), Expr::infinity()),
+        exact(Expr::pi())
+    );
+    assert_eq!(
+        (x * Expr::exp(-x)).integrate_between(x, 0, Expr::infinity()),
+        exact(Expr::integer(1))
+    );
+    assert_eq!(Expr::ln(x).integrate_between(x, 0, 1), exact(Expr::integer(-1)));
+    // A pole outside the interval.
+    assert_eq!((x - 2).pow(-2).integrate_between(x, -1, 1), exact(Expr::rational(2, 3)));
+}
+
+#[test]
+fn definite_numeric() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    check_numeric((Expr::sin(x) / x).integrate_between(x, 0, 1), 0.946_083_070_367_183);
+    check_numeric(
+        Expr::exp(-x.pow(2)).integrate_between(x, -Expr::infinity(), Expr::infinity()),
+        std::f64::consts::PI.sqrt(),
+    );
+    check_numeric(
+        Expr::exp(-x.pow(2)).integrate_between(x, 1, 0),
+        -0.746_824_132_812_427_1,
+    );
+    check_numeric(
+        (Expr::exp(-x) * Expr::ln(x)).integrate_between(x, 0, Expr::infinity()),
+        -0.577_215_664_901_532_9,
+    );
+    // The antiderivative `ln(x)` isn't continuous, and the integral diverges.
+    assert!(x.pow(-1).integrate_between(x, -1, 1).is_err());
+    assert!(x.pow(-2).integrate_between(x, -1, 1).is_err());
+    assert!((x - Expr::rational(1, 3)).pow(-2).integrate_between(x, 0, 1).is_err());
+    // With a symbolic bound, `ln(x)` isn't known to be continuous from a bound where it isn't.
+    assert_eq!(x.pow(-1).integrate_between(x, 0, a), Err(EvalError::UnboundSymbol(a)));
+    assert_eq!(x.pow(-1).integrate_between(x, -1, a), Err(EvalError::UnboundSymbol(a)));
+    assert_eq!(Expr::tan(x).integrate_between(x, 0, 2), Err(EvalError::NoConvergence));
+    assert_eq!(
+        Expr::exp(a * x.pow(2)).integrate_between(x, 0, 1),
+        Err(EvalError::UnboundSymbol(a))
+    );
+}
@@ -0,0 +1,107 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+fn oo() -> Expr {
+    Expr::infinity()
+}
+
+#[test]
+fn finite_points() {
+    let x = Symbol::new("x");
+
+    assert_eq!((x.pow(2) + 1).limit(x, 2, Direction::Both), Some(Expr::integer(5)));
+    assert_eq!((Expr::sin(x) / x).limit(x, 0, Direction::Both), Some(Expr::integer(1)));
+    assert_eq!(
+        ((1i32 - Expr::cos(x)) / x.pow(2)).limit(x, 0, Direction::Both),
+        Some(Expr::rational(1, 2))
+    );
+    assert_eq!(
+        ((x.pow(2) - 1) / (x - 1)).limit(x, 1, Direction::Both),
+        Some(Expr::integer(2))
+    );
+    assert_eq!(
+        ((Expr::exp(x) - 1i32 - x) / x.pow(2)).limit(x, 0, Direction::Both),
+        Some(Expr::rational(1, 2))
+    );
+    assert_eq!(Expr::exp(x).limit(x, 0, Direction::Both), Some(Expr::integer(1)));
+}
+
+#[test]
+fn one_sided() {
+    let x = Symbol::new("x");
+
+    assert_eq!(x.pow(-1).limit(x, 0, Direction::Right), Some(oo()));
+    assert_eq!(x.pow(-1).limit(x, 0, Direction::Left), Some((-oo()).simplify()));
+    assert_eq!(x.pow(-1).limit(x, 0, Direction::Both), None);
+    assert_eq!(x.pow(-2).limit(x, 0, Direction::Both), Some(oo()));
+
+    assert_eq!(x.pow(x).limit(x, 0, Direction::Right), Some(Expr::integer(1)));
+    assert_eq!((x * Expr::ln(x)).limit(x, 0, Direction::Right), Some(Expr::integer(0)));
+    assert_eq!(Expr::ln(x).limit(x, 0, Direction::Right), Some((-oo()).simplify()));
+    assert_eq!(
+        Expr::exp(x.pow(-1)).limit(x, 0, Direction::Left),
+        Some(Expr::integer(0))
+    );
+    assert_eq!(Expr::exp(x.pow(-1)).limit(x, 0, Direction::Right), Some(oo()));
+    assert_eq!((Expr::abs(x) / x).limit(x, 0, Direction::Left), Some(Expr::integer(-1)));
+    assert_eq!((Expr::abs(x) / x).limit(x, 0, Direction::Both), None);
+}
+
+#[test]
+fn infinite_points() {
+    let x = Symbol::new("x");
+
+    assert_eq!(
+        ((2i32 * x.pow(2) + 1) / (x.pow(2) - 3i32 * x)).limit(x, oo(), Direction::Both),
+        Some(Expr::integer(2))
+    );
+    assert_eq!(((x + 1) / x.pow(2)).limit(x, oo(), Direction::Both), Some(Expr::integer(0)));
+    assert_eq!((x.pow(3) - x).limit(x, -oo(), Direction::Both), Some((-oo()).simplify()));
+    assert_eq!(
+        (x - (x.pow(2) + 1).pow(Expr::rational(1, 2))).limit(x, oo(), Direction::Both),
+        Some(Expr::integer(0))
+    );
+    assert_eq!(
+        (1i32 + x.pow(-1)).pow(x).limit(x, oo(), Direction::Both),
+        Some(Expr::e())
+    );
+    assert_eq!((Expr::ln(x) / x).limit(x, oo(), Direction::Both), Some(Expr::integer(0)));
+    assert_eq!(
+        (x.pow(3) * Expr::exp(-x)).limit(x, oo(), Direction::Both),
+        Some(Expr::integer(0))
+    );
+    assert_eq!((Expr::exp(x) / x.pow(5)).limit(x, oo(), Direction::Both), Some(oo()));
+    assert_eq!(
+        Expr::atan(x).limit(x, oo(), Direction::Both),
+        Some((Expr::pi() / 2).simplify())
+    );
+    assert_eq!(Expr::exp(x).limit(x, -oo(), Direction::Both), Some(Expr::integer(0)));
+    assert_eq!(Expr::sin(x).limit(x, oo(), Direction::Both), None);
+
+    // Sums of infinities of different growth.
+    assert_eq!((Expr::exp(x) - x).limit(x, oo(), Direction::Both), Some(oo()));
+    assert_eq!((x.pow(2) - Expr::exp(x)).limit(x, oo(), Direction::Both), Some((-oo()).simplify()));
+    assert_eq!(
+        (Expr::exp(x) - Expr::exp(x / 2)).limit(x, oo(), Direction::Both),
+        Some(oo())
+    );
+}
+
+#[test]
+fn parameters() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    assert_eq!((a * x + 1).limit(x, 0, Direction::Both), Some(Expr::integer(1)));
+    assert_eq!(
+        (Expr::sin(a * x) / x).limit(x, 0, Direction::Both),
+        Some(Expr::from(a))
+    );
+    assert_eq!((a * x).limit(x, oo(), Direction::Both), None);
+
+    // A symbol can't be confused with the one substituted for `x`.
+    let t = Symbol::new("_t");
+    assert_eq!((Expr::from(x) / t).limit(x, 0, Direction::Right), Some(Expr::integer(0)));
+    assert_eq!((x + t).limit(x, oo(), Direction::Both), Some(oo()));
+}
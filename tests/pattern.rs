@@ -0,0 +1,165 @@
+extern crate symrs;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use symrs::sym::*;
+
+#[test]
+fn wildcards() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let a = Wild::new("a");
+    let sa = a.symbol();
+
+    let pattern = Pattern::new(Expr::exp(sa), &[a.clone()]);
+    let b = Expr::exp(x * y).matches(&pattern).unwrap();
+    assert_eq!(b.len(), 1);
+    assert_eq!(b[&sa], (x * y).simplify());
+    assert!(Expr::ln(x).matches(&pattern).is_none());
+
+    // A wildcard repeated must match the same expression.
+    let pattern = Pattern::new(sa * Expr::ln(sa), &[a.clone()]);
+    assert_eq!((x * Expr::ln(x)).matches(&pattern).unwrap()[&sa], x.into());
+    assert!((y * Expr::ln(x)).matches(&pattern).is_none());
+
+    let integer = Wild::new("n").with_predicate(|e| match *e {
+        Expr::Integer(_) => true,
+        _ => false,
+    });
+    let n = integer.symbol();
+    let pattern = Pattern::new(x.pow(n), &[integer]);
+    assert_eq!(x.pow(3).matches(&pattern).unwrap()[&n], Expr::integer(3));
+    assert!(x.pow(y).matches(&pattern).is_none());
+
+    let constant = Wild::new("c").exclude(&[x]);
+    let c = constant.symbol();
+    let pattern = Pattern::new(Expr::sin(c * x), &[constant]);
+    assert_eq!(Expr::sin(y * x * 3).matches(&pattern).unwrap()[&c], (y * 3).simplify());
+    assert_eq!(Expr::sin(x).matches(&pattern).unwrap()[&c], Expr::integer(1));
+    assert!(Expr::sin(x.pow(2)).matches(&pattern).is_none());
+}
+
+#[test]
+fn commutative() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let a = Wild::new("a");
+    let b = Wild::new("b");
+    let (sa, sb) = (a.symbol(), b.symbol());
+
+    let pattern = Pattern::new(Expr::sin(sa).pow(2) + Expr::cos(sa).pow(2), &[a.clone()]);
+    let e = Expr::cos(x + y).pow(2) + Expr::sin(y + x).pow(2);
+    assert_eq!(e.matches(&pattern).unwrap()[&sa], (x + y).simplify());
+    assert!((Expr::cos(x).pow(2) + Expr::sin(y).pow(2)).matches(&pattern).is_none());
+
+    // The remaining terms are shared among the wildcards, which get at least one if possible.
+    let pattern = Pattern::new(sa + sb, &[a.clone(), b.clone()]);
+    let bindings = (x + y + 1).matches(&pattern).unwrap();
+    assert_ne!(bindings[&sa], Expr::integer(0));
+    assert_ne!(bindings[&sb], Expr::integer(0));
+    assert_eq!((bindings[&sa].clone() + bindings[&sb].clone()).simplify(), (x + y + 1).simplify());
+    let bindings = Expr::from(x).matches(&pattern).unwrap();
+    assert_eq!((bindings[&sa].clone() + bindings[&sb].clone()).simplify(), x.into());
+
+    let pattern = Pattern::new(sa * x + sb, &[a.clone().exclude(&[x]), b.clone().exclude(&[x])]);
+    let bindings = (y * x * 2 + y + 3).matches(&pattern).unwrap();
+    assert_eq!(bindings[&sa], (y * 2).simplify());
+    assert_eq!(bindings[&sb], (y + 3).simplify());
+    assert!((x.pow(2) + 1).matches(&pattern).is_none());
+}
+
+#[test]
+fn numbers() {
+    let x = Symbol::new("x");
+    let a = Wild::new("a");
+    let sa = a.symbol();
+
+    let pattern = Pattern::new(sa * 2, &[a.clone()]);
+    assert_eq!((x * 6).matches(&pattern).unwrap()[&sa], (x * 3).simplify());
+    assert_eq!(Expr::from(x).matches(&pattern).unwrap()[&sa], (x / 2).simplify());
+    assert_eq!(Expr::integer(4).matches(&pattern).unwrap()[&sa], Expr::integer(2));
+
+    let pattern = Pattern::new(sa + 1, &[a.clone()]);
+    assert_eq!((x + 3).matches(&pattern).unwrap()[&sa], (x + 2).simplify());
+    assert_eq!((x + 1).matches(&pattern).unwrap()[&sa], x.into());
+
+    // Without wildcards to take the rest, numbers must be equal.
+    let pattern = Pattern::new(Expr::sin(x) * 2, &[a.clone()]);
+    assert!((Expr::sin(x) * 2).matches(&pattern).is_some());
+    assert!((Expr::sin(x) * 4).matches(&pattern).is_none());
+}
+
+#[test]
+fn rules() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let a = Wild::new("a");
+    let b = Wild::new("b");
+    let (sa, sb) = (a.symbol(), b.symbol());
+
+    let pythagoras = RewriteRule::new(
+        Pattern::new(Expr::sin(sa).pow(2) + Expr::cos(sa).pow(2), &[a.clone()]),
+        1,
+    );
+    let e = Expr::exp(Expr::sin(x).pow(2) + Expr::cos(x).pow(2)) * y;
+    assert_eq!(e.rewrite(&[pythagoras.clone()]), (Expr::e() * y).simplify());
+    assert_eq!(pythagoras.apply(&e), None);
+    let lhs = Expr::sin(sa).pow(2) + Expr::cos(sa).pow(2);
+    assert_eq!(pythagoras.lhs().expr(), &lhs.simplify());
+
+    // The kinetic energy of a body with a positive mass.
+    let m = Symbol::new("m");
+    let v = Symbol::new("v");
+    let energy = RewriteRule::new(
+        Pattern::new(sa * sb.pow(2) / 2, &[a.clone(), b.clone()]),
+        Expr::symbol("E"),
+    )
+    .with_condition(move |b| b[&sa] == Expr::from(m));
+    let e = (m * v.pow(2) / 2 + 1).rewrite(&[energy.clone()]);
+    assert_eq!(e, (Expr::symbol("E") + 1).simplify());
+    assert_eq!((x * v.pow(2) / 2).rewrite(&[energy]), (x * v.pow(2) / 2).simplify());
+
+    // Rules apply repeatedly until none does. Without the predicate, `sin(x)` would match with
+    // `a = x / 2`.
+    let whole = Wild::new("a").with_predicate(|e| match *e {
+        Expr::Product(ref args) => match args[0] {
+            Expr::Rational(_) => false,
+            _ => true,
+        },
+        _ => true,
+    });
+    let double = RewriteRule::new(
+        Pattern::new(Expr::sin(sa * 2), &[whole]),
+        2i32 * Expr::sin(sa) * Expr::cos(sa),
+    );
+    let e = Expr::sin(x * 4).rewrite(&[double]);
+    let expected = 4i32 * Expr::sin(x) * Expr::cos(x) * Expr::cos(x * 2);
+    assert_eq!(e, expected.simplify());
+
+    // Rules undoing each other stop.
+    let swap = RewriteRule::new(Pattern::new(Expr::sin(sa), &[a.clone()]), Expr::cos(sa));
+    let back = RewriteRule::new(Pattern::new(Expr::cos(sa), &[a.clone()]), Expr::sin(sa));
+    let e = Expr::sin(x).rewrite(&[swap, back]);
+    assert!(e == Expr::sin(x) || e == Expr::cos(x));
+}
+
+#[test]
+fn lazy() {
+    // The terms of the pattern can be placed in 12 * 11 * ... * 7 ways, which are tried one at a
+    // time until one matches.
+    let x = Symbol::new("x");
+    let tries = Rc::new(Cell::new(0));
+    let counter = tries.clone();
+    let rest = Wild::new("r").with_predicate(move |_| {
+        counter.set(counter.get() + 1);
+        true
+    });
+    let mut wilds = (0..6).map(|i| Wild::new(&format!("a{}", i))).collect::<Vec<_>>();
+    let lhs = wilds.iter().fold(Expr::from(&rest), |acc, w| acc + Expr::sin(w.symbol()));
+    wilds.push(rest);
+    let rule = RewriteRule::new(Pattern::new(lhs, &wilds), 0);
+
+    let e = Expr::Sum((1..=12).map(|k| Expr::sin(k * x)).collect());
+    assert_eq!(rule.apply(&e), Some(Expr::integer(0)));
+    assert_eq!(tries.get(), 1);
+}
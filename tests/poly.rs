@@ -0,0 +1,117 @@
+extern crate num;
+extern crate symrs;
+
+mod common;
+
+use num::{BigInt, BigRational};
+use symrs::poly::*;
+use symrs::sym::*;
+use common::ratio;
+
+#[test]
+fn from_expr() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    let p: Poly = Poly::from_expr(&((x + 1) * (x - y) / 2), &[x, y]).unwrap();
+    assert_eq!(p.num_terms(), 4);
+    assert_eq!(p.coeff(&[2, 0]), ratio(1, 2));
+    assert_eq!(p.coeff(&[1, 1]), ratio(-1, 2));
+    assert_eq!(p.coeff(&[0, 0]), ratio(0, 1));
+
+    let p: Poly = Poly::from_expr(&Expr::pow(2.into(), -1), &[x]).unwrap();
+    assert_eq!(p, Poly::constant(&[x], ratio(1, 2)));
+
+    let not_poly = |e: Expr| Poly::<BigRational>::from_expr(&e, &[x, y]).unwrap_err();
+    assert_eq!(not_poly(x * z), NotPolynomial(z.into()));
+    assert_eq!(not_poly(x.pow(-1)), NotPolynomial(x.pow(-1)));
+    assert_eq!(not_poly(x.pow(y)), NotPolynomial(x.pow(y)));
+    assert_eq!(not_poly(Expr::sin(x)), NotPolynomial(Expr::sin(x)));
+    assert_eq!(not_poly(x / y), NotPolynomial(x / y));
+
+    let p = Poly::<BigInt>::from_expr(&(x / 2), &[x]);
+    assert_eq!(p, Err(NotPolynomial(x / 2)));
+}
+
+#[test]
+fn round_trip() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let e = (x.pow(3) - 2i32 * x * y + y.pow(2) / 3 - 7).simplify();
+    let p: Poly = Poly::from_expr(&e, &[x, y]).unwrap();
+    assert_eq!(p.to_expr(), e);
+    assert_eq!(p.to_string(), e.to_string());
+    assert_eq!(Poly::<BigInt>::zero(&[x]).to_expr(), Expr::integer(0));
+}
+
+#[test]
+fn arithmetic() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let vars = [x, y];
+    let poly = |e: Expr| Poly::<BigInt>::from_expr(&e, &vars).unwrap();
+
+    let (p, q) = (poly(x + y), poly(x - y));
+    assert_eq!(&p + &q, poly(2i32 * x));
+    assert_eq!(&p - &q, poly(2i32 * y));
+    assert_eq!(&p * &q, poly(x.pow(2) - y.pow(2)));
+    assert_eq!(-&p, poly(-x - y));
+    assert_eq!(p.pow(3), poly((x + y).pow(3)));
+    assert_eq!(p.pow(0), Poly::one(&vars));
+    assert!((&p - &p).is_zero());
+    assert_eq!(p.scale(&BigInt::from(3)), poly(3i32 * x + 3i32 * y));
+}
+
+#[test]
+#[should_panic]
+fn different_vars() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let _ = Poly::<BigInt>::var(&[x], 0) + Poly::var(&[y], 0);
+}
+
+#[test]
+fn degree() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let p: Poly = Poly::from_expr(&(x.pow(3) * y + y.pow(2) + 1), &[x, y]).unwrap();
+    assert_eq!(p.degree(x), Some(3));
+    assert_eq!(p.degree(y), Some(2));
+    assert_eq!(p.total_degree(), Some(4));
+    assert_eq!(Poly::<BigInt>::zero(&[x]).total_degree(), None);
+    assert!(Poly::<BigInt>::constant(&[x], 5.into()).is_constant());
+}
+
+#[test]
+fn leading_term() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    // x y ^ 2 z + 2 z ^ 2 - 3 x ^ 3 + 4 z ^ 4 in x > y > z.
+    let e = x * y.pow(2) * z + 2i32 * z.pow(2) - 3i32 * x.pow(3) + 4i32 * z.pow(4);
+    let p: Poly<BigInt> = Poly::from_expr(&e, &[x, y, z]).unwrap();
+    assert_eq!(p.leading_term(MonomialOrder::Lex), Some((&[3, 0, 0][..], &BigInt::from(-3))));
+    assert_eq!(p.leading_monomial(MonomialOrder::Grlex), Some(&[1, 2, 1][..]));
+    assert_eq!(p.leading_coeff(MonomialOrder::Grlex), BigInt::from(1));
+
+    // x ^ 2 z ^ 2 and x y ^ 3 have the same degree.
+    let p: Poly<BigInt> = Poly::from_expr(&(x.pow(2) * z.pow(2) + x * y.pow(3)), &[x, y, z]).unwrap();
+    assert_eq!(p.leading_monomial(MonomialOrder::Grlex), Some(&[2, 0, 2][..]));
+    assert_eq!(p.leading_monomial(MonomialOrder::Grevlex), Some(&[1, 3, 0][..]));
+    assert_eq!(Poly::<BigInt>::zero(&[x]).leading_term(MonomialOrder::Lex), None);
+}
+
+#[test]
+fn eval() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let p: Poly = Poly::from_expr(&(x.pow(2) / 2 + x * y - 1), &[x, y]).unwrap();
+    assert_eq!(p.eval(&[ratio(1, 1), ratio(1, 2)]), ratio(0, 1));
+    assert_eq!(p.eval_f64(&[2.0, 0.5]), 2.0);
+}
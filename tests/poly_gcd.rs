@@ -0,0 +1,97 @@
+extern crate num;
+extern crate symrs;
+
+mod common;
+
+use symrs::poly::*;
+use symrs::sym::*;
+use common::{int_poly, ratio};
+
+#[test]
+fn div_rem() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let f: Poly = Poly::from_expr(&(x.pow(4) - 1), &[x]).unwrap();
+    let d = Poly::from_expr(&(2i32 * x.pow(2) + 1), &[x]).unwrap();
+    let (q, r) = f.div_rem(&d);
+    assert_eq!(q.to_expr(), (x.pow(2) / 2 - ratio(1, 4)).simplify());
+    assert_eq!(r.to_expr(), Expr::rational(-3, 4));
+    assert_eq!(&q * &d + &r, f);
+
+    // Over the integers, 2 x ^ 2 + 1 doesn't divide x ^ 4.
+    let (q, r) = int_poly(x.pow(4) - 1, &[x]).div_rem(&int_poly(2i32 * x.pow(2) + 1, &[x]));
+    assert!(q.is_zero());
+    assert_eq!(r, int_poly(x.pow(4) - 1, &[x]));
+
+    let f = int_poly(x.pow(2) * y + x * y.pow(2) + y.pow(2), &[x, y]);
+    let d1 = int_poly(x * y - 1, &[x, y]);
+    let d2 = int_poly(y.pow(2) - 1, &[x, y]);
+    let (qs, r) = f.reduce(&[d1.clone(), d2.clone()], MonomialOrder::Lex);
+    assert_eq!(qs[0], int_poly(x + y, &[x, y]));
+    assert_eq!(qs[1], int_poly(1.into(), &[x, y]));
+    assert_eq!(r, int_poly(x + y + 1, &[x, y]));
+
+    assert_eq!(
+        int_poly(x.pow(2) - y.pow(2), &[x, y]).exact_div(&int_poly(x + y, &[x, y])),
+        Some(int_poly(x - y, &[x, y]))
+    );
+    assert_eq!(int_poly(x.pow(2) + 1, &[x]).exact_div(&int_poly(x + 1, &[x])), None);
+}
+
+#[test]
+fn pseudo_div_rem() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let vars = [x, y];
+
+    let f = int_poly(x.pow(3) * y + x + 1, &vars);
+    let d = int_poly(y * x.pow(2) + 2i32 * x, &vars);
+    let (q, r) = f.pseudo_div_rem(&d, x);
+    let l = int_poly(y.into(), &vars);
+    assert_eq!(l.pow(2) * f, &q * &d + &r);
+    assert!(r.degree(x) < Some(2));
+}
+
+#[test]
+fn gcd() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    let vars = [x];
+    let f = int_poly((x + 1).pow(2) * (x - 2) * 6i32, &vars);
+    let g = int_poly((x + 1) * (x + 3) * 4i32, &vars);
+    assert_eq!(f.gcd(&g), int_poly(2i32 * x + 2, &vars));
+    assert_eq!(f.lcm(&g), int_poly(12i32 * (x + 1).pow(2) * (x - 2) * (x + 3), &vars));
+
+    let vars = [x, y, z];
+    let common = x * y + z.pow(2) - 3;
+    let f = int_poly(common.clone() * (x + y).pow(2) * (z - 1), &vars);
+    let g = int_poly(-common.clone() * (x - y) * (z - 1), &vars);
+    assert_eq!(f.gcd(&g), int_poly(common * (z - 1), &vars));
+
+    let vars = [x];
+    assert!(int_poly(0.into(), &vars).gcd(&int_poly(0.into(), &vars)).is_zero());
+    assert_eq!(int_poly(0.into(), &vars).gcd(&int_poly(-x, &vars)), int_poly(x.into(), &vars));
+    assert_eq!(int_poly(6.into(), &vars).gcd(&int_poly(4i32 * x, &vars)), int_poly(2.into(), &vars));
+    assert_eq!(int_poly(x.pow(2) + 1, &vars).gcd(&int_poly(x + 1, &vars)), Poly::one(&vars));
+
+    // Over the rationals, the GCD is monic.
+    let f: Poly = Poly::from_expr(&(2i32 * x + 2), &vars).unwrap();
+    let g = Poly::from_expr(&((x + 1) / 3), &vars).unwrap();
+    assert_eq!(f.gcd(&g).to_expr(), (x + 1).simplify());
+}
+
+#[test]
+fn expr_gcd() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(
+        Expr::poly_gcd(&(x.pow(2) * y - y), &(x * y + y), &[x, y]),
+        Ok((x * y + y).simplify())
+    );
+    assert_eq!(Expr::poly_gcd(&Expr::sin(x), &x.into(), &[x]), Err(NotPolynomial(Expr::sin(x))));
+    assert_eq!(Expr::poly_lcm(&(2i32 * x), &(3i32 * y), &[x, y]), Ok((6i32 * x * y).simplify()));
+}
@@ -0,0 +1,112 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+fn integers(v: Vec<i64>) -> Vec<Expr> {
+    v.into_iter().map(Expr::integer).collect()
+}
+
+#[test]
+fn conversion() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    let s = PowerSeries::new(x, |n| Expr::integer(n as i64));
+    assert_eq!(s.var(), x);
+    assert_eq!(s.coeffs(4), integers(vec![0, 1, 2, 3]));
+    assert_eq!(s.to_expr(3), (x + 2i32 * x.pow(2)).simplify());
+
+    let s = PowerSeries::from_expr(&(1i32 - a * x).pow(-1), x).unwrap();
+    assert_eq!(s.coeff(3), a.pow(3));
+    let s = PowerSeries::from_expr(&Expr::cos(x), x).unwrap();
+    assert_eq!(s.to_series(5), Expr::cos(x).series(x, 0, 5).unwrap());
+    assert_eq!(s.to_string(), "1 + (-1/2) * x ^ 2 + (1/24) * x ^ 4 + O(x ^ 6)");
+
+    assert!(PowerSeries::from_expr(&x.pow(Expr::rational(1, 2)), x).is_none());
+    assert!(PowerSeries::from_expr(&x.pow(-1), x).is_none());
+    assert!(PowerSeries::from_expr(&Expr::ln(x), x).is_none());
+    // Terms which aren't of a Taylor series beyond the first few.
+    assert!(PowerSeries::from_expr(&x.pow(Expr::rational(9, 2)), x).is_none());
+    assert!(PowerSeries::from_expr(&(x.pow(5) * Expr::ln(x)), x).is_none());
+    assert!(PowerSeries::from_expr(&(1i32 + x.pow(7) * Expr::ln(x + x.pow(2))), x).is_none());
+    assert!(PowerSeries::from_expr(&(x.pow(2) + x.pow(a)), x).is_none());
+    // Expansions which only hold for `x > 0`.
+    assert!(PowerSeries::from_expr(&Expr::abs(x), x).is_none());
+    assert!(PowerSeries::from_expr(&x.pow(2).pow(Expr::rational(1, 2)), x).is_none());
+    let s = PowerSeries::from_expr(&x.pow(4).pow(Expr::rational(1, 2)), x).unwrap();
+    assert_eq!(s.coeffs(4), integers(vec![0, 0, 1, 0]));
+    // Removable singularities.
+    let s = PowerSeries::from_expr(&((Expr::exp(x) - 1i32) / x), x).unwrap();
+    assert_eq!(s.coeffs(3), vec![Expr::integer(1), Expr::rational(1, 2), Expr::rational(1, 6)]);
+    assert_eq!(s.coeff(9), Expr::rational(1, 3_628_800));
+
+    // A symbol can't be confused with the one standing for `ln(x)`.
+    let log = Symbol::new("x_log");
+    let s = PowerSeries::from_expr(&(x + log), x).unwrap();
+    assert_eq!(s.coeffs(2), vec![Expr::from(log), Expr::integer(1)]);
+}
+
+#[test]
+fn arithmetic() {
+    let x = Symbol::new("x");
+    let exp = PowerSeries::from_expr(&Expr::exp(x), x).unwrap();
+    let geometric = PowerSeries::from_expr(&(1i32 - x).pow(-1), x).unwrap();
+
+    let s = &exp * &exp;
+    assert_eq!(s.coeff(5), Expr::rational(32, 120));
+    let s = &geometric + &exp;
+    assert_eq!(s.coeff(3), Expr::rational(7, 6));
+    let s = &geometric - &geometric;
+    assert_eq!(s.coeffs(3), integers(vec![0, 0, 0]));
+    assert_eq!((-geometric.clone()).coeff(7), Expr::integer(-1));
+
+    // Partial sums of the harmonic series.
+    let ln = PowerSeries::from_expr(&(1i32 - x), x).unwrap().ln().unwrap();
+    let s = -ln * geometric.clone();
+    assert_eq!(s.coeff(4), Expr::rational(25, 12));
+
+    let fib = PowerSeries::from_expr(&(1i32 - x - x.pow(2)), x).unwrap().reciprocal().unwrap();
+    assert_eq!(fib.coeffs(8), integers(vec![1, 1, 2, 3, 5, 8, 13, 21]));
+    assert!(PowerSeries::from_expr(&Expr::sin(x), x).unwrap().reciprocal().is_none());
+}
+
+#[test]
+fn exp_and_ln() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    let s = PowerSeries::from_expr(&(a * x), x).unwrap().exp();
+    assert_eq!(s.coeff(3), (a.pow(3) / 6).expand());
+    let s = PowerSeries::from_expr(&(1i32 + x), x).unwrap().exp();
+    assert_eq!(s.coeff(0), Expr::e());
+    assert_eq!(s.coeff(2), (Expr::e() / 2).expand());
+
+    // ln(exp(f)) = f
+    let f = PowerSeries::from_expr(&Expr::sin(x), x).unwrap();
+    let s = f.exp().ln().unwrap();
+    assert_eq!(s.coeffs(8), f.coeffs(8));
+    assert!(f.ln().is_none());
+
+    // The number of set partitions.
+    let bell = PowerSeries::from_expr(&(Expr::exp(x) - 1i32), x).unwrap().exp();
+    let counts = (0..6).map(|n| (0..n).fold(bell.coeff(n), |c, k| c * (k + 1) as i64).expand());
+    assert_eq!(counts.collect::<Vec<_>>(), integers(vec![1, 1, 2, 5, 15, 52]));
+}
+
+#[test]
+fn composition() {
+    let x = Symbol::new("x");
+    let exp = PowerSeries::from_expr(&Expr::exp(x), x).unwrap();
+    let sin = PowerSeries::from_expr(&Expr::sin(x), x).unwrap();
+
+    let s = exp.compose(&sin).unwrap();
+    let expected = PowerSeries::from_expr(&Expr::exp(Expr::sin(x)), x).unwrap();
+    assert_eq!(s.coeffs(7), expected.coeffs(7));
+    assert!(sin.compose(&exp).is_none());
+
+    let asin = sin.reversion().unwrap();
+    let expected = PowerSeries::from_expr(&Expr::asin(x), x).unwrap();
+    assert_eq!(asin.coeffs(8), expected.coeffs(8));
+    assert_eq!(sin.compose(&asin).unwrap().coeffs(8), integers(vec![0, 1, 0, 0, 0, 0, 0, 0]));
+    assert!(exp.reversion().is_none());
+}
@@ -0,0 +1,77 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+#[test]
+fn symbols() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    assert!(Expr::pi().free_symbols().is_empty());
+    let e = Expr::exp(z.pow(y)) / (x + 1);
+    assert_eq!(e.free_symbols().into_iter().collect::<Vec<_>>(), vec![x, y, z]);
+    assert!(e.has_symbol(y));
+    assert!(!(x + 1).has_symbol(y));
+
+    assert!(e.contains(&e));
+    assert!(e.contains(&z.pow(y)));
+    assert!(e.contains(&Expr::integer(1)));
+    assert!(!e.contains(&Expr::integer(2)));
+    assert!(!e.contains(&(x + 2)));
+}
+
+#[test]
+fn sizes() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(Expr::integer(1).depth(), 1);
+    assert_eq!(Expr::integer(1).node_count(), 1);
+    assert_eq!(Expr::integer(1).count_ops(), 0);
+
+    let e = Expr::cos(x * y) - x.pow(2) / y;
+    assert_eq!(e.depth(), 5);
+    assert_eq!(e.node_count(), 11);
+    // One addition, one negation, one multiplication, a division, a power and a cosine.
+    assert_eq!(e.count_ops(), 1 + 1 + 2 + 3 + 3 + 5);
+}
+
+#[test]
+fn classification() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let p = (x + y).pow(3) * 2i32 - x * y / 3;
+    assert!(p.is_polynomial_in(&[x, y]));
+    assert!(p.is_polynomial_in(&[x]));
+    assert!(p.is_polynomial_in(&[]));
+    assert!(Expr::exp(y).is_polynomial_in(&[x]));
+    assert!(!Expr::exp(x).is_polynomial_in(&[x]));
+    assert!(!x.pow(-1).is_polynomial_in(&[x]));
+    assert!(!x.pow(y).is_polynomial_in(&[x]));
+    assert!(!(y / x).is_polynomial_in(&[x]));
+    assert!((y / x).is_polynomial_in(&[y]));
+
+    assert!(p.is_rational_function_in(&[x, y]));
+    assert!((y / x + x.pow(-3)).is_rational_function_in(&[x, y]));
+    assert!(Expr::sin(y).pow(-1).is_rational_function_in(&[x]));
+    assert!(!Expr::sin(x).pow(-1).is_rational_function_in(&[x]));
+    assert!(!x.pow(Expr::rational(1, 2)).is_rational_function_in(&[x]));
+}
+
+#[test]
+fn degree() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let p = (x + y).pow(3) * 2i32 - x * y.pow(4) / 3;
+    assert_eq!(p.degree_in(x), Some(3));
+    assert_eq!(p.degree_in(y), Some(4));
+    assert_eq!(((x + 1).pow(2) - x.pow(2)).degree_in(x), Some(1));
+    assert_eq!(Expr::integer(5).degree_in(x), Some(0));
+    assert_eq!(Expr::integer(0).degree_in(x), None);
+    assert_eq!((x.pow(2) * Expr::exp(y)).degree_in(x), Some(2));
+    assert_eq!((1i32 / x).degree_in(x), None);
+    assert_eq!(Expr::sin(x).degree_in(x), None);
+}
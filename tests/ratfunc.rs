@@ -0,0 +1,98 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+#[test]
+fn together() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((x + 1).together(), (x + 1).simplify());
+    assert_eq!(
+        (1i32 / (x + 1) + 1i32 / (x - 1)).together(),
+        (2i32 * x / ((x - 1) * (x + 1))).simplify()
+    );
+    // The greatest power of each factor is taken.
+    assert_eq!(
+        (x.pow(-2) + y / x).together(),
+        ((x * y + 1) / x.pow(2)).simplify()
+    );
+    // Nested fractions.
+    assert_eq!((1i32 / (1i32 + 1i32 / x)).together(), (x / (x + 1)).simplify());
+    assert_eq!(
+        Expr::sin(x.pow(-1) + 1).together(),
+        Expr::sin((x + 1) / x).simplify()
+    );
+    assert_eq!(
+        (Expr::rational(1, 2) + Expr::rational(1, 3) * x).together(),
+        ((2i32 * x + 3) / 6).simplify()
+    );
+}
+
+#[test]
+fn numer_denom() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((x / y).numer_denom(), (x.into(), y.into()));
+    assert_eq!((x + 1).numer_denom(), ((x + 1).simplify(), 1.into()));
+    assert_eq!(Expr::rational(-2, 3).numer_denom(), ((-2).into(), 3.into()));
+    // The numerator isn't expanded.
+    assert_eq!(
+        (x / (x + 1) - 1).numer_denom(),
+        ((x - (x + 1)).simplify(), (x + 1).simplify())
+    );
+}
+
+#[test]
+fn cancel() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(
+        ((x.pow(2) + 2i32 * x + 1) / (x.pow(2) - 1)).cancel(),
+        ((x + 1) / (x - 1)).simplify()
+    );
+    assert_eq!((x / (2i32 * x)).cancel(), Expr::rational(1, 2));
+    assert_eq!(
+        (1i32 / x + 1i32 / (x * (x + 1))).cancel(),
+        ((x + 2) / (x.pow(2) + x)).simplify()
+    );
+    // The denominator has a positive leading coefficient.
+    assert_eq!(((x * y - x) / (2i32 - 2i32 * y)).cancel(), (-1i32 * x / 2).simplify());
+    assert_eq!((x.pow(2) - 1).cancel(), (x.pow(2) - 1).simplify());
+    // Not a rational function.
+    assert_eq!(
+        (Expr::sin(x) / x + 1).cancel(),
+        ((Expr::sin(x) + x) / x).simplify()
+    );
+}
+
+#[test]
+fn apart() {
+    let x = Symbol::new("x");
+
+    assert_eq!(
+        (1i32 / (x.pow(2) + 3i32 * x + 2)).apart(x),
+        (1i32 / (x + 1) - 1i32 / (x + 2)).simplify()
+    );
+    // With a polynomial part.
+    assert_eq!(
+        (x.pow(3) / (x.pow(2) - 1)).apart(x),
+        (x + 1i32 / (2i32 * (x - 1)) + 1i32 / (2i32 * (x + 1))).simplify()
+    );
+    // Repeated and irreducible factors.
+    assert_eq!(
+        ((x.pow(2) + 1) / (x.pow(2) * (x.pow(2) + 2))).apart(x),
+        (1i32 / (2i32 * x.pow(2)) + 1i32 / (2i32 * (x.pow(2) + 2))).simplify()
+    );
+    assert_eq!(
+        ((2i32 * x + 3) / (x - 1).pow(3)).apart(x),
+        (2i32 / (x - 1).pow(2) + 5i32 / (x - 1).pow(3)).simplify()
+    );
+    assert_eq!((x.pow(2) + 1).apart(x), (x.pow(2) + 1).simplify());
+    assert_eq!(
+        (x / (x + 1)).apart(x).together().cancel(),
+        (x / (x + 1)).cancel()
+    );
+}
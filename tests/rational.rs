@@ -0,0 +1,82 @@
+extern crate num;
+extern crate symrs;
+
+use std::collections::HashMap;
+use num::{BigInt, BigRational};
+use symrs::sym::*;
+
+#[test]
+fn construct_rational() {
+    let half = BigRational::new(1.into(), 2.into());
+
+    assert_eq!(Expr::rational(1, 2), Expr::Rational(half.clone()));
+    assert_eq!(Expr::rational(-3, -6), Expr::Rational(half.clone()));
+    assert_eq!(Expr::rational(4, -2), Expr::integer(-2));
+    assert_eq!(Expr::rational(0, 5), Expr::integer(0));
+    assert_eq!(Expr::rational(1, 0), Expr::Undefined);
+
+    assert_eq!(Expr::from(half.clone()), Expr::rational(1, 2));
+    assert_eq!(Expr::from(&half), Expr::rational(1, 2));
+    assert_eq!(Expr::from(BigRational::from_integer(BigInt::from(7))), Expr::integer(7));
+}
+
+#[test]
+fn display_rational() {
+    let x = Symbol::new("x");
+
+    assert_eq!(format!("{}", Expr::rational(-1, 3)), "-1/3");
+    assert_eq!(format!("{}", x.pow(Expr::rational(1, 3))), "x ^ (1/3)");
+    assert_eq!(format!("{}", Expr::rational(1, 3) + x), "1/3 + x");
+}
+
+#[test]
+fn exact_operators() {
+    let x = Symbol::new("x");
+
+    assert_eq!(Expr::integer(1) / 3, Expr::rational(1, 3));
+    assert_eq!(Expr::integer(6) / 3, Expr::integer(2));
+    assert_eq!(Expr::integer(1) / 0, Expr::Undefined);
+    assert_eq!(Expr::rational(1, 3) + Expr::rational(1, 6), Expr::rational(1, 2));
+    assert_eq!(Expr::rational(1, 3) - 1, Expr::rational(-2, 3));
+    assert_eq!(Expr::rational(2, 3) * Expr::rational(3, 4), Expr::rational(1, 2));
+    assert_eq!(-Expr::rational(2, 3), Expr::rational(-2, 3));
+    assert_eq!(-Expr::integer(2), Expr::integer(-2));
+
+    // Only exact numbers are folded.
+    assert_eq!(Expr::integer(1) / 2.0, Expr::Ratio(Box::new(1.into()), Box::new(2.0.into())));
+    assert_eq!(x / 2, Expr::Ratio(Box::new(x.into()), Box::new(2.into())));
+}
+
+#[test]
+fn simplify_rational() {
+    let x = Symbol::new("x");
+
+    assert_eq!((x / 2 + x / 3).simplify(), (Expr::rational(5, 6) * x).simplify());
+    assert_eq!(Expr::pow(2.into(), -2).simplify(), Expr::rational(1, 4));
+    assert_eq!(Expr::pow(4.into(), Expr::rational(1, 2)).simplify(), Expr::integer(2));
+    assert_eq!(
+        Expr::pow(Expr::rational(8, 27), Expr::rational(2, 3)).simplify(),
+        Expr::rational(4, 9)
+    );
+    assert_eq!(
+        Expr::pow(2.into(), Expr::rational(3, 2)).simplify(),
+        (2i32 * Expr::pow(2.into(), Expr::rational(1, 2))).simplify()
+    );
+    assert_eq!(
+        (Expr::pow(2.into(), Expr::rational(1, 2)) * Expr::pow(2.into(), Expr::rational(1, 2)))
+            .simplify(),
+        Expr::integer(2)
+    );
+    assert_eq!(Expr::Rational(BigRational::from_integer(3.into())).simplify(), Expr::integer(3));
+    assert_eq!((Expr::rational(1, 2) + 0.5).simplify(), Expr::approximate(1.0));
+}
+
+#[test]
+fn eval_rational() {
+    let x = Symbol::new("x");
+    let env = HashMap::new();
+
+    assert_eq!(Expr::rational(1, 4).eval(&env), Ok(0.25));
+    assert_eq!(Expr::rational(1, 4).eval_exact(&HashMap::new()), Ok(BigRational::new(1.into(), 4.into())));
+    assert_eq!(Expr::rational(1, 4).diff(x), Expr::integer(0));
+}
@@ -0,0 +1,108 @@
+extern crate symrs;
+
+use std::collections::HashMap;
+use symrs::sym::*;
+
+#[test]
+fn sharing() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let a = RcExpr::from(x) + RcExpr::from(y);
+    let b = RcExpr::from(&(x + y));
+    assert_eq!(a, b);
+    match (a.node(), b.node()) {
+        (RcNode::Sum(a), RcNode::Sum(b)) => assert_eq!(a, b),
+        _ => panic!("not a sum"),
+    }
+    assert_ne!(a, RcExpr::from(y) + RcExpr::from(x));
+
+    let e = RcExpr::from(&((x + y) * Expr::sin(x + y)));
+    assert_eq!(e.node_count(), 5);
+
+    let mut e = RcExpr::from(x);
+    for _ in 0..1000 {
+        e = &e * &e + &e;
+    }
+    assert_eq!(e.node_count(), 2001);
+
+    let nan = RcExpr::from(Expr::Approx(std::f64::NAN));
+    assert_eq!(nan, RcExpr::from(Expr::Approx(std::f64::NAN)));
+    assert_eq!(RcExpr::from(Expr::Approx(-0.0)), RcExpr::from(Expr::Approx(0.0)));
+}
+
+#[test]
+fn conversion() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let exprs = vec![
+        Expr::integer(3),
+        Expr::rational(-1, 2),
+        Expr::Approx(1.5),
+        Expr::pi(),
+        Expr::Undefined,
+        x + y * 2i32 - x.pow(y),
+        Expr::exp(x) / Expr::atan(y),
+        (x + y) * (x + y),
+    ];
+    for e in exprs {
+        let rc = RcExpr::from(&e);
+        assert_eq!(rc.to_expr(), e);
+        assert_eq!(rc.to_string(), e.to_string());
+        assert_eq!(RcExpr::from(rc.to_expr()), rc);
+    }
+}
+
+#[test]
+fn operators() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let rx = RcExpr::from(x);
+    let ry = RcExpr::from(y);
+
+    assert_eq!((&rx + &ry + &rx).to_expr(), x + y + x);
+    assert_eq!((&rx - &ry).to_expr(), x - y);
+    assert_eq!((&rx * &ry * RcExpr::from(2)).to_expr(), x * y * 2i32);
+    assert_eq!((&rx / &ry).to_expr(), x / y);
+    assert_eq!((-&rx).to_expr(), -Expr::from(x));
+    assert_eq!(rx.pow(2).to_expr(), x.pow(2));
+
+    assert_eq!(RcExpr::from(1) + RcExpr::from(2), RcExpr::from(3));
+    assert_eq!(RcExpr::from(1) / RcExpr::from(2), RcExpr::from(&Expr::rational(1, 2)));
+    assert_eq!(-RcExpr::from(2), RcExpr::from(-2));
+    assert_eq!(RcExpr::from(1) / RcExpr::from(0), RcExpr::from(Expr::Undefined));
+
+    let terms = vec![rx.clone(), ry.clone(), RcExpr::from(1)];
+    assert_eq!(RcExpr::sum(terms.clone()), &rx + &ry + RcExpr::from(1));
+    assert_eq!(RcExpr::product(terms).to_expr(), Expr::Product(vec![x.into(), y.into(), 1.into()]));
+}
+
+#[test]
+fn large() {
+    let x = Symbol::new("x");
+
+    let terms = (0..100_000).map(|k| RcExpr::from(x).pow(k)).collect::<Vec<_>>();
+    let e = RcExpr::sum(terms);
+    // The sum, `x`, the powers and their exponents.
+    assert_eq!(e.node_count(), 2 + 2 * 100_000);
+
+    // Deep expressions are dropped without overflowing the stack.
+    let mut e = RcExpr::from(x);
+    for _ in 0..200_000 {
+        e = -e;
+    }
+    drop(e);
+}
+
+#[test]
+fn hashing() {
+    let x = Symbol::new("x");
+
+    let mut counts = HashMap::new();
+    for e in vec![x + 1i32, x * 2i32, x + 1i32] {
+        *counts.entry(RcExpr::from(e)).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&RcExpr::from(&(x + 1i32))], 2);
+    assert_eq!(counts[&(RcExpr::from(x) * RcExpr::from(2))], 1);
+}
@@ -0,0 +1,116 @@
+extern crate num;
+extern crate symrs;
+
+use std::collections::HashMap;
+use num::complex::Complex64;
+use symrs::sym::*;
+
+/// Check that each root is a root of `p` numerically, and return the multiplicities.
+fn check_roots(p: &Expr, x: Symbol) -> Vec<u32> {
+    let res = p.solve_poly(x).unwrap();
+    for &(ref root, _) in &res.roots {
+        let value = root.eval_complex(&HashMap::new()).unwrap();
+        let mut env = HashMap::new();
+        env.insert(x, value);
+        let residual = p.eval_complex(&env).unwrap();
+        assert!(residual.norm() < 1e-9, "{} isn't a root of {}", root, p);
+    }
+    res.roots.iter().map(|r| r.1).collect()
+}
+
+#[test]
+fn rational_roots() {
+    let x = Symbol::new("x");
+
+    let res = ((x - 1) * (2i32 * x + 3).pow(2)).solve_poly(x).unwrap();
+    assert_eq!(res.roots, vec![(1.into(), 1), (Expr::rational(-3, 2), 2)]);
+    assert!(res.unsolved.is_empty());
+
+    assert!(Expr::integer(3).solve_poly(x).unwrap().roots.is_empty());
+    assert!(Expr::integer(0).solve_poly(x).unwrap().roots.is_empty());
+    assert!(Expr::sin(x).solve_poly(x).is_err());
+}
+
+#[test]
+fn radical_roots() {
+    let x = Symbol::new("x");
+
+    let res = (x.pow(2) - 2).solve_poly(x).unwrap();
+    assert_eq!(res.roots, vec![
+        ((-1i32 * Expr::sqrt(2)).simplify(), 1),
+        (Expr::sqrt(2).simplify(), 1),
+    ]);
+
+    assert_eq!(check_roots(&(x.pow(2) + x + 1).simplify(), x), vec![1, 1]);
+    // Three real roots, with trigonometric functions.
+    assert_eq!(check_roots(&(x.pow(3) - 3i32 * x + 1).simplify(), x), vec![1, 1, 1]);
+    // One real root, by Cardano's formula.
+    assert_eq!(check_roots(&(x.pow(3) - 2).simplify(), x), vec![1, 1, 1]);
+    assert_eq!(check_roots(&(x.pow(3) + x + 1).simplify(), x), vec![1, 1, 1]);
+    // Quartics, biquadratic and general.
+    assert_eq!(check_roots(&(x.pow(4) - 5i32 * x.pow(2) + 5).simplify(), x), vec![1; 4]);
+    assert_eq!(check_roots(&(x.pow(4) + x + 1).simplify(), x), vec![1; 4]);
+    assert_eq!(
+        check_roots(&((x.pow(2) - 3).pow(2) * (x + 1)).simplify(), x),
+        vec![1, 2, 2]
+    );
+}
+
+#[test]
+fn unsolved() {
+    let x = Symbol::new("x");
+
+    let res = ((x.pow(5) - x + 1) * (x - 2)).solve_poly(x).unwrap();
+    assert_eq!(res.roots, vec![(2.into(), 1)]);
+    assert_eq!(res.unsolved, vec![((x.pow(5) - x + 1).simplify(), 1)]);
+}
+
+#[test]
+fn symbolic_roots() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+    let b = Symbol::new("b");
+
+    let res = (x.pow(2) + a * x).solve_poly(x).unwrap();
+    assert_eq!(res.roots.len(), 2);
+    assert!(res.roots.contains(&(0.into(), 1)));
+
+    let p = (x.pow(3) + a * x + b).simplify();
+    let res = p.solve_poly(x).unwrap();
+    assert_eq!(res.roots.len(), 3);
+    let mut env = HashMap::new();
+    env.insert(a, Complex64::new(2.0, 0.0));
+    env.insert(b, Complex64::new(-1.0, 0.5));
+    for &(ref root, _) in &res.roots {
+        let value = root.eval_complex(&env).unwrap();
+        env.insert(x, value);
+        assert!(p.eval_complex(&env).unwrap().norm() < 1e-9);
+    }
+}
+
+#[test]
+fn numeric_roots() {
+    let x = Symbol::new("x");
+
+    let roots = (x.pow(2) - 2).nroots(x).unwrap();
+    assert_eq!(roots.len(), 2);
+    assert!((roots[1].value - Complex64::new(2f64.sqrt(), 0.0)).norm() <= roots[1].error);
+    assert!(roots[1].error < 1e-12);
+
+    let p = (x.pow(5) - x + 1).simplify();
+    let roots = p.nroots(x).unwrap();
+    assert_eq!(roots.len(), 5);
+    for root in &roots {
+        let mut env = HashMap::new();
+        env.insert(x, root.value);
+        assert!(p.eval_complex(&env).unwrap().norm() < 1e-12);
+    }
+
+    let roots = (x.pow(3) * (x + 1)).nroots(x).unwrap();
+    assert_eq!(roots[1..].iter().map(|r| r.value).collect::<Vec<_>>(), vec![0.0.into(); 3]);
+    assert!(Expr::integer(2).nroots(x).unwrap().is_empty());
+    assert!((x.pow(2) + Expr::sin(x)).nroots(x).is_err());
+
+    // Non-finite coefficients give non-finite roots without panicking.
+    assert_eq!((x.pow(3) + Expr::approximate(f64::NAN)).nroots(x).unwrap().len(), 3);
+}
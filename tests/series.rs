@@ -0,0 +1,144 @@
+extern crate num;
+extern crate symrs;
+
+mod common;
+
+use symrs::sym::*;
+use common::ratio;
+
+#[test]
+fn taylor() {
+    let x = Symbol::new("x");
+
+    let s = Expr::exp(x).series(x, 0, 4).unwrap();
+    assert_eq!(s.var(), x);
+    assert_eq!(s.point(), &Expr::integer(0));
+    assert_eq!(
+        s.terms(),
+        &[
+            (ratio(0, 1), Expr::integer(1)),
+            (ratio(1, 1), Expr::integer(1)),
+            (ratio(2, 1), Expr::rational(1, 2)),
+            (ratio(3, 1), Expr::rational(1, 6)),
+        ][..]
+    );
+    assert_eq!(s.order(), Order::new(x, 0, ratio(4, 1)));
+    assert_eq!(s.coeff(&ratio(2, 1)), Some(Expr::rational(1, 2)));
+    assert_eq!(s.coeff(&ratio(5, 1)), None);
+
+    let s = Expr::tan(x).series(x, 0, 6).unwrap();
+    assert_eq!(s.to_expr(), (x + x.pow(3) / 3 + 2i32 * x.pow(5) / 15).simplify());
+
+    // Around another point, in powers of `x - 1`.
+    let s = Expr::ln(x).series(x, 1, 3).unwrap();
+    assert_eq!(s.to_expr(), ((x - 1) - (x - 1).pow(2) / 2).simplify());
+    assert_eq!(s.order().to_string(), "O((-1 + x) ^ 3)");
+
+    // Terms cancel.
+    let s = (Expr::sin(x) - x).series(x, 0, 4).unwrap();
+    assert_eq!(s.to_expr(), (-x.pow(3) / 6).simplify());
+    assert_eq!(s.order(), Order::new(x, 0, ratio(4, 1)));
+}
+
+#[test]
+fn laurent_and_puiseux() {
+    let x = Symbol::new("x");
+
+    let s = Expr::sin(x).pow(-1).series(x, 0, 3).unwrap();
+    assert_eq!(s.to_expr(), (x.pow(-1) + x / 6).simplify());
+    assert_eq!(s.to_string(), "x ^ -1 + (1/6) * x + O(x ^ 3)");
+
+    let s = (x.pow(3) + x.pow(4)).pow(Expr::rational(1, 2)).series(x, 0, 3).unwrap();
+    assert_eq!(
+        s.terms(),
+        &[(ratio(3, 2), Expr::integer(1)), (ratio(5, 2), Expr::rational(1, 2))][..]
+    );
+
+    // Logarithms in the coefficients.
+    let s = x.pow(x).series(x, 0, 2).unwrap();
+    assert_eq!(s.to_expr(), (1i32 + x * Expr::ln(x)).simplify());
+
+    // Around infinity, in powers of `1 / x`.
+    let s = (x.pow(2) + 1).pow(Expr::rational(1, 2)) - x;
+    let s = s.series(x, Expr::infinity(), 4).unwrap();
+    assert_eq!(s.to_expr(), (x.pow(-1) / 2 - x.pow(-3) / 8).simplify());
+    assert_eq!(s.order().to_string(), "O(x ^ -4)");
+
+    assert_eq!(Expr::exp(x.pow(-1)).series(x, 0, 3), None);
+}
+
+#[test]
+fn other_symbols() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    let s = Expr::exp(a * x).series(x, 0, 3).unwrap();
+    assert_eq!(s.to_expr(), (1i32 + a * x + a.pow(2) * x.pow(2) / 2).simplify());
+
+    // Symbols can't be confused with the ones used for the expansion.
+    for &name in &["_t", "_t_log", "_t#0", "_t_log#1", "_arg#0", "x_arg"] {
+        let t = Symbol::new(name);
+        let s = (x + t).series(x, 0, 3).unwrap();
+        assert_eq!(s.to_expr(), (x + t).simplify());
+        let s = Expr::sin(x + t).series(x, 0, 2).unwrap();
+        assert_eq!(s.to_expr(), (Expr::sin(t) + Expr::cos(t) * x).simplify());
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let x = Symbol::new("x");
+    let sin = Expr::sin(x).series(x, 0, 6).unwrap();
+    let cos = Expr::cos(x).series(x, 0, 4).unwrap();
+
+    // The lower order wins.
+    let sum = &sin + &cos;
+    assert_eq!(sum.order(), Order::new(x, 0, ratio(4, 1)));
+    assert_eq!(sum, (Expr::sin(x) + Expr::cos(x)).series(x, 0, 4).unwrap());
+
+    let product = &sin * &cos;
+    assert_eq!(product.order(), Order::new(x, 0, ratio(5, 1)));
+    assert_eq!(product, (Expr::sin(x) * Expr::cos(x)).series(x, 0, 5).unwrap());
+
+    let tan = &sin * &cos.pow(-1).unwrap();
+    assert_eq!(tan, Expr::tan(x).series(x, 0, 5).unwrap());
+
+    assert_eq!((&sin - &sin).terms(), &[][..]);
+    assert_eq!((-sin.clone()).to_expr(), (-sin.to_expr()).expand());
+
+    // Adding an order term drops the terms beyond it.
+    let s = sin + Order::new(x, 0, ratio(3, 1));
+    assert_eq!(s.to_string(), "x + O(x ^ 3)");
+    assert_eq!(
+        Order::new(x, 0, ratio(2, 1)) + Order::new(x, 0, ratio(3, 1)),
+        Order::new(x, 0, ratio(2, 1))
+    );
+    assert_eq!((Order::new(x, 0, ratio(2, 1)) + cos.clone()).to_string(), "1 + O(x ^ 2)");
+
+    // Multiplying an order term shifts it by the leading exponent.
+    assert_eq!(cos.clone() * Order::new(x, 0, ratio(2, 1)), Order::new(x, 0, ratio(2, 1)));
+    let s = Expr::from(x).pow(-1).series(x, 0, 2).unwrap();
+    assert_eq!(Order::new(x, 0, ratio(3, 1)) * s, Order::new(x, 0, ratio(2, 1)));
+    let s = (x.pow(2) - x.pow(2)).series(x, 0, 3).unwrap();
+    assert_eq!(s * Order::new(x, 0, ratio(1, 1)), Order::new(x, 0, ratio(4, 1)));
+    assert_eq!(Order::new(x, 0, ratio(2, 1)) * x.pow(3), Order::new(x, 0, ratio(5, 1)));
+    let o = Order::new(x, 0, ratio(1, 2)) * Expr::sin(x).pow(-2);
+    assert_eq!(o, Order::new(x, 0, ratio(-3, 2)));
+    assert_eq!(Order::new(x, 0, ratio(2, 1)) * Expr::cos(x), Order::new(x, 0, ratio(2, 1)));
+    let o = Order::new(x, Expr::infinity(), ratio(1, 1)) * x.pow(2);
+    assert_eq!(o.to_string(), "O(x)");
+}
+
+#[test]
+#[should_panic]
+fn different_points() {
+    let x = Symbol::new("x");
+    let _ = Expr::exp(x).series(x, 0, 2).unwrap() + Expr::exp(x).series(x, 1, 2).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn order_times_zero() {
+    let x = Symbol::new("x");
+    let _ = Order::new(x, 0, ratio(1, 1)) * Expr::integer(0);
+}
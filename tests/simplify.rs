@@ -0,0 +1,98 @@
+extern crate symrs;
+
+use symrs::sym::*;
+
+#[test]
+fn fold_constants() {
+    assert_eq!((Expr::integer(2) * 3).simplify(), Expr::integer(6));
+    assert_eq!((Expr::integer(2) + 3 + 4).simplify(), Expr::integer(9));
+    assert_eq!(Expr::pow(2.into(), 10).simplify(), Expr::integer(1024));
+    assert_eq!((Expr::integer(2) * 1.5).simplify(), Expr::approximate(3));
+    assert_eq!((Expr::integer(1) - 1).simplify(), Expr::integer(0));
+}
+
+#[test]
+fn collect_like_terms() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((x + x).simplify(), Expr::Product(vec![2.into(), x.into()]));
+    assert_eq!((x - x).simplify(), Expr::integer(0));
+    assert_eq!((x * y + 2i32 * (y * x)).simplify(), (3i32 * x * y).simplify());
+    assert_eq!((x * x).simplify(), x.pow(2));
+    assert_eq!((x * x.pow(y)).simplify(), x.pow(1i32 + y).simplify());
+    assert_eq!((x / x).simplify(), Expr::integer(1));
+    assert_eq!((x.pow(2) / x).simplify(), Expr::Sym(x));
+}
+
+#[test]
+fn canonical_order() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((x + 1).simplify(), (1i32 + x).simplify());
+    assert_eq!((x * y).simplify(), (y * x).simplify());
+    assert_eq!((y + x + 1).simplify(), (1i32 + (x + y)).simplify());
+    assert_eq!(
+        (x.pow(2) + x + 1).simplify(),
+        Expr::Sum(vec![1.into(), x.into(), x.pow(2)])
+    );
+}
+
+#[test]
+fn flatten_nested() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    assert_eq!(
+        (x + (y + z)).simplify(),
+        Expr::Sum(vec![x.into(), y.into(), z.into()])
+    );
+    assert_eq!(
+        (x * (y * z)).simplify(),
+        Expr::Product(vec![x.into(), y.into(), z.into()])
+    );
+}
+
+#[test]
+fn normalize_neg_and_ratio() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!((-x).simplify(), Expr::Product(vec![(-1).into(), x.into()]));
+    assert_eq!((-(-x)).simplify(), Expr::Sym(x));
+    assert_eq!((x / y).simplify(), (x * y.pow(-1)).simplify());
+    assert_eq!((x - y).simplify(), (x + -1 * Expr::from(y)).simplify());
+}
+
+#[test]
+fn powers() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(x.pow(0).simplify(), Expr::integer(1));
+    assert_eq!(x.pow(1).simplify(), Expr::Sym(x));
+    assert_eq!(Expr::pow(1.into(), x).simplify(), Expr::integer(1));
+    assert_eq!(x.pow(2).pow(3).simplify(), x.pow(6));
+    assert_eq!((x * y).pow(2).simplify(), (x.pow(2) * y.pow(2)).simplify());
+    assert_eq!(Expr::pow(0.into(), -1).simplify(), Expr::Undefined);
+}
+
+#[test]
+fn undefined_propagates() {
+    let x = Symbol::new("x");
+
+    assert_eq!((x + Expr::Undefined).simplify(), Expr::Undefined);
+    assert_eq!((x * Expr::Undefined).simplify(), Expr::Undefined);
+    assert_eq!(x.pow(Expr::Undefined).simplify(), Expr::Undefined);
+}
+
+#[test]
+fn idempotent() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let e = (x + (4 - (x / 2)) * -Expr::from(y)).pow(2) - 3 * x / y;
+    assert_eq!(e.simplify().simplify(), e.simplify());
+}
@@ -0,0 +1,111 @@
+extern crate symrs;
+
+use std::collections::HashMap;
+use symrs::poly::{solve, SolutionSet, SolveError};
+use symrs::sym::*;
+
+fn map(pairs: Vec<(Symbol, Expr)>) -> HashMap<Symbol, Expr> {
+    pairs.into_iter().map(|(s, e)| (s, e.expand())).collect()
+}
+
+#[test]
+fn equation() {
+    let x = Symbol::new("x");
+
+    let eq = (x + 1).eq_to(2i32 * x);
+    assert_eq!(eq, Equation { lhs: x + 1, rhs: 2i32 * x });
+    assert_eq!(eq.to_expr().simplify(), (1i32 - x).simplify());
+    assert_eq!(x.eq_to(1).simplify(), Equation::new(x, 1));
+    assert_eq!(format!("{}", x.eq_to(1)), "x = 1");
+}
+
+#[test]
+fn single_equation() {
+    let x = Symbol::new("x");
+    let a = Symbol::new("a");
+
+    assert_eq!(
+        solve(&[(3i32 * x + 1).eq_to(x)], &[x]),
+        Ok(SolutionSet::Finite(vec![map(vec![(x, Expr::rational(-1, 2))])]))
+    );
+    // Other symbols are parameters.
+    assert_eq!(
+        solve(&[(a * x).eq_to(1)], &[x]),
+        Ok(SolutionSet::Finite(vec![map(vec![(x, a.pow(-1))])]))
+    );
+    match solve(&[(x.pow(3) - x).eq_to(0)], &[x]) {
+        Ok(SolutionSet::Finite(solutions)) => assert_eq!(solutions.len(), 3),
+        res => panic!("unexpected {:?}", res),
+    }
+
+    assert_eq!(solve(&[x.eq_to(x)], &[x]), Ok(SolutionSet::All));
+    assert_eq!(solve(&[(x + 1).eq_to(x)], &[x]), Ok(SolutionSet::Empty));
+    assert_eq!(
+        solve(&[Expr::sin(x).eq_to(0)], &[x]),
+        Err(SolveError::NotPolynomial(Expr::sin(x)))
+    );
+    assert!(match solve(&[(x.pow(5) - x).eq_to(1)], &[x]) {
+        Err(SolveError::Unsolved(_)) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn linear_system() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+    let a = Symbol::new("a");
+
+    let eqs = [
+        (x + y + z).eq_to(6),
+        (2i32 * x - y).eq_to(0),
+        (x + z / 2).eq_to(Expr::rational(5, 2)),
+    ];
+    assert_eq!(
+        solve(&eqs, &[x, y, z]),
+        Ok(SolutionSet::Finite(vec![map(vec![(x, 1.into()), (y, 2.into()), (z, 3.into())])]))
+    );
+
+    // The right-hand sides may contain parameters.
+    let eqs = [(x + y).eq_to(a), (x - y).eq_to(1)];
+    assert_eq!(
+        solve(&eqs, &[x, y]),
+        Ok(SolutionSet::Finite(vec![map(vec![(x, (a + 1) / 2), (y, (a - 1) / 2)])]))
+    );
+
+    // Underdetermined.
+    let eqs = [(x + y + z).eq_to(1), (x - y).eq_to(0)];
+    assert_eq!(
+        solve(&eqs, &[x, y, z]),
+        Ok(SolutionSet::Parametric {
+            params: vec![z],
+            solution: map(vec![
+                (x, Expr::rational(1, 2) - z / 2),
+                (y, Expr::rational(1, 2) - z / 2),
+            ]),
+        })
+    );
+    let eqs = [(x + y).eq_to(1), (2i32 * x + 2i32 * y).eq_to(2)];
+    assert_eq!(
+        solve(&eqs, &[x, y]),
+        Ok(SolutionSet::Parametric { params: vec![y], solution: map(vec![(x, 1i32 - y)]) })
+    );
+
+    // Inconsistent.
+    let eqs = [(x + y).eq_to(1), (x + y).eq_to(2)];
+    assert_eq!(solve(&eqs, &[x, y]), Ok(SolutionSet::Empty));
+    assert_eq!(solve(&[(x + y).eq_to(a), (x + y).eq_to(1)], &[x, y]), Ok(SolutionSet::Empty));
+
+    assert_eq!(solve(&[], &[x, y]), Ok(SolutionSet::All));
+    assert_eq!(solve(&[(x - x).eq_to(y - y)], &[x, y]), Ok(SolutionSet::All));
+
+    assert_eq!(
+        solve(&[(x * y).eq_to(1), x.eq_to(y)], &[x, y]),
+        Err(SolveError::NonLinear((x * y - 1).expand()))
+    );
+    assert_eq!(
+        solve(&[(a * x).eq_to(1), x.eq_to(y)], &[x, y]),
+        Err(SolveError::NonLinear((a * x - 1).expand()))
+    );
+}
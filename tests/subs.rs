@@ -0,0 +1,48 @@
+extern crate symrs;
+
+use std::collections::HashMap;
+use symrs::sym::*;
+
+#[test]
+fn subs_symbol() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    assert_eq!(Expr::from(x).subs(x, 3), Expr::integer(3));
+    assert_eq!(Expr::from(y).subs(x, 3), Expr::Sym(y));
+    assert_eq!((x * y).subs(x, y), y * y);
+    assert_eq!(x.pow(x).subs(x, 2), Expr::pow(2.into(), 2));
+    assert_eq!((-x / y).subs(y, x + 1), -x / (x + 1));
+    assert_eq!(Expr::Undefined.subs(x, 1), Expr::Undefined);
+}
+
+#[test]
+fn subs_simultaneously() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let z = Symbol::new("z");
+
+    let mut map = HashMap::new();
+    map.insert(x, Expr::from(y));
+    map.insert(y, Expr::from(x));
+    assert_eq!((x + 2 * y).subs_all(&map), y + 2 * x);
+
+    let mut map = HashMap::new();
+    map.insert(x, y + 1);
+    map.insert(y, z + 1);
+    assert_eq!((x * y).subs_all(&map), (y + 1) * (z + 1));
+    assert_eq!((x * y).subs_all(&HashMap::new()), x * y);
+}
+
+#[test]
+fn replace_subtree() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+
+    let e = (x * y).pow(2) + x * y;
+    assert_eq!(e.replace(&(x * y), &2.into()), Expr::pow(2.into(), 2) + 2);
+    assert_eq!(e.replace(&(y * x), &2.into()), e);
+    assert_eq!(e.replace(&e, &x.into()), Expr::Sym(x));
+    // The replacement is not searched again.
+    assert_eq!(e.replace(&x.into(), &(x + 1)), ((x + 1) * y).pow(2) + (x + 1) * y);
+}
@@ -0,0 +1,143 @@
+extern crate symrs;
+
+use std::collections::HashSet;
+use symrs::sym::*;
+
+#[test]
+fn children() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let all = |e: &Expr| e.children().cloned().collect::<Vec<_>>();
+
+    assert_eq!(all(&Expr::integer(1)), vec![]);
+    assert_eq!(all(&Expr::negative(x)), vec![x.into()]);
+    assert_eq!(all(&(x + y + 1)), vec![x.into(), y.into(), Expr::integer(1)]);
+    assert_eq!(all(&(x / y)), vec![x.into(), y.into()]);
+    assert_eq!(all(&x.pow(y)), vec![x.into(), y.into()]);
+    assert_eq!(all(&Expr::sin(x)), vec![x.into()]);
+    assert_eq!((x * y * 2i32).children().len(), 3);
+
+    let mut e = x.pow(y) + 1;
+    for c in e.children_mut() {
+        *c = Expr::from(x);
+    }
+    assert_eq!(e, x + x);
+
+    let e = (x + 1) / Expr::sin(y);
+    assert_eq!(e.map_children(|c| Expr::negative(c.clone())), -(x + 1) / -Expr::sin(y));
+    assert_eq!(Expr::from(x).map_children(|_| y.into()), x.into());
+}
+
+#[test]
+fn iterators() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let e = Expr::sin(x + y) * x.pow(2);
+
+    let pre = e.iter_preorder().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        pre,
+        vec![
+            e.clone(),
+            Expr::sin(x + y),
+            x + y,
+            x.into(),
+            y.into(),
+            x.pow(2),
+            x.into(),
+            Expr::integer(2),
+        ]
+    );
+    let post = e.iter_postorder().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        post,
+        vec![
+            x.into(),
+            y.into(),
+            x + y,
+            Expr::sin(x + y),
+            x.into(),
+            Expr::integer(2),
+            x.pow(2),
+            e.clone(),
+        ]
+    );
+
+    let symbols = e.iter_preorder().filter_map(|e| match *e {
+        Expr::Sym(s) => Some(s),
+        _ => None,
+    });
+    assert_eq!(symbols.collect::<HashSet<_>>(), vec![x, y].into_iter().collect());
+
+    let mut deep = Expr::from(x);
+    for _ in 0..10000 {
+        deep = Expr::negative(deep);
+    }
+    assert_eq!(deep.iter_preorder().count(), 10001);
+    assert_eq!(deep.iter_postorder().count(), 10001);
+}
+
+struct Depth {
+    current: usize,
+    max: usize,
+}
+
+impl Visitor for Depth {
+    fn visit(&mut self, e: &Expr) {
+        self.current += 1;
+        self.max = self.max.max(self.current);
+        self.visit_children(e);
+        self.current -= 1;
+    }
+}
+
+struct Rename(Symbol, Symbol);
+
+impl VisitorMut for Rename {
+    fn visit_mut(&mut self, e: &mut Expr) {
+        match *e {
+            Expr::Sym(ref mut s) if *s == self.0 => *s = self.1,
+            _ => self.visit_children_mut(e),
+        }
+    }
+}
+
+struct ExpandSquares;
+
+impl Folder for ExpandSquares {
+    fn fold(&mut self, e: &Expr) -> Expr {
+        match *e {
+            Expr::Pow(ref b, ref p) if **p == Expr::integer(2) => {
+                let b = self.fold(b);
+                b.clone() * b
+            }
+            _ => self.fold_children(e),
+        }
+    }
+}
+
+struct Identity;
+
+impl Visitor for Identity {}
+
+impl Folder for Identity {}
+
+#[test]
+fn traits() {
+    let x = Symbol::new("x");
+    let y = Symbol::new("y");
+    let e = Expr::sin(x + y.pow(2)) / x;
+
+    let mut depth = Depth { current: 0, max: 0 };
+    depth.visit(&e);
+    assert_eq!(depth.max, 5);
+    Identity.visit(&e);
+
+    let mut renamed = e.clone();
+    Rename(x, y).visit_mut(&mut renamed);
+    assert_eq!(renamed, Expr::sin(y + y.pow(2)) / y);
+
+    assert_eq!(ExpandSquares.fold(&e), Expr::sin(x + y * y) / x);
+    assert_eq!(ExpandSquares.fold(&(x + 1).pow(2).pow(2)), (x + 1) * (x + 1) * ((x + 1) * (x + 1)));
+    assert_eq!(Identity.fold(&e), e);
+}